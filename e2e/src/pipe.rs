@@ -1,10 +1,13 @@
 #[cfg(feature = "itest")]
 #[cfg(test)]
 mod tests {
-    use crate::utils::{build_client, wait};
+    use crate::utils::{build_client, read_unit_configuration, wait};
     use pipebased_common::grpc::daemon::{
-        AppDescriptor, CatalogsDescriptor, CreatePipeRequest, EnvironmentVariable, ListPipeRequest,
-        PullAppRequest, PullCatalogsRequest, RemovePipeRequest, StartPipeRequest, StopPipeRequest,
+        AppDescriptor, ApplyPipesRequest, CatalogsDescriptor, CreatePipeRequest, DiagnoseRequest,
+        DisablePipeRequest, EnvironmentVariable, GetPipeStatsRequest, InspectPipeRequest,
+        KillPipeRequest, LimitDescriptor, ListPipeRequest, ListPipeRunsRequest, PipeDependency,
+        PullAppRequest, PullCatalogsRequest, RemovePipeRequest, SecurityDescriptor,
+        StartPipeRequest, StopDescriptor, StopPipeRequest,
     };
 
     const TEST_CLI_CONFIG_FILE_PATH: &str = "resources/cli.yml";
@@ -20,6 +23,22 @@ mod tests {
     const TEST_ENV_FORMATTER_VALUE: &str = "json";
     const TEST_ENV_RUST_LOG_KEY: &str = "RUST_LOG";
     const TEST_ENV_RUST_LOG_VALUE: &str = "info";
+    const TEST_ENV_MESSAGE_KEY: &str = "PIPEBASE_TEST_MESSAGE";
+    const TEST_ENV_MESSAGE_VALUE: &str = r#"hello "pipe" 100%"#;
+    const TEST_ENV_TOKEN_KEY: &str = "PIPEBASE_TEST_TOKEN";
+    const TEST_ENV_TOKEN_VALUE: &str = "secret";
+    const TEST_ENV_FILE: &str = "-/etc/pipebase/timer.env";
+    const TEST_LABEL_ENV_KEY: &str = "env";
+    const TEST_LABEL_ENV_VALUE: &str = "dev";
+    const TEST_LABEL_TEAM_KEY: &str = "team";
+    const TEST_LABEL_TEAM_VALUE: &str = "ingest";
+    const TEST_UNIT_NAME: &str = "pipebase-pipebase.dev.timer.service";
+    const TEST_SLICE: &str = "pipebase.slice";
+    const TEST_MEMORY_MAX: &str = "512M";
+    const TEST_CPU_QUOTA: &str = "50%";
+    const TEST_STOP_TIMEOUT: &str = "10s";
+    const TEST_KILL_MODE: &str = "mixed";
+    const TEST_UNKNOWN_PIPE_ID: &str = "pipebase.dev.unknown";
 
     #[tokio::test]
     async fn test_pipe() {
@@ -44,92 +63,316 @@ mod tests {
             })
             .await
             .expect("pull catalogs failed");
+        // dependency must be registered
+        let mut request = build_create_test_pipe_request();
+        request.depends_on = vec![PipeDependency {
+            id: String::from(TEST_UNKNOWN_PIPE_ID),
+            dependency_type: None,
+        }];
+        assert!(client.create_pipe(request).await.is_err());
         // create pipe
         println!("create pipe ...");
         client
             .create_pipe(build_create_test_pipe_request())
             .await
             .expect("create pipe failed");
+        // check environment block in unit configuration
+        let unit = read_unit_configuration(TEST_UNIT_NAME).expect("read unit failed");
+        let envs: Vec<&str> = unit
+            .lines()
+            .filter(|line| line.starts_with("Environment"))
+            .collect();
+        assert_eq!(
+            vec![
+                r#"Environment="PIPEBASE_LOG_FORMATTER=json""#,
+                r#"Environment="RUST_LOG=info""#,
+                r#"Environment="PIPEBASE_TEST_MESSAGE=hello \"pipe\" 100%%""#,
+                r#"Environment="PIPEBASE_TEST_TOKEN=secret""#,
+                "EnvironmentFile=-/etc/pipebase/timer.env",
+            ],
+            envs
+        );
+        // check pipe id marked in unit configuration
+        assert!(unit
+            .lines()
+            .any(|line| line == "X-Pipebase-Id=pipebase.dev.timer"));
+        // check resource limits in unit configuration
+        assert!(unit.lines().any(|line| line == "MemoryMax=512M"));
+        assert!(unit.lines().any(|line| line == "CPUQuota=50%"));
+        // check security preset in unit configuration
+        assert!(unit.lines().any(|line| line == "ProtectSystem=full"));
+        assert!(unit.lines().any(|line| line == "ProtectHome=read-only"));
+        assert!(unit.lines().any(|line| line == "PrivateTmp=yes"));
+        assert!(unit.lines().any(|line| line == "NoNewPrivileges=yes"));
+        assert!(unit.lines().any(|line| line.starts_with("ReadWritePaths=")));
+        // check slice in unit configuration
+        assert!(unit
+            .lines()
+            .any(|line| line == format!("Slice={}", TEST_SLICE)));
+        // check install section in unit configuration
+        assert!(unit
+            .lines()
+            .any(|line| line == "WantedBy=multi-user.target"));
+        // check default service type in unit configuration
+        assert!(unit.lines().any(|line| line == "Type=simple"));
+        // check accounting in unit configuration
+        assert!(unit.lines().any(|line| line == "CPUAccounting=yes"));
+        assert!(unit.lines().any(|line| line == "MemoryAccounting=yes"));
+        // check graceful stop in unit configuration
+        assert!(unit
+            .lines()
+            .any(|line| line == format!("TimeoutStopSec={}", TEST_STOP_TIMEOUT)));
+        assert!(unit
+            .lines()
+            .any(|line| line == format!("KillMode={}", TEST_KILL_MODE)));
+        assert!(unit.lines().any(|line| line == "KillSignal=SIGINT"));
         // wait for configuration loaded
         wait(1000).await;
         // check pipe status
         let resp = client
-            .list_pipe(ListPipeRequest {})
+            .list_pipe(ListPipeRequest { selector: None })
             .await
             .expect("list pipe failed")
             .into_inner();
         let pipes = resp.pipes;
         assert_eq!(1, pipes.len());
-        let pipe = pipes.get(0).expect("pipe state not found");
+        let pipe = pipes.first().expect("pipe state not found");
         assert_eq!("loaded", pipe.load_state.as_str());
         assert_eq!("inactive", pipe.active_state.as_str());
         assert_eq!("dead", pipe.sub_state.as_str());
+        assert!(pipe.enabled);
+        // not scheduled
+        assert!(pipe.next_trigger.is_none());
+        assert!(pipe.last_trigger.is_none());
+        // no probe defined
+        assert!(pipe.liveness.is_none());
+        assert!(pipe.readiness.is_none());
+        assert_eq!(
+            Some(TEST_LABEL_ENV_VALUE),
+            pipe.labels.get(TEST_LABEL_ENV_KEY).map(String::as_str)
+        );
+        // filter pipes with label selector
+        let selected = |selector: &str| ListPipeRequest {
+            selector: Some(String::from(selector)),
+        };
+        let resp = client
+            .list_pipe(selected("env=dev,team in (ingest,etl)"))
+            .await
+            .expect("list pipe failed")
+            .into_inner();
+        assert_eq!(1, resp.pipes.len());
+        let resp = client
+            .list_pipe(selected("env!=dev"))
+            .await
+            .expect("list pipe failed")
+            .into_inner();
+        assert_eq!(0, resp.pipes.len());
+        let resp = client
+            .list_pipe(selected("!team"))
+            .await
+            .expect("list pipe failed")
+            .into_inner();
+        assert_eq!(0, resp.pipes.len());
+        // apply same pipe is unchanged
+        let resp = client
+            .apply_pipes(ApplyPipesRequest {
+                pipes: vec![build_create_test_pipe_request()],
+                dry_run: true,
+                prune: true,
+                timeout: None,
+            })
+            .await
+            .expect("apply pipes failed")
+            .into_inner();
+        assert_eq!(1, resp.changes.len());
+        let change = resp.changes.first().expect("pipe change not found");
+        assert_eq!("unchanged", change.change_type.as_str());
+        assert!(!change.applied);
+        // no drift between registers and files
+        let resp = client
+            .diagnose(DiagnoseRequest {
+                repair: false,
+                purge: false,
+            })
+            .await
+            .expect("diagnose failed")
+            .into_inner();
+        assert!(resp.drifts.is_empty());
         // start pipe
         println!("start pipe ...");
-        client
+        let resp = client
             .start_pipe(StartPipeRequest {
                 id: String::from(TEST_PIPE_ID),
+                wait: true,
+                timeout: None,
+                mode: Some(String::from("fail")),
             })
             .await
-            .expect("start pipe failed");
-        // wait for service status change
-        wait(1000).await;
+            .expect("start pipe failed")
+            .into_inner();
+        assert_eq!(Some("done"), resp.job_result.as_deref());
         // check pipe status
         let resp = client
-            .list_pipe(ListPipeRequest {})
+            .list_pipe(ListPipeRequest { selector: None })
             .await
             .expect("list pipe failed")
             .into_inner();
         let pipes = resp.pipes;
         assert_eq!(1, pipes.len());
-        let pipe = pipes.get(0).expect("pipe state not found");
+        let pipe = pipes.first().expect("pipe state not found");
         assert_eq!("loaded", pipe.load_state.as_str());
         assert_eq!("active", pipe.active_state.as_str());
         assert_eq!("running", pipe.sub_state.as_str());
+        // inspect pipe
+        let inspection = client
+            .inspect_pipe(InspectPipeRequest {
+                id: String::from(TEST_PIPE_ID),
+            })
+            .await
+            .expect("inspect pipe failed")
+            .into_inner();
+        assert_eq!(TEST_PIPE_DESCRIPTION, inspection.description.as_str());
+        assert_eq!(TEST_USER, inspection.user.as_str());
+        assert_eq!(TEST_GROUP, inspection.group.as_str());
+        assert_eq!(
+            Some(build_app_descriptor(
+                TEST_NAMESPACE,
+                TEST_PROJECT_ID,
+                TEST_BUILD_VERSION
+            )),
+            inspection.app
+        );
+        assert_eq!(
+            Some(build_catalogs_descriptor(
+                TEST_NAMESPACE,
+                TEST_PROJECT_ID,
+                TEST_CATALOGS_VERSION
+            )),
+            inspection.catalogs
+        );
+        assert!(inspection.main_pid > 0);
+        assert!(inspection.active_enter_timestamp > 0);
+        assert!(inspection.unit_path.ends_with(TEST_UNIT_NAME));
+        // secret env value masked
+        let token = inspection
+            .envs
+            .iter()
+            .find(|env| env.key == TEST_ENV_TOKEN_KEY)
+            .expect("token env not found");
+        assert_ne!(TEST_ENV_TOKEN_VALUE, token.value.as_str());
+        // no run history for simple pipe
+        let runs = client
+            .list_pipe_runs(ListPipeRunsRequest {
+                id: String::from(TEST_PIPE_ID),
+            })
+            .await
+            .expect("list pipe runs failed")
+            .into_inner()
+            .runs;
+        assert!(runs.is_empty());
+        // resource usage of running pipe
+        let stats = client
+            .get_pipe_stats(GetPipeStatsRequest {
+                ids: vec![String::from(TEST_PIPE_ID)],
+            })
+            .await
+            .expect("get pipe stats failed")
+            .into_inner()
+            .stats;
+        assert_eq!(1, stats.len());
+        assert!(stats[0].memory_current.is_some());
+        assert!(stats[0].cpu_usage_nsec.is_some());
+        // invalid signal rejected
+        assert!(client
+            .kill_pipe(KillPipeRequest {
+                id: String::from(TEST_PIPE_ID),
+                signal: String::from("SIGFOO"),
+                whom: None,
+            })
+            .await
+            .is_err());
+        // continue signal leaves pipe running
+        client
+            .kill_pipe(KillPipeRequest {
+                id: String::from(TEST_PIPE_ID),
+                signal: String::from("SIGCONT"),
+                whom: Some(String::from("main")),
+            })
+            .await
+            .expect("kill pipe failed");
         // wait for 5 seconds, pipe is still running, since we count down with 10s
         wait(5000).await;
         println!("pipe status check ...");
         let resp = client
-            .list_pipe(ListPipeRequest {})
+            .list_pipe(ListPipeRequest { selector: None })
             .await
             .expect("list pipe failed")
             .into_inner();
         let pipes = resp.pipes;
         assert_eq!(1, pipes.len());
-        let pipe = pipes.get(0).expect("pipe state not found");
+        let pipe = pipes.first().expect("pipe state not found");
         assert_eq!("loaded", pipe.load_state.as_str());
         assert_eq!("active", pipe.active_state.as_str());
         assert_eq!("running", pipe.sub_state.as_str());
+        // disable pipe
+        println!("disable pipe ...");
+        client
+            .disable_pipe(DisablePipeRequest {
+                id: String::from(TEST_PIPE_ID),
+            })
+            .await
+            .expect("disable pipe failed");
+        // isolate not allowed for stop
+        client
+            .stop_pipe(StopPipeRequest {
+                id: String::from(TEST_PIPE_ID),
+                wait: false,
+                timeout: None,
+                mode: Some(String::from("isolate")),
+            })
+            .await
+            .expect_err("stop pipe with isolate mode should fail");
         // stop pipe
         println!("stop pipe ...");
-        client
+        let resp = client
             .stop_pipe(StopPipeRequest {
                 id: String::from(TEST_PIPE_ID),
+                wait: true,
+                timeout: None,
+                mode: Some(String::from("fail")),
             })
             .await
-            .expect("stop pipe failed");
+            .expect("stop pipe failed")
+            .into_inner();
+        assert_eq!(Some("done"), resp.job_result.as_deref());
+        let exit = resp.exit.expect("pipe exit not found");
+        assert!(!exit.sigkilled);
         // check pipe status
         let resp = client
-            .list_pipe(ListPipeRequest {})
+            .list_pipe(ListPipeRequest { selector: None })
             .await
             .expect("list pipe failed")
             .into_inner();
         let pipes = resp.pipes;
         assert_eq!(1, pipes.len());
-        let pipe = pipes.get(0).expect("pipe state not found");
+        let pipe = pipes.first().expect("pipe state not found");
         assert_eq!("loaded", pipe.load_state.as_str());
         assert_eq!("inactive", pipe.active_state.as_str());
         assert_eq!("dead", pipe.sub_state.as_str());
+        assert!(!pipe.enabled);
         // remove pipe
         println!("remove pipe ...");
         client
             .remove_pipe(RemovePipeRequest {
                 id: String::from(TEST_PIPE_ID),
+                force: false,
+                keep_data: false,
+                purge_failed: true,
             })
             .await
             .expect("remove pipe failed");
         let resp = client
-            .list_pipe(ListPipeRequest {})
+            .list_pipe(ListPipeRequest { selector: None })
             .await
             .expect("list pipe failed")
             .into_inner();
@@ -171,7 +414,41 @@ mod tests {
             envs: build_envs(&[
                 (TEST_ENV_FORMATTER_KEY, TEST_ENV_FORMATTER_VALUE),
                 (TEST_ENV_RUST_LOG_KEY, TEST_ENV_RUST_LOG_VALUE),
+                (TEST_ENV_MESSAGE_KEY, TEST_ENV_MESSAGE_VALUE),
+                (TEST_ENV_TOKEN_KEY, TEST_ENV_TOKEN_VALUE),
             ]),
+            env_files: vec![String::from(TEST_ENV_FILE)],
+            restart: None,
+            stop: Some(StopDescriptor {
+                timeout: Some(String::from(TEST_STOP_TIMEOUT)),
+                kill_signal: Some(String::from("INT")),
+                kill_mode: Some(String::from(TEST_KILL_MODE)),
+                ..Default::default()
+            }),
+            limits: Some(LimitDescriptor {
+                memory_max: Some(String::from(TEST_MEMORY_MAX)),
+                cpu_quota: Some(String::from(TEST_CPU_QUOTA)),
+                ..Default::default()
+            }),
+            // hardening is opt-in
+            security: Some(SecurityDescriptor {
+                preset: Some(String::from("default")),
+                ..Default::default()
+            }),
+            autostart: Some(true),
+            labels: [
+                (TEST_LABEL_ENV_KEY, TEST_LABEL_ENV_VALUE),
+                (TEST_LABEL_TEAM_KEY, TEST_LABEL_TEAM_VALUE),
+            ]
+            .iter()
+            .map(|&(key, value)| (String::from(key), String::from(value)))
+            .collect(),
+            depends_on: vec![],
+            schedule: None,
+            service_type: None,
+            remain_after_exit: None,
+            liveness: None,
+            readiness: None,
             app: Some(build_app_descriptor(
                 TEST_NAMESPACE,
                 TEST_PROJECT_ID,
@@ -42,7 +42,7 @@ mod tests {
             .into_inner();
         let apps = resp.apps;
         assert_eq!(1, apps.len());
-        let app = apps.get(0).expect("no local app found");
+        let app = apps.first().expect("no local app found");
         assert_eq!("dev", app.namespace);
         assert_eq!("timer", app.id);
         assert_eq!(0, app.version);
@@ -62,7 +62,7 @@ mod tests {
             .into_inner();
         let catalogss = resp.catalogss;
         assert_eq!(1, catalogss.len());
-        let catalogs = catalogss.get(0).expect("no local catalogs found");
+        let catalogs = catalogss.first().expect("no local catalogs found");
         assert_eq!("dev", catalogs.namespace);
         assert_eq!("timer", catalogs.id);
         assert_eq!(0, catalogs.version);
@@ -3,12 +3,14 @@ use pipebased_common::{
         client::{DaemonClientBuilder, RpcClientConfig},
         daemon::daemon_client::DaemonClient,
     },
-    read_yml, Result,
+    read_file, read_yml, PathBuilder, Result,
 };
 use std::path::Path;
 use tokio::time::{sleep, Duration};
 use tonic::transport::Channel;
 
+const SYSTEMD_UNIT_CONFIGURATION_DIRECTORY: &str = "/etc/systemd/system";
+
 #[allow(dead_code)]
 pub(crate) async fn build_client<P>(path: P) -> Result<DaemonClient<Channel>>
 where
@@ -28,3 +30,14 @@ where
 pub async fn wait(millis: u64) {
     sleep(Duration::from_millis(millis)).await;
 }
+
+#[allow(dead_code)]
+pub(crate) fn read_unit_configuration(unit_name: &str) -> Result<String> {
+    let path = PathBuilder::default()
+        .push(SYSTEMD_UNIT_CONFIGURATION_DIRECTORY)
+        .push(unit_name)
+        .build();
+    let buffer = read_file(path)?;
+    let unit = String::from_utf8(buffer)?;
+    Ok(unit)
+}
@@ -0,0 +1,44 @@
+use super::Cmd;
+use crate::ops::{do_pipe, print::PrintRecords};
+use clap::Arg;
+use pipebased_common::{grpc::daemon::daemon_client::DaemonClient, Result};
+use tonic::transport::Channel;
+
+pub fn apply_pipes() -> Cmd {
+    Cmd::new("apply")
+        .about("apply pipes in compose file, create, update or remove pipes to match")
+        .args(vec![
+            Arg::new("file")
+                .short('f')
+                .long("file")
+                .help("Specify path to pipe compose file")
+                .required(true)
+                .takes_value(true),
+            Arg::new("dry-run")
+                .long("dry-run")
+                .help("Print plan without applying"),
+            Arg::new("prune")
+                .long("prune")
+                .help("Remove pipes not declared in compose file"),
+            Arg::new("timeout")
+                .short('t')
+                .long("timeout")
+                .help("Specify seconds to wait for updated pipe running before rollback")
+                .takes_value(true),
+        ])
+}
+
+pub async fn exec_apply_pipes(
+    mut client: DaemonClient<Channel>,
+    args: &clap::ArgMatches,
+) -> Result<()> {
+    let file = args.value_of("file").unwrap();
+    let dry_run = args.is_present("dry-run");
+    let prune = args.is_present("prune");
+    let timeout: Option<u64> = args
+        .value_of("timeout")
+        .map(|timeout| timeout.parse().expect("invalid timeout"));
+    let response = do_pipe::apply_pipes(&mut client, file, dry_run, prune, timeout).await?;
+    response.print_records();
+    Ok(())
+}
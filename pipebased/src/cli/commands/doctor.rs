@@ -0,0 +1,32 @@
+use super::Cmd;
+use crate::ops::{do_pipe, print::PrintRecords};
+use clap::Arg;
+use pipebased_common::{grpc::daemon::daemon_client::DaemonClient, Result};
+use tonic::transport::Channel;
+
+pub fn diagnose() -> Cmd {
+    Cmd::new("doctor")
+        .about("Diagnose drift between registers, working directories and unit files")
+        .args(vec![
+            Arg::new("repair")
+                .short('r')
+                .long("repair")
+                .help("Repair drifts found"),
+            Arg::new("purge")
+                .short('p')
+                .long("purge")
+                .help("Delete orphan working directories and unit files at repair")
+                .requires("repair"),
+        ])
+}
+
+pub async fn exec_diagnose(
+    mut client: DaemonClient<Channel>,
+    args: &clap::ArgMatches,
+) -> Result<()> {
+    let repair = args.is_present("repair");
+    let purge = args.is_present("purge");
+    let response = do_pipe::diagnose(&mut client, repair, purge).await?;
+    response.print_records();
+    Ok(())
+}
@@ -0,0 +1,25 @@
+use super::Cmd;
+use crate::ops::do_pipe;
+use clap::Arg;
+use pipebased_common::{grpc::daemon::daemon_client::DaemonClient, Result};
+use tonic::transport::Channel;
+
+pub fn enable_pipe() -> Cmd {
+    Cmd::new("enable")
+        .about("enable pipe to start at boot")
+        .arg(
+            Arg::new("id")
+                .help("Specify pipe id")
+                .required(true)
+                .index(1),
+        )
+}
+
+pub async fn exec_enable_pipe(
+    mut client: DaemonClient<Channel>,
+    args: &clap::ArgMatches,
+) -> Result<()> {
+    let id = args.value_of("id").unwrap();
+    let _ = do_pipe::enable_pipe(&mut client, id.to_owned()).await?;
+    Ok(())
+}
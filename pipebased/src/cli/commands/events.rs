@@ -0,0 +1,29 @@
+use super::Cmd;
+use crate::ops::{do_pipe, print::PrintRecords};
+use clap::Arg;
+use pipebased_common::{grpc::daemon::daemon_client::DaemonClient, Result};
+use tonic::transport::Channel;
+
+pub fn watch_pipes() -> Cmd {
+    Cmd::new("events").about("watch pipe events").arg(
+        Arg::new("id")
+            .help("Specify pipe ids, watch all pipes if not given")
+            .multiple_values(true)
+            .index(1),
+    )
+}
+
+pub async fn exec_watch_pipes(
+    mut client: DaemonClient<Channel>,
+    args: &clap::ArgMatches,
+) -> Result<()> {
+    let ids: Vec<String> = match args.values_of("id") {
+        Some(ids) => ids.map(String::from).collect(),
+        None => vec![],
+    };
+    let mut events = do_pipe::watch_pipes(&mut client, ids).await?;
+    while let Some(event) = events.message().await? {
+        event.print_records();
+    }
+    Ok(())
+}
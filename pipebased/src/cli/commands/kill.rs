@@ -0,0 +1,39 @@
+use super::Cmd;
+use crate::ops::do_pipe;
+use clap::Arg;
+use pipebased_common::{grpc::daemon::daemon_client::DaemonClient, Result};
+use tonic::transport::Channel;
+
+pub fn kill_pipe() -> Cmd {
+    Cmd::new("kill")
+        .about("send signal to pipe processes")
+        .args(vec![
+            Arg::new("id")
+                .help("Specify pipe id")
+                .required(true)
+                .index(1),
+            Arg::new("signal")
+                .short('s')
+                .long("signal")
+                .help("Specify signal name or number, i.e 'SIGUSR1', 'USR1', '10'")
+                .takes_value(true)
+                .default_value("SIGTERM"),
+            Arg::new("whom")
+                .short('w')
+                .long("whom")
+                .help("Specify processes to signal")
+                .takes_value(true)
+                .possible_values(["main", "control", "all"]),
+        ])
+}
+
+pub async fn exec_kill_pipe(
+    mut client: DaemonClient<Channel>,
+    args: &clap::ArgMatches,
+) -> Result<()> {
+    let id = args.value_of("id").unwrap();
+    let signal = args.value_of("signal").unwrap();
+    let whom = args.value_of("whom").map(String::from);
+    let _ = do_pipe::kill_pipe(&mut client, id.to_owned(), signal.to_owned(), whom).await?;
+    Ok(())
+}
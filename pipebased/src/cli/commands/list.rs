@@ -1,10 +1,17 @@
 use super::Cmd;
 use crate::ops::{do_app, do_catalogs, do_pipe, print::PrintRecords};
+use clap::Arg;
 use pipebased_common::{grpc::daemon::daemon_client::DaemonClient, Result};
 use tonic::transport::Channel;
 
 pub fn list_pipe() -> Cmd {
-    Cmd::new("ps").about("List pipe instance")
+    Cmd::new("ps").about("List pipe instance").arg(
+        Arg::new("selector")
+            .short('l')
+            .long("selector")
+            .help("Filter by label selector, i.e 'env=prod,team!=ingest,tier in (a,b),!canary'")
+            .takes_value(true),
+    )
 }
 
 pub fn list_app() -> Cmd {
@@ -15,8 +22,12 @@ pub fn list_catalogs() -> Cmd {
     Cmd::new("catalogs").about("List catalogs manifest")
 }
 
-pub async fn exec_list_pipe(mut client: DaemonClient<Channel>) -> Result<()> {
-    let response = do_pipe::list_pipe(&mut client).await?;
+pub async fn exec_list_pipe(
+    mut client: DaemonClient<Channel>,
+    args: &clap::ArgMatches,
+) -> Result<()> {
+    let selector = args.value_of("selector").map(String::from);
+    let response = do_pipe::list_pipe(&mut client, selector).await?;
     response.print_records();
     Ok(())
 }
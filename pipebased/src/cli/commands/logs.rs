@@ -0,0 +1,65 @@
+use super::Cmd;
+use crate::ops::{do_pipe, print::PrintRecords};
+use clap::Arg;
+use pipebased_common::{
+    grpc::daemon::{daemon_client::DaemonClient, StreamPipeLogsRequest},
+    Result,
+};
+use tonic::transport::Channel;
+
+pub fn stream_pipe_logs() -> Cmd {
+    Cmd::new("logs").about("show pipe logs").args(vec![
+        Arg::new("id")
+            .help("Specify pipe id")
+            .required(true)
+            .index(1),
+        Arg::new("tail")
+            .short('n')
+            .long("tail")
+            .help("Specify number of most recent entries to show")
+            .takes_value(true),
+        Arg::new("since")
+            .short('s')
+            .long("since")
+            .help("Show entries since time, i.e '2022-01-01 00:00:00', 'yesterday', '-1h'")
+            .takes_value(true),
+        Arg::new("until")
+            .short('u')
+            .long("until")
+            .help("Show entries until time")
+            .takes_value(true)
+            .conflicts_with("follow"),
+        Arg::new("follow")
+            .short('f')
+            .long("follow")
+            .help("Follow new entries"),
+        Arg::new("priority")
+            .short('p')
+            .long("priority")
+            .help("Filter by priority level or range, i.e 'err', '3', 'warning..err'")
+            .takes_value(true),
+    ])
+}
+
+pub async fn exec_stream_pipe_logs(
+    mut client: DaemonClient<Channel>,
+    args: &clap::ArgMatches,
+) -> Result<()> {
+    let id = args.value_of("id").unwrap();
+    let tail: Option<u64> = args
+        .value_of("tail")
+        .map(|tail| tail.parse().expect("invalid tail"));
+    let request = StreamPipeLogsRequest {
+        id: id.to_owned(),
+        tail,
+        since: args.value_of("since").map(String::from),
+        until: args.value_of("until").map(String::from),
+        follow: args.is_present("follow"),
+        priority: args.value_of("priority").map(String::from),
+    };
+    let mut entries = do_pipe::stream_pipe_logs(&mut client, request).await?;
+    while let Some(entry) = entries.message().await? {
+        entry.print_records();
+    }
+    Ok(())
+}
@@ -1,9 +1,22 @@
+pub mod apply;
 pub mod create;
+pub mod disable;
+pub mod doctor;
+pub mod enable;
+pub mod events;
+pub mod inspect;
+pub mod kill;
 pub mod list;
+pub mod logs;
 pub mod pull;
+pub mod reload;
 pub mod remove;
+pub mod restart;
+pub mod runs;
 pub mod start;
+pub mod stats;
 pub mod stop;
+pub mod update;
 
 use pipebased_common::{grpc::daemon::daemon_client::DaemonClient, Result};
 use tonic::transport::Channel;
@@ -12,33 +25,59 @@ pub type Cmd = clap::App<'static>;
 
 pub fn cmds() -> Vec<Cmd> {
     vec![
+        apply::apply_pipes(),
         create::create_pipe(),
+        disable::disable_pipe(),
+        doctor::diagnose(),
+        enable::enable_pipe(),
+        events::watch_pipes(),
+        inspect::inspect_pipe(),
+        kill::kill_pipe(),
         list::list_pipe(),
         list::list_app(),
         list::list_catalogs(),
+        logs::stream_pipe_logs(),
         pull::pull_app(),
         pull::pull_catalogs(),
         remove::remove_pipe(),
         remove::remove_app(),
         remove::remove_catalogs(),
+        restart::restart_pipe(),
+        runs::list_pipe_runs(),
+        reload::reload_pipe(),
         start::start_pipe(),
+        stats::get_pipe_stats(),
         stop::stop_pipe(),
+        update::update_pipe(),
     ]
 }
 
 pub async fn exec(cmd: &str, client: DaemonClient<Channel>, args: &clap::ArgMatches) -> Result<()> {
     match cmd {
+        "apply" => apply::exec_apply_pipes(client, args).await,
         "create" => create::exec_create_pipe(client, args).await,
-        "ps" => list::exec_list_pipe(client).await,
+        "disable" => disable::exec_disable_pipe(client, args).await,
+        "doctor" => doctor::exec_diagnose(client, args).await,
+        "enable" => enable::exec_enable_pipe(client, args).await,
+        "events" => events::exec_watch_pipes(client, args).await,
+        "inspect" => inspect::exec_inspect_pipe(client, args).await,
+        "kill" => kill::exec_kill_pipe(client, args).await,
+        "ps" => list::exec_list_pipe(client, args).await,
         "apps" => list::exec_list_app(client).await,
         "catalogs" => list::exec_list_catalogs(client).await,
+        "logs" => logs::exec_stream_pipe_logs(client, args).await,
         "pulla" => pull::exec_pull_app(client, args).await,
         "pullc" => pull::exec_pull_catalogs(client, args).await,
         "rm" => remove::exec_remove_pipe(client, args).await,
         "rma" => remove::exec_remove_app(client, args).await,
         "rmc" => remove::exec_remove_catalogs(client, args).await,
+        "restart" => restart::exec_restart_pipe(client, args).await,
+        "runs" => runs::exec_list_pipe_runs(client, args).await,
+        "reload" => reload::exec_reload_pipe(client, args).await,
         "start" => start::exec_start_pipe(client, args).await,
+        "stats" => stats::exec_get_pipe_stats(client, args).await,
         "stop" => stop::exec_stop_pipe(client, args).await,
+        "update" => update::exec_update_pipe(client, args).await,
         _ => unreachable!("unknown cmd {}", cmd),
     }
 }
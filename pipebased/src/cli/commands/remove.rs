@@ -5,12 +5,24 @@ use pipebased_common::{grpc::daemon::daemon_client::DaemonClient, Result};
 use tonic::transport::Channel;
 
 pub fn remove_pipe() -> Cmd {
-    Cmd::new("rm").about("remove pipe").arg(
+    Cmd::new("rm").about("remove pipe").args(vec![
         Arg::new("id")
             .help("Specify pipe id")
             .required(true)
             .index(1),
-    )
+        Arg::new("force")
+            .short('f')
+            .long("force")
+            .help("Stop pipe, reset failed state and remove even if other pipes depend on it"),
+        Arg::new("keep-data")
+            .short('k')
+            .long("keep-data")
+            .help("Keep working directory of pipe for inspection"),
+        Arg::new("purge-failed")
+            .short('p')
+            .long("purge-failed")
+            .help("Reset failed state of pipe before removal"),
+    ])
 }
 
 pub fn remove_catalogs() -> Cmd {
@@ -58,7 +70,11 @@ pub async fn exec_remove_pipe(
     args: &clap::ArgMatches,
 ) -> Result<()> {
     let id = args.value_of("id").unwrap();
-    let _ = do_pipe::remove_pipe(&mut client, id.to_owned()).await?;
+    let force = args.is_present("force");
+    let keep_data = args.is_present("keep-data");
+    let purge_failed = args.is_present("purge-failed");
+    let _ =
+        do_pipe::remove_pipe(&mut client, id.to_owned(), force, keep_data, purge_failed).await?;
     Ok(())
 }
 
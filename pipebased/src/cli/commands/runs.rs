@@ -0,0 +1,24 @@
+use super::Cmd;
+use crate::ops::{do_pipe, print::PrintRecords};
+use clap::Arg;
+use pipebased_common::{grpc::daemon::daemon_client::DaemonClient, Result};
+use tonic::transport::Channel;
+
+pub fn list_pipe_runs() -> Cmd {
+    Cmd::new("runs").about("list runs of oneshot pipe").arg(
+        Arg::new("id")
+            .help("Specify pipe id")
+            .required(true)
+            .index(1),
+    )
+}
+
+pub async fn exec_list_pipe_runs(
+    mut client: DaemonClient<Channel>,
+    args: &clap::ArgMatches,
+) -> Result<()> {
+    let id = args.value_of("id").unwrap();
+    let response = do_pipe::list_pipe_runs(&mut client, id.to_owned()).await?;
+    response.print_records();
+    Ok(())
+}
@@ -1,23 +1,83 @@
 use super::Cmd;
-use crate::ops::do_pipe;
+use crate::ops::{do_pipe, print::PrintRecords};
 use clap::Arg;
-use pipebased_common::{grpc::daemon::daemon_client::DaemonClient, Result};
+use pipebased_common::{
+    grpc::daemon::daemon_client::DaemonClient, pipe_error, PipeOperation, Result,
+};
 use tonic::transport::Channel;
 
 pub fn start_pipe() -> Cmd {
-    Cmd::new("start").about("start pipe").arg(
+    Cmd::new("start").about("start pipe").args(vec![
         Arg::new("id")
-            .help("Specify pipe id")
+            .help("Specify pipe ids, started with dependencies first")
             .required(true)
+            .multiple_values(true)
             .index(1),
-    )
+        Arg::new("wait")
+            .short('w')
+            .long("wait")
+            .help("Wait for start job of single pipe completed and show job result"),
+        Arg::new("timeout")
+            .short('t')
+            .long("timeout")
+            .help("Specify seconds to wait for start job completed")
+            .takes_value(true)
+            .requires("wait"),
+        Arg::new("mode")
+            .short('m')
+            .long("mode")
+            .help("Specify start job mode of single pipe, i.e 'fail' rejects conflicting jobs")
+            .takes_value(true)
+            .possible_values([
+                "replace",
+                "fail",
+                "isolate",
+                "ignore-dependencies",
+                "ignore-requirements",
+            ]),
+    ])
 }
 
 pub async fn exec_start_pipe(
     mut client: DaemonClient<Channel>,
     args: &clap::ArgMatches,
 ) -> Result<()> {
-    let id = args.value_of("id").unwrap();
-    let _ = do_pipe::start_pipe(&mut client, id.to_owned()).await?;
+    let ids: Vec<String> = args.values_of("id").unwrap().map(String::from).collect();
+    let wait = args.is_present("wait");
+    let timeout: Option<u64> = args
+        .value_of("timeout")
+        .map(|timeout| timeout.parse().expect("invalid timeout"));
+    let mode = args.value_of("mode").map(String::from);
+    match ids.as_slice() {
+        [id] => {
+            let response =
+                do_pipe::start_pipe(&mut client, id.to_owned(), mode, wait, timeout).await?;
+            response.print_records();
+            match response.job_result {
+                Some(job_result) if job_result != "done" => {
+                    return Err(pipe_error(
+                        PipeOperation::Start,
+                        format!("start job of pipe '{}' {}", id, job_result),
+                    ))
+                }
+                _ => (),
+            }
+        }
+        _ if wait => {
+            return Err(pipe_error(
+                PipeOperation::Start,
+                String::from("wait for start job of multiple pipes not supported"),
+            ))
+        }
+        _ if mode.is_some() => {
+            return Err(pipe_error(
+                PipeOperation::Start,
+                String::from("job mode for multiple pipes not supported"),
+            ))
+        }
+        _ => {
+            let _ = do_pipe::start_pipes(&mut client, ids).await?;
+        }
+    };
     Ok(())
 }
@@ -0,0 +1,56 @@
+use super::Cmd;
+use crate::ops::{
+    do_pipe,
+    print::{PipeStatsRates, PrintRecords},
+};
+use clap::Arg;
+use pipebased_common::{grpc::daemon::daemon_client::DaemonClient, Result};
+use std::time::Duration;
+use tonic::transport::Channel;
+
+// interval between two samples to derive rates
+const STATS_SAMPLE_INTERVAL_MILLIS: u64 = 1000;
+
+pub fn get_pipe_stats() -> Cmd {
+    Cmd::new("stats")
+        .about("show pipe resource usage")
+        .args(vec![
+            Arg::new("id")
+                .help("Specify pipe ids, show all pipes if not given")
+                .multiple_values(true)
+                .index(1),
+            Arg::new("watch")
+                .short('w')
+                .long("watch")
+                .help("Refresh stats until interrupted"),
+        ])
+}
+
+pub async fn exec_get_pipe_stats(
+    mut client: DaemonClient<Channel>,
+    args: &clap::ArgMatches,
+) -> Result<()> {
+    let ids: Vec<String> = match args.values_of("id") {
+        Some(ids) => ids.map(String::from).collect(),
+        None => vec![],
+    };
+    let watch = args.is_present("watch");
+    let mut previous = do_pipe::get_pipe_stats(&mut client, ids.clone()).await?;
+    loop {
+        tokio::time::sleep(Duration::from_millis(STATS_SAMPLE_INTERVAL_MILLIS)).await;
+        let current = do_pipe::get_pipe_stats(&mut client, ids.clone()).await?;
+        if watch {
+            // clear screen and move cursor to top left
+            print!("\x1b[2J\x1b[H");
+        }
+        PipeStatsRates {
+            previous: &previous,
+            current: &current,
+        }
+        .print_records();
+        if !watch {
+            return Ok(());
+        }
+        previous = current;
+    }
+}
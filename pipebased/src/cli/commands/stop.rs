@@ -1,16 +1,39 @@
 use super::Cmd;
-use crate::ops::do_pipe;
+use crate::ops::{do_pipe, print::PrintRecords};
 use clap::Arg;
-use pipebased_common::{grpc::daemon::daemon_client::DaemonClient, Result};
+use pipebased_common::{
+    grpc::daemon::daemon_client::DaemonClient, pipe_error, PipeOperation, Result,
+};
 use tonic::transport::Channel;
 
 pub fn stop_pipe() -> Cmd {
-    Cmd::new("stop").about("stop pipe").arg(
+    Cmd::new("stop").about("stop pipe").args(vec![
         Arg::new("id")
             .help("Specify pipe id")
             .required(true)
             .index(1),
-    )
+        Arg::new("wait")
+            .short('w')
+            .long("wait")
+            .help("Wait for stop job completed and show job result and exit"),
+        Arg::new("timeout")
+            .short('t')
+            .long("timeout")
+            .help("Specify seconds to wait for stop job completed")
+            .takes_value(true)
+            .requires("wait"),
+        Arg::new("mode")
+            .short('m')
+            .long("mode")
+            .help("Specify stop job mode, i.e 'fail' rejects conflicting jobs")
+            .takes_value(true)
+            .possible_values([
+                "replace",
+                "fail",
+                "ignore-dependencies",
+                "ignore-requirements",
+            ]),
+    ])
 }
 
 pub async fn exec_stop_pipe(
@@ -18,6 +41,18 @@ pub async fn exec_stop_pipe(
     args: &clap::ArgMatches,
 ) -> Result<()> {
     let id = args.value_of("id").unwrap();
-    let _ = do_pipe::stop_pipe(&mut client, id.to_owned()).await?;
-    Ok(())
+    let wait = args.is_present("wait");
+    let timeout: Option<u64> = args
+        .value_of("timeout")
+        .map(|timeout| timeout.parse().expect("invalid timeout"));
+    let mode = args.value_of("mode").map(String::from);
+    let response = do_pipe::stop_pipe(&mut client, id.to_owned(), mode, wait, timeout).await?;
+    response.print_records();
+    match response.job_result {
+        Some(job_result) if job_result != "done" => Err(pipe_error(
+            PipeOperation::Stop,
+            format!("stop job of pipe '{}' {}", id, job_result),
+        )),
+        _ => Ok(()),
+    }
 }
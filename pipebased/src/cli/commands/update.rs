@@ -0,0 +1,69 @@
+use super::Cmd;
+use crate::ops::do_pipe;
+use clap::Arg;
+use pipebased_common::{
+    grpc::daemon::{daemon_client::DaemonClient, AppDescriptor, CatalogsDescriptor},
+    Result,
+};
+use tonic::transport::Channel;
+
+pub fn update_pipe() -> Cmd {
+    Cmd::new("update")
+        .about("update pipe app and / or catalogs, rollback if pipe not running")
+        .args(vec![
+            Arg::new("id")
+                .help("Specify pipe id")
+                .required(true)
+                .index(1),
+            Arg::new("namespace")
+                .short('n')
+                .help("Specify namespace")
+                .required(true)
+                .takes_value(true),
+            Arg::new("project")
+                .short('i')
+                .help("Specify project id")
+                .required(true)
+                .takes_value(true),
+            Arg::new("app")
+                .short('a')
+                .long("app")
+                .help("Specify app version")
+                .takes_value(true)
+                .required_unless_present("catalogs"),
+            Arg::new("catalogs")
+                .short('g')
+                .long("catalogs")
+                .help("Specify catalogs version")
+                .takes_value(true),
+            Arg::new("timeout")
+                .short('t')
+                .long("timeout")
+                .help("Specify seconds to wait for pipe running before rollback")
+                .takes_value(true),
+        ])
+}
+
+pub async fn exec_update_pipe(
+    mut client: DaemonClient<Channel>,
+    args: &clap::ArgMatches,
+) -> Result<()> {
+    let id = args.value_of("id").unwrap();
+    let namespace = args.value_of("namespace").unwrap();
+    let project = args.value_of("project").unwrap();
+    let app = args.value_of("app").map(|version| AppDescriptor {
+        namespace: namespace.to_owned(),
+        id: project.to_owned(),
+        version: version.parse().expect("invalid app version"),
+    });
+    let catalogs = args.value_of("catalogs").map(|version| CatalogsDescriptor {
+        namespace: namespace.to_owned(),
+        id: project.to_owned(),
+        version: version.parse().expect("invalid catalogs version"),
+    });
+    let timeout: Option<u64> = args
+        .value_of("timeout")
+        .map(|timeout| timeout.parse().expect("invalid timeout"));
+    let _ = do_pipe::update_pipe(&mut client, id.to_owned(), app, catalogs, timeout).await?;
+    Ok(())
+}
@@ -1,18 +1,33 @@
 use pipebased_common::{
     grpc::daemon::{
-        daemon_client::DaemonClient, CreatePipeRequest, CreatePipeResponse, ListPipeRequest,
-        ListPipeResponse, RemovePipeRequest, RemovePipeResponse, StartPipeRequest,
-        StartPipeResponse, StopPipeRequest, StopPipeResponse,
+        daemon_client::DaemonClient, AppDescriptor, ApplyPipesRequest, ApplyPipesResponse,
+        CatalogsDescriptor, CreatePipeRequest, CreatePipeResponse, DiagnoseRequest,
+        DiagnoseResponse, DisablePipeRequest, DisablePipeResponse, EnablePipeRequest,
+        EnablePipeResponse, GetPipeStatsRequest, GetPipeStatsResponse, InspectPipeRequest,
+        InspectPipeResponse, KillPipeRequest, KillPipeResponse, ListPipeRequest, ListPipeResponse,
+        ListPipeRunsRequest, ListPipeRunsResponse, PipeEvent, PipeLogEntry, ReloadPipeRequest,
+        ReloadPipeResponse, RemovePipeRequest, RemovePipeResponse, RestartPipeRequest,
+        RestartPipeResponse, StartPipeRequest, StartPipeResponse, StartPipesRequest,
+        StartPipesResponse, StopPipeRequest, StopPipeResponse, StreamPipeLogsRequest,
+        UpdatePipeRequest, UpdatePipeResponse, WatchPipesRequest,
     },
     read_yml, Result,
 };
-use tonic::transport::Channel;
+use tonic::{transport::Channel, Streaming};
 
 pub async fn remove_pipe(
     client: &mut DaemonClient<Channel>,
     id: String,
+    force: bool,
+    keep_data: bool,
+    purge_failed: bool,
 ) -> Result<RemovePipeResponse> {
-    let request = RemovePipeRequest { id };
+    let request = RemovePipeRequest {
+        id,
+        force,
+        keep_data,
+        purge_failed,
+    };
     let response = client.remove_pipe(request).await?;
     Ok(response.into_inner())
 }
@@ -20,24 +35,173 @@ pub async fn remove_pipe(
 pub async fn start_pipe(
     client: &mut DaemonClient<Channel>,
     id: String,
+    mode: Option<String>,
+    wait: bool,
+    timeout: Option<u64>,
 ) -> Result<StartPipeResponse> {
-    let request = StartPipeRequest { id };
+    let request = StartPipeRequest {
+        id,
+        wait,
+        timeout,
+        mode,
+    };
     let response = client.start_pipe(request).await?;
     Ok(response.into_inner())
 }
 
-pub async fn stop_pipe(client: &mut DaemonClient<Channel>, id: String) -> Result<StopPipeResponse> {
-    let request = StopPipeRequest { id };
+pub async fn start_pipes(
+    client: &mut DaemonClient<Channel>,
+    ids: Vec<String>,
+) -> Result<StartPipesResponse> {
+    let request = StartPipesRequest { ids };
+    let response = client.start_pipes(request).await?;
+    Ok(response.into_inner())
+}
+
+pub async fn stop_pipe(
+    client: &mut DaemonClient<Channel>,
+    id: String,
+    mode: Option<String>,
+    wait: bool,
+    timeout: Option<u64>,
+) -> Result<StopPipeResponse> {
+    let request = StopPipeRequest {
+        id,
+        wait,
+        timeout,
+        mode,
+    };
     let response = client.stop_pipe(request).await?;
     Ok(response.into_inner())
 }
 
-pub async fn list_pipe(client: &mut DaemonClient<Channel>) -> Result<ListPipeResponse> {
-    let request = ListPipeRequest {};
+pub async fn restart_pipe(
+    client: &mut DaemonClient<Channel>,
+    id: String,
+) -> Result<RestartPipeResponse> {
+    let request = RestartPipeRequest { id };
+    let response = client.restart_pipe(request).await?;
+    Ok(response.into_inner())
+}
+
+pub async fn reload_pipe(
+    client: &mut DaemonClient<Channel>,
+    id: String,
+) -> Result<ReloadPipeResponse> {
+    let request = ReloadPipeRequest { id };
+    let response = client.reload_pipe(request).await?;
+    Ok(response.into_inner())
+}
+
+pub async fn kill_pipe(
+    client: &mut DaemonClient<Channel>,
+    id: String,
+    signal: String,
+    whom: Option<String>,
+) -> Result<KillPipeResponse> {
+    let request = KillPipeRequest { id, signal, whom };
+    let response = client.kill_pipe(request).await?;
+    Ok(response.into_inner())
+}
+
+pub async fn enable_pipe(
+    client: &mut DaemonClient<Channel>,
+    id: String,
+) -> Result<EnablePipeResponse> {
+    let request = EnablePipeRequest { id };
+    let response = client.enable_pipe(request).await?;
+    Ok(response.into_inner())
+}
+
+pub async fn disable_pipe(
+    client: &mut DaemonClient<Channel>,
+    id: String,
+) -> Result<DisablePipeResponse> {
+    let request = DisablePipeRequest { id };
+    let response = client.disable_pipe(request).await?;
+    Ok(response.into_inner())
+}
+
+pub async fn inspect_pipe(
+    client: &mut DaemonClient<Channel>,
+    id: String,
+) -> Result<InspectPipeResponse> {
+    let request = InspectPipeRequest { id };
+    let response = client.inspect_pipe(request).await?;
+    Ok(response.into_inner())
+}
+
+pub async fn get_pipe_stats(
+    client: &mut DaemonClient<Channel>,
+    ids: Vec<String>,
+) -> Result<GetPipeStatsResponse> {
+    let request = GetPipeStatsRequest { ids };
+    let response = client.get_pipe_stats(request).await?;
+    Ok(response.into_inner())
+}
+
+pub async fn list_pipe_runs(
+    client: &mut DaemonClient<Channel>,
+    id: String,
+) -> Result<ListPipeRunsResponse> {
+    let request = ListPipeRunsRequest { id };
+    let response = client.list_pipe_runs(request).await?;
+    Ok(response.into_inner())
+}
+
+pub async fn stream_pipe_logs(
+    client: &mut DaemonClient<Channel>,
+    request: StreamPipeLogsRequest,
+) -> Result<Streaming<PipeLogEntry>> {
+    let response = client.stream_pipe_logs(request).await?;
+    Ok(response.into_inner())
+}
+
+pub async fn watch_pipes(
+    client: &mut DaemonClient<Channel>,
+    ids: Vec<String>,
+) -> Result<Streaming<PipeEvent>> {
+    let request = WatchPipesRequest { ids };
+    let response = client.watch_pipes(request).await?;
+    Ok(response.into_inner())
+}
+
+pub async fn update_pipe(
+    client: &mut DaemonClient<Channel>,
+    id: String,
+    app: Option<AppDescriptor>,
+    catalogs: Option<CatalogsDescriptor>,
+    timeout: Option<u64>,
+) -> Result<UpdatePipeResponse> {
+    let request = UpdatePipeRequest {
+        id,
+        app,
+        catalogs,
+        timeout,
+    };
+    let response = client.update_pipe(request).await?;
+    Ok(response.into_inner())
+}
+
+pub async fn list_pipe(
+    client: &mut DaemonClient<Channel>,
+    selector: Option<String>,
+) -> Result<ListPipeResponse> {
+    let request = ListPipeRequest { selector };
     let response = client.list_pipe(request).await?;
     Ok(response.into_inner())
 }
 
+pub async fn diagnose(
+    client: &mut DaemonClient<Channel>,
+    repair: bool,
+    purge: bool,
+) -> Result<DiagnoseResponse> {
+    let request = DiagnoseRequest { repair, purge };
+    let response = client.diagnose(request).await?;
+    Ok(response.into_inner())
+}
+
 fn parse_create_pipe_request<P>(path: P) -> Result<CreatePipeRequest>
 where
     P: AsRef<std::path::Path>,
@@ -46,6 +210,35 @@ where
     Ok(request.into())
 }
 
+fn parse_apply_pipes_request<P>(path: P) -> Result<Vec<CreatePipeRequest>>
+where
+    P: AsRef<std::path::Path>,
+{
+    let compose: models::PipeCompose = read_yml(path)?;
+    Ok(compose.pipes.into_iter().map(|pipe| pipe.into()).collect())
+}
+
+pub async fn apply_pipes<P>(
+    client: &mut DaemonClient<Channel>,
+    path: P,
+    dry_run: bool,
+    prune: bool,
+    timeout: Option<u64>,
+) -> Result<ApplyPipesResponse>
+where
+    P: AsRef<std::path::Path>,
+{
+    let pipes = parse_apply_pipes_request(path)?;
+    let request = ApplyPipesRequest {
+        pipes,
+        dry_run,
+        prune,
+        timeout,
+    };
+    let response = client.apply_pipes(request).await?;
+    Ok(response.into_inner())
+}
+
 pub async fn create_pipe<P>(
     client: &mut DaemonClient<Channel>,
     path: P,
@@ -61,6 +254,7 @@ where
 mod models {
     use pipebased_common::grpc::daemon;
     use serde::Deserialize;
+    use std::collections::HashMap;
 
     #[derive(Deserialize)]
     pub struct AppDescriptor {
@@ -82,6 +276,86 @@ mod models {
         pub value: String,
     }
 
+    #[derive(Deserialize)]
+    pub struct RestartDescriptor {
+        pub policy: Option<String>,
+        pub delay: Option<String>,
+        pub start_limit_burst: Option<u32>,
+        pub start_limit_interval: Option<String>,
+    }
+
+    #[derive(Deserialize)]
+    pub struct StopDescriptor {
+        pub timeout: Option<String>,
+        pub kill_signal: Option<String>,
+        pub kill_mode: Option<String>,
+        pub final_kill_signal: Option<String>,
+        pub send_sigkill: Option<bool>,
+    }
+
+    #[derive(Deserialize)]
+    pub struct LimitDescriptor {
+        pub memory_max: Option<String>,
+        pub memory_high: Option<String>,
+        pub cpu_quota: Option<String>,
+        pub cpu_weight: Option<u64>,
+        pub tasks_max: Option<String>,
+        pub io_weight: Option<u64>,
+    }
+
+    #[derive(Deserialize)]
+    pub struct SecurityDescriptor {
+        pub preset: Option<String>,
+        pub protect_system: Option<String>,
+        pub protect_home: Option<String>,
+        pub private_tmp: Option<bool>,
+        pub no_new_privileges: Option<bool>,
+        pub capability_bounding_set: Option<Vec<String>>,
+        pub read_write_paths: Option<Vec<String>>,
+        pub restrict_address_families: Option<Vec<String>>,
+    }
+
+    #[derive(Deserialize)]
+    pub struct ScheduleDescriptor {
+        pub on_calendar: Option<String>,
+        pub on_boot_sec: Option<String>,
+        pub on_unit_active_sec: Option<String>,
+    }
+
+    #[derive(Deserialize)]
+    pub struct TcpProbe {
+        pub host: Option<String>,
+        pub port: u32,
+    }
+
+    #[derive(Deserialize)]
+    pub struct HttpProbe {
+        pub host: Option<String>,
+        pub port: u32,
+        pub path: Option<String>,
+    }
+
+    #[derive(Deserialize)]
+    pub struct ExecProbe {
+        pub command: Vec<String>,
+    }
+
+    #[derive(Deserialize)]
+    pub struct ProbeDescriptor {
+        pub tcp: Option<TcpProbe>,
+        pub http: Option<HttpProbe>,
+        pub exec: Option<ExecProbe>,
+        pub interval: Option<u64>,
+        pub timeout: Option<u64>,
+        pub failure_threshold: Option<u32>,
+    }
+
+    #[derive(Deserialize)]
+    pub struct PipeDependency {
+        pub id: String,
+        pub dependency_type: Option<String>,
+    }
+
     #[derive(Deserialize)]
     pub struct CreatePipeRequest {
         pub id: String,
@@ -89,10 +363,31 @@ mod models {
         pub user: Option<String>,
         pub group: Option<String>,
         pub envs: Option<Vec<EnvironmentVariable>>,
+        pub env_files: Option<Vec<String>>,
+        pub restart: Option<RestartDescriptor>,
+        pub stop: Option<StopDescriptor>,
+        pub limits: Option<LimitDescriptor>,
+        pub security: Option<SecurityDescriptor>,
+        pub autostart: Option<bool>,
+        pub labels: Option<HashMap<String, String>>,
+        pub depends_on: Option<Vec<PipeDependency>>,
+        pub schedule: Option<ScheduleDescriptor>,
+        // simple or oneshot
+        #[serde(rename = "type")]
+        pub service_type: Option<String>,
+        pub remain_after_exit: Option<bool>,
+        pub liveness: Option<ProbeDescriptor>,
+        pub readiness: Option<ProbeDescriptor>,
         pub app: AppDescriptor,
         pub catalogs: CatalogsDescriptor,
     }
 
+    // desired pipes of host
+    #[derive(Deserialize)]
+    pub struct PipeCompose {
+        pub pipes: Vec<CreatePipeRequest>,
+    }
+
     impl From<AppDescriptor> for daemon::AppDescriptor {
         fn from(origin: AppDescriptor) -> Self {
             let namespace = origin.namespace;
@@ -127,6 +422,136 @@ mod models {
         }
     }
 
+    impl From<RestartDescriptor> for daemon::RestartDescriptor {
+        fn from(origin: RestartDescriptor) -> Self {
+            let policy = origin.policy;
+            let delay = origin.delay;
+            let start_limit_burst = origin.start_limit_burst;
+            let start_limit_interval = origin.start_limit_interval;
+            daemon::RestartDescriptor {
+                policy,
+                delay,
+                start_limit_burst,
+                start_limit_interval,
+            }
+        }
+    }
+
+    impl From<StopDescriptor> for daemon::StopDescriptor {
+        fn from(origin: StopDescriptor) -> Self {
+            let timeout = origin.timeout;
+            let kill_signal = origin.kill_signal;
+            let kill_mode = origin.kill_mode;
+            let final_kill_signal = origin.final_kill_signal;
+            let send_sigkill = origin.send_sigkill;
+            daemon::StopDescriptor {
+                timeout,
+                kill_signal,
+                kill_mode,
+                final_kill_signal,
+                send_sigkill,
+            }
+        }
+    }
+
+    impl From<LimitDescriptor> for daemon::LimitDescriptor {
+        fn from(origin: LimitDescriptor) -> Self {
+            let memory_max = origin.memory_max;
+            let memory_high = origin.memory_high;
+            let cpu_quota = origin.cpu_quota;
+            let cpu_weight = origin.cpu_weight;
+            let tasks_max = origin.tasks_max;
+            let io_weight = origin.io_weight;
+            daemon::LimitDescriptor {
+                memory_max,
+                memory_high,
+                cpu_quota,
+                cpu_weight,
+                tasks_max,
+                io_weight,
+            }
+        }
+    }
+
+    impl From<SecurityDescriptor> for daemon::SecurityDescriptor {
+        fn from(origin: SecurityDescriptor) -> Self {
+            let preset = origin.preset;
+            let protect_system = origin.protect_system;
+            let protect_home = origin.protect_home;
+            let private_tmp = origin.private_tmp;
+            let no_new_privileges = origin.no_new_privileges;
+            let capability_bounding_set = origin
+                .capability_bounding_set
+                .map(|capabilities| capabilities.join(" "));
+            let read_write_paths = origin.read_write_paths.unwrap_or_default();
+            let restrict_address_families = origin
+                .restrict_address_families
+                .map(|families| families.join(" "));
+            daemon::SecurityDescriptor {
+                preset,
+                protect_system,
+                protect_home,
+                private_tmp,
+                no_new_privileges,
+                capability_bounding_set,
+                read_write_paths,
+                restrict_address_families,
+            }
+        }
+    }
+
+    impl From<ScheduleDescriptor> for daemon::ScheduleDescriptor {
+        fn from(origin: ScheduleDescriptor) -> Self {
+            let on_calendar = origin.on_calendar;
+            let on_boot_sec = origin.on_boot_sec;
+            let on_unit_active_sec = origin.on_unit_active_sec;
+            daemon::ScheduleDescriptor {
+                on_calendar,
+                on_boot_sec,
+                on_unit_active_sec,
+            }
+        }
+    }
+
+    impl From<ProbeDescriptor> for daemon::ProbeDescriptor {
+        fn from(origin: ProbeDescriptor) -> Self {
+            let tcp = origin.tcp.map(|tcp| daemon::TcpProbe {
+                host: tcp.host,
+                port: tcp.port,
+            });
+            let http = origin.http.map(|http| daemon::HttpProbe {
+                host: http.host,
+                port: http.port,
+                path: http.path,
+            });
+            let exec = origin.exec.map(|exec| daemon::ExecProbe {
+                command: exec.command,
+            });
+            let interval = origin.interval;
+            let timeout = origin.timeout;
+            let failure_threshold = origin.failure_threshold;
+            daemon::ProbeDescriptor {
+                tcp,
+                http,
+                exec,
+                interval,
+                timeout,
+                failure_threshold,
+            }
+        }
+    }
+
+    impl From<PipeDependency> for daemon::PipeDependency {
+        fn from(origin: PipeDependency) -> Self {
+            let id = origin.id;
+            let dependency_type = origin.dependency_type;
+            daemon::PipeDependency {
+                id,
+                dependency_type,
+            }
+        }
+    }
+
     impl From<CreatePipeRequest> for daemon::CreatePipeRequest {
         fn from(origin: CreatePipeRequest) -> Self {
             let id = origin.id;
@@ -143,6 +568,25 @@ mod models {
                     .collect(),
                 None => vec![],
             };
+            let env_files = origin.env_files.unwrap_or_default();
+            let restart = origin.restart.map(|restart| restart.into());
+            let stop = origin.stop.map(|stop| stop.into());
+            let limits = origin.limits.map(|limits| limits.into());
+            let security = origin.security.map(|security| security.into());
+            let autostart = origin.autostart;
+            let labels = origin.labels.unwrap_or_default();
+            let depends_on: Vec<daemon::PipeDependency> = match origin.depends_on {
+                Some(depends_on) => depends_on
+                    .into_iter()
+                    .map(|dependency| dependency.into())
+                    .collect(),
+                None => vec![],
+            };
+            let schedule = origin.schedule.map(|schedule| schedule.into());
+            let service_type = origin.service_type;
+            let remain_after_exit = origin.remain_after_exit;
+            let liveness = origin.liveness.map(|liveness| liveness.into());
+            let readiness = origin.readiness.map(|readiness| readiness.into());
             let app: daemon::AppDescriptor = origin.app.into();
             let catalogs: daemon::CatalogsDescriptor = origin.catalogs.into();
             daemon::CreatePipeRequest {
@@ -151,6 +595,19 @@ mod models {
                 user,
                 group,
                 envs,
+                env_files,
+                restart,
+                limits,
+                security,
+                autostart,
+                labels,
+                depends_on,
+                schedule,
+                service_type,
+                remain_after_exit,
+                liveness,
+                readiness,
+                stop,
                 app: Some(app),
                 catalogs: Some(catalogs),
             }
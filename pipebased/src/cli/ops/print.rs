@@ -1,8 +1,13 @@
+use chrono::{Local, TimeZone};
 use pipebased_common::{
-    grpc::daemon::{ListAppResponse, ListCatalogsResponse, ListPipeResponse},
+    grpc::daemon::{
+        ApplyPipesResponse, DiagnoseResponse, GetPipeStatsResponse, InspectPipeResponse,
+        ListAppResponse, ListCatalogsResponse, ListPipeResponse, ListPipeRunsResponse, PipeEvent,
+        PipeLogEntry, PipeStats, StartPipeResponse, StopPipeResponse,
+    },
     Result,
 };
-use std::{fmt, io::Write};
+use std::{collections::HashMap, fmt, io::Write};
 use termcolor::{Color, ColorChoice, ColorSpec, StandardStream, WriteColor};
 
 const DISPLAY_ID_WIDTH: usize = 12;
@@ -11,6 +16,84 @@ const DISPLAY_VERSION_WIDTH: usize = 12;
 const DISPLAY_LOAD_STATE_WIDTH: usize = 12;
 const DISPLAY_ACTIVE_STATE_WIDTH: usize = 12;
 const DISPLAY_SUBSTATE_STATE_WIDTH: usize = 12;
+const DISPLAY_ENABLED_WIDTH: usize = 8;
+const DISPLAY_HEALTH_WIDTH: usize = 11;
+const DISPLAY_TIMESTAMP_WIDTH: usize = 21;
+const DISPLAY_FIELD_WIDTH: usize = 20;
+const DISPLAY_RUN_RESULT_WIDTH: usize = 10;
+const DISPLAY_EXIT_CODE_WIDTH: usize = 6;
+const DISPLAY_EXIT_REASON_WIDTH: usize = 8;
+const DISPLAY_EVENT_TYPE_WIDTH: usize = 12;
+const DISPLAY_CHANGE_TYPE_WIDTH: usize = 12;
+const DISPLAY_APPLY_STATUS_WIDTH: usize = 10;
+const DISPLAY_DRIFT_TYPE_WIDTH: usize = 28;
+const DISPLAY_RESOURCE_WIDTH: usize = 32;
+const DISPLAY_REPAIRED_WIDTH: usize = 10;
+const DISPLAY_CPU_WIDTH: usize = 8;
+const DISPLAY_BYTES_WIDTH: usize = 10;
+const DISPLAY_TASKS_WIDTH: usize = 7;
+const DISPLAY_RATE_WIDTH: usize = 12;
+const BYTE_UNITS: [&str; 5] = ["B", "K", "M", "G", "T"];
+
+// format microseconds since epoch in local time
+fn format_timestamp_micros(timestamp: u64) -> String {
+    match Local.timestamp_micros(timestamp as i64).single() {
+        Some(timestamp) => timestamp.format("%Y-%m-%dT%H:%M:%S%.6f%:z").to_string(),
+        None => timestamp.to_string(),
+    }
+}
+
+// microseconds since epoch in local time to seconds, '-' if undefined
+fn format_local_time(timestamp: Option<u64>) -> String {
+    let timestamp = match timestamp {
+        Some(timestamp) => timestamp,
+        None => return String::from("-"),
+    };
+    match Local.timestamp_micros(timestamp as i64).single() {
+        Some(timestamp) => timestamp.format("%Y-%m-%d %H:%M:%S").to_string(),
+        None => timestamp.to_string(),
+    }
+}
+
+// bytes in binary units, i.e '1.5M'
+fn format_bytes(bytes: u64) -> String {
+    let mut value = bytes as f64;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < BYTE_UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+    match unit {
+        0 => format!("{}{}", bytes, BYTE_UNITS[unit]),
+        _ => format!("{:.1}{}", value, BYTE_UNITS[unit]),
+    }
+}
+
+fn format_optional_bytes(bytes: Option<u64>) -> String {
+    bytes.map(format_bytes).unwrap_or_else(|| String::from("-"))
+}
+
+// sorted 'key=value' pairs separated by comma
+fn format_labels(labels: &HashMap<String, String>) -> String {
+    if labels.is_empty() {
+        return String::from("-");
+    }
+    let mut labels: Vec<String> = labels
+        .iter()
+        .map(|(key, value)| format!("{}={}", key, value))
+        .collect();
+    labels.sort();
+    labels.join(",")
+}
+
+fn print_field<T: fmt::Display>(name: &str, value: T) {
+    println!(
+        "{name:<width$}{value}",
+        name = name,
+        value = value,
+        width = DISPLAY_FIELD_WIDTH
+    );
+}
 
 pub trait PrintRecords {
     fn print_records(&self);
@@ -72,29 +155,402 @@ impl PrintRecords for ListPipeResponse {
     fn print_records(&self) {
         // print header
         println!(
-            "{col0:<col0_width$}{col1:<col1_width$}{col2:<col2_width$}{col3:<col3_width$}",
+            "{col0:<col0_width$}{col1:<col1_width$}{col2:<col2_width$}{col3:<col3_width$}{col4:<col4_width$}{col5:<col5_width$}{col6:<col6_width$}{col7:<col7_width$}{col8:<col8_width$}Labels",
             col0 = "Id",
             col1 = "Load",
             col2 = "Active",
             col3 = "Sub",
+            col4 = "Enabled",
+            col5 = "Live",
+            col6 = "Ready",
+            col7 = "Next Trigger",
+            col8 = "Last Trigger",
             col0_width = DISPLAY_ID_WIDTH,
             col1_width = DISPLAY_LOAD_STATE_WIDTH,
             col2_width = DISPLAY_ACTIVE_STATE_WIDTH,
             col3_width = DISPLAY_SUBSTATE_STATE_WIDTH,
+            col4_width = DISPLAY_ENABLED_WIDTH,
+            col5_width = DISPLAY_HEALTH_WIDTH,
+            col6_width = DISPLAY_HEALTH_WIDTH,
+            col7_width = DISPLAY_TIMESTAMP_WIDTH,
+            col8_width = DISPLAY_TIMESTAMP_WIDTH,
         );
         for pipe in &self.pipes {
             println!(
-                "{col0:<col0_width$}{col1:<col1_width$}{col2:<col2_width$}{col3:<col3_width$}",
+                "{col0:<col0_width$}{col1:<col1_width$}{col2:<col2_width$}{col3:<col3_width$}{col4:<col4_width$}{col5:<col5_width$}{col6:<col6_width$}{col7:<col7_width$}{col8:<col8_width$}{col9}",
                 col0 = pipe.id,
                 col1 = pipe.load_state,
                 col2 = pipe.active_state,
                 col3 = pipe.sub_state,
+                col4 = pipe.enabled,
+                col5 = pipe.liveness.as_deref().unwrap_or("-"),
+                col6 = pipe.readiness.as_deref().unwrap_or("-"),
+                col7 = format_local_time(pipe.next_trigger),
+                col8 = format_local_time(pipe.last_trigger),
+                col9 = format_labels(&pipe.labels),
                 col0_width = DISPLAY_ID_WIDTH,
                 col1_width = DISPLAY_LOAD_STATE_WIDTH,
                 col2_width = DISPLAY_ACTIVE_STATE_WIDTH,
                 col3_width = DISPLAY_SUBSTATE_STATE_WIDTH,
+                col4_width = DISPLAY_ENABLED_WIDTH,
+                col5_width = DISPLAY_HEALTH_WIDTH,
+                col6_width = DISPLAY_HEALTH_WIDTH,
+                col7_width = DISPLAY_TIMESTAMP_WIDTH,
+                col8_width = DISPLAY_TIMESTAMP_WIDTH,
+            );
+        }
+    }
+}
+
+impl PrintRecords for ListPipeRunsResponse {
+    fn print_records(&self) {
+        // print header
+        println!(
+            "{col0:<col0_width$}{col1:<col1_width$}{col2:<col2_width$}{col3:<col3_width$}{col4:<col4_width$}State",
+            col0 = "Started",
+            col1 = "Ended",
+            col2 = "Result",
+            col3 = "Exit",
+            col4 = "Reason",
+            col0_width = DISPLAY_TIMESTAMP_WIDTH,
+            col1_width = DISPLAY_TIMESTAMP_WIDTH,
+            col2_width = DISPLAY_RUN_RESULT_WIDTH,
+            col3_width = DISPLAY_EXIT_CODE_WIDTH,
+            col4_width = DISPLAY_EXIT_REASON_WIDTH,
+        );
+        for run in &self.runs {
+            let result = if run.succeeded { "succeeded" } else { "failed" };
+            println!(
+                "{col0:<col0_width$}{col1:<col1_width$}{col2:<col2_width$}{col3:<col3_width$}{col4:<col4_width$}{col5}/{col6}",
+                col0 = format_local_time(Some(run.started_at).filter(|usec| *usec > 0)),
+                col1 = format_local_time(Some(run.ended_at).filter(|usec| *usec > 0)),
+                col2 = result,
+                col3 = run.exit_code,
+                col4 = run.exit_reason.as_deref().unwrap_or("-"),
+                col5 = run.active_state,
+                col6 = run.sub_state,
+                col0_width = DISPLAY_TIMESTAMP_WIDTH,
+                col1_width = DISPLAY_TIMESTAMP_WIDTH,
+                col2_width = DISPLAY_RUN_RESULT_WIDTH,
+                col3_width = DISPLAY_EXIT_CODE_WIDTH,
+                col4_width = DISPLAY_EXIT_REASON_WIDTH,
+            );
+        }
+    }
+}
+
+// two stats samples, rates derived from counters in between
+pub(crate) struct PipeStatsRates<'a> {
+    pub previous: &'a GetPipeStatsResponse,
+    pub current: &'a GetPipeStatsResponse,
+}
+
+impl<'a> PipeStatsRates<'a> {
+    // counter increase per second, '-' if undefined in either sample
+    fn rate(
+        previous: &PipeStats,
+        current: &PipeStats,
+        counter: fn(&PipeStats) -> Option<u64>,
+    ) -> Option<f64> {
+        let elapsed_secs = current.timestamp.checked_sub(previous.timestamp)? as f64 / 1e6;
+        if elapsed_secs <= 0.0 {
+            return None;
+        }
+        let delta = counter(current)?.checked_sub(counter(previous)?)?;
+        Some(delta as f64 / elapsed_secs)
+    }
+
+    fn format_rate(rate: Option<f64>) -> String {
+        match rate {
+            Some(rate) => format!("{}/s", format_bytes(rate as u64)),
+            None => String::from("-"),
+        }
+    }
+}
+
+impl<'a> PrintRecords for PipeStatsRates<'a> {
+    fn print_records(&self) {
+        // print header
+        println!(
+            "{col0:<col0_width$}{col1:<col1_width$}{col2:<col2_width$}{col3:<col3_width$}{col4:<col4_width$}{col5:<col5_width$}{col6:<col6_width$}Net Out",
+            col0 = "Id",
+            col1 = "CPU",
+            col2 = "Memory",
+            col3 = "Tasks",
+            col4 = "IO Read",
+            col5 = "IO Write",
+            col6 = "Net In",
+            col0_width = DISPLAY_ID_WIDTH,
+            col1_width = DISPLAY_CPU_WIDTH,
+            col2_width = DISPLAY_BYTES_WIDTH,
+            col3_width = DISPLAY_TASKS_WIDTH,
+            col4_width = DISPLAY_RATE_WIDTH,
+            col5_width = DISPLAY_RATE_WIDTH,
+            col6_width = DISPLAY_RATE_WIDTH,
+        );
+        for current in &self.current.stats {
+            let previous = match self
+                .previous
+                .stats
+                .iter()
+                .find(|previous| previous.id == current.id)
+            {
+                Some(previous) => previous,
+                None => continue,
+            };
+            // cpu nanoseconds per second in percentage of one core
+            let cpu = match Self::rate(previous, current, |stats| stats.cpu_usage_nsec) {
+                Some(rate) => format!("{:.1}%", rate / 1e7),
+                None => String::from("-"),
+            };
+            let tasks = current
+                .tasks_current
+                .map(|tasks| tasks.to_string())
+                .unwrap_or_else(|| String::from("-"));
+            println!(
+                "{col0:<col0_width$}{col1:<col1_width$}{col2:<col2_width$}{col3:<col3_width$}{col4:<col4_width$}{col5:<col5_width$}{col6:<col6_width$}{col7}",
+                col0 = current.id,
+                col1 = cpu,
+                col2 = format_optional_bytes(current.memory_current),
+                col3 = tasks,
+                col4 = Self::format_rate(Self::rate(previous, current, |stats| stats.io_read_bytes)),
+                col5 = Self::format_rate(Self::rate(previous, current, |stats| stats.io_write_bytes)),
+                col6 = Self::format_rate(Self::rate(previous, current, |stats| stats.ip_ingress_bytes)),
+                col7 = Self::format_rate(Self::rate(previous, current, |stats| stats.ip_egress_bytes)),
+                col0_width = DISPLAY_ID_WIDTH,
+                col1_width = DISPLAY_CPU_WIDTH,
+                col2_width = DISPLAY_BYTES_WIDTH,
+                col3_width = DISPLAY_TASKS_WIDTH,
+                col4_width = DISPLAY_RATE_WIDTH,
+                col5_width = DISPLAY_RATE_WIDTH,
+                col6_width = DISPLAY_RATE_WIDTH,
+            );
+        }
+    }
+}
+
+impl PrintRecords for ApplyPipesResponse {
+    fn print_records(&self) {
+        // print header
+        println!(
+            "{col0:<col0_width$}{col1:<col1_width$}{col2:<col2_width$}Details",
+            col0 = "Id",
+            col1 = "Change",
+            col2 = "Status",
+            col0_width = DISPLAY_ID_WIDTH,
+            col1_width = DISPLAY_CHANGE_TYPE_WIDTH,
+            col2_width = DISPLAY_APPLY_STATUS_WIDTH,
+        );
+        for change in &self.changes {
+            let status = match (change.applied, change.error.as_ref()) {
+                (true, _) => "applied",
+                (false, Some(_)) => "failed",
+                (false, None) => "planned",
+            };
+            let mut details = change.details.to_owned();
+            if let Some(error) = change.error.as_ref() {
+                details.push(format!("error: {}", error));
+            }
+            let details = match details.is_empty() {
+                true => String::from("-"),
+                false => details.join(", "),
+            };
+            println!(
+                "{col0:<col0_width$}{col1:<col1_width$}{col2:<col2_width$}{col3}",
+                col0 = change.id,
+                col1 = change.change_type,
+                col2 = status,
+                col3 = details,
+                col0_width = DISPLAY_ID_WIDTH,
+                col1_width = DISPLAY_CHANGE_TYPE_WIDTH,
+                col2_width = DISPLAY_APPLY_STATUS_WIDTH,
+            );
+        }
+    }
+}
+
+impl PrintRecords for DiagnoseResponse {
+    fn print_records(&self) {
+        if self.drifts.is_empty() {
+            println!("no drift found");
+            return;
+        }
+        // print header
+        println!(
+            "{col0:<col0_width$}{col1:<col1_width$}{col2:<col2_width$}Detail",
+            col0 = "Drift",
+            col1 = "Resource",
+            col2 = "Repaired",
+            col0_width = DISPLAY_DRIFT_TYPE_WIDTH,
+            col1_width = DISPLAY_RESOURCE_WIDTH,
+            col2_width = DISPLAY_REPAIRED_WIDTH,
+        );
+        for drift in &self.drifts {
+            let detail = match drift.error.as_ref() {
+                Some(error) => format!("{}, repair failed: {}", drift.detail, error),
+                None => drift.detail.to_owned(),
+            };
+            println!(
+                "{col0:<col0_width$}{col1:<col1_width$}{col2:<col2_width$}{col3}",
+                col0 = drift.drift_type,
+                col1 = drift.resource,
+                col2 = drift.repaired,
+                col3 = detail,
+                col0_width = DISPLAY_DRIFT_TYPE_WIDTH,
+                col1_width = DISPLAY_RESOURCE_WIDTH,
+                col2_width = DISPLAY_REPAIRED_WIDTH,
+            );
+        }
+    }
+}
+
+impl PrintRecords for PipeLogEntry {
+    fn print_records(&self) {
+        let timestamp = format_timestamp_micros(self.timestamp);
+        // prefer app log level, fallback to journal priority
+        let level = match (self.level.as_ref(), self.priority) {
+            (Some(level), _) => level.to_owned(),
+            (None, Some(priority)) => priority.to_string(),
+            (None, None) => String::from("-"),
+        };
+        let mut app_fields: Vec<String> = self
+            .app_fields
+            .iter()
+            .map(|(key, value)| format!("{}={}", key, value))
+            .collect();
+        app_fields.sort();
+        match app_fields.is_empty() {
+            true => println!("{} {} {}", timestamp, level, self.message),
+            false => println!(
+                "{} {} {} {}",
+                timestamp,
+                level,
+                self.message,
+                app_fields.join(" ")
+            ),
+        }
+    }
+}
+
+impl PrintRecords for PipeEvent {
+    fn print_records(&self) {
+        let timestamp = format_timestamp_micros(self.timestamp);
+        let mut details: Vec<String> = vec![];
+        if let Some(exit_reason) = self.exit_reason.as_ref() {
+            details.push(format!("reason={}", exit_reason));
+        }
+        if let Some(exit_code) = self.exit_code {
+            details.push(format!("code={}", exit_code));
+        }
+        if let Some(job_result) = self.job_result.as_ref() {
+            details.push(format!("job={}", job_result));
+        }
+        println!(
+            "{} {col0:<col0_width$}{col1:<col1_width$}{col2:<col2_width$}{col3:<col3_width$}{}",
+            timestamp,
+            details.join(" "),
+            col0 = self.id,
+            col1 = self.event_type,
+            col2 = self.active_state,
+            col3 = self.sub_state,
+            col0_width = DISPLAY_ID_WIDTH,
+            col1_width = DISPLAY_EVENT_TYPE_WIDTH,
+            col2_width = DISPLAY_ACTIVE_STATE_WIDTH,
+            col3_width = DISPLAY_SUBSTATE_STATE_WIDTH,
+        );
+    }
+}
+
+impl PrintRecords for StartPipeResponse {
+    fn print_records(&self) {
+        if let Some(job_result) = self.job_result.as_ref() {
+            print_field("Job Result", job_result);
+        }
+    }
+}
+
+impl PrintRecords for StopPipeResponse {
+    fn print_records(&self) {
+        if let Some(job_result) = self.job_result.as_ref() {
+            print_field("Job Result", job_result);
+        }
+        let exit = match self.exit.as_ref() {
+            Some(exit) => exit,
+            None => return,
+        };
+        print_field("Result", &exit.result);
+        print_field("Exit Code", exit.exit_code);
+        print_field("Exit Reason", exit.exit_reason.as_deref().unwrap_or("-"));
+        print_field("Signal", exit.signal.as_deref().unwrap_or("-"));
+        print_field("Clean", exit.clean);
+        print_field("SIGKILLed", exit.sigkilled);
+    }
+}
+
+impl PrintRecords for InspectPipeResponse {
+    fn print_records(&self) {
+        if let Some(state) = self.state.as_ref() {
+            print_field("Id", &state.id);
+            print_field(
+                "State",
+                format!(
+                    "{} / {} / {}",
+                    state.load_state, state.active_state, state.sub_state
+                ),
             );
+            print_field("Enabled", state.enabled);
+            print_field("Next Trigger", format_local_time(state.next_trigger));
+            print_field("Last Trigger", format_local_time(state.last_trigger));
+            print_field("Liveness", state.liveness.as_deref().unwrap_or("-"));
+            print_field("Readiness", state.readiness.as_deref().unwrap_or("-"));
+            print_field("Labels", format_labels(&state.labels));
+        }
+        print_field("Description", &self.description);
+        print_field("User", &self.user);
+        print_field("Group", &self.group);
+        match self.app.as_ref() {
+            Some(app) => print_field(
+                "App",
+                format!("{}/{}/{}", app.namespace, app.id, app.version),
+            ),
+            None => print_field("App", "-"),
+        };
+        print_field("App Path", self.app_path.as_deref().unwrap_or("-"));
+        match self.catalogs.as_ref() {
+            Some(catalogs) => print_field(
+                "Catalogs",
+                format!(
+                    "{}/{}/{}",
+                    catalogs.namespace, catalogs.id, catalogs.version
+                ),
+            ),
+            None => print_field("Catalogs", "-"),
+        };
+        print_field(
+            "Catalogs Path",
+            self.catalogs_path.as_deref().unwrap_or("-"),
+        );
+        print_field("Working Directory", &self.working_directory);
+        print_field("Main PID", self.main_pid);
+        print_field("Exec Main Code", self.exec_main_code);
+        print_field("Exec Main Status", self.exec_main_status);
+        print_field("Restarts", self.n_restarts);
+        let active_enter = match self.active_enter_timestamp {
+            0 => String::from("-"),
+            timestamp => format_timestamp_micros(timestamp),
+        };
+        print_field("Active Enter", active_enter);
+        let inactive_enter = match self.inactive_enter_timestamp {
+            0 => String::from("-"),
+            timestamp => format_timestamp_micros(timestamp),
+        };
+        print_field("Inactive Enter", inactive_enter);
+        println!("Envs:");
+        for env in &self.envs {
+            println!("  {}={}", env.key, env.value);
         }
+        println!("Unit: {}", self.unit_path);
+        print!("{}", self.unit);
     }
 }
 
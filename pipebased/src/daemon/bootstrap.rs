@@ -3,6 +3,7 @@ use pipebased_common::{
     Daemon, DaemonConfig, PipeManager, PipeManagerConfig, RepositoryManager,
     RepositoryManagerConfig,
 };
+use tracing::{info, warn};
 
 fn build_repository_manager(config: RepositoryManagerConfig) -> RepositoryManager {
     let pb_client_config = config.pb_client;
@@ -16,19 +17,40 @@ fn build_repository_manager(config: RepositoryManagerConfig) -> RepositoryManage
 }
 
 fn build_pipe_manager(config: PipeManagerConfig) -> PipeManager {
-    let workspace = config.workspace;
-    PipeManager::builder().workspace(workspace).build()
+    let mut builder = PipeManager::builder().workspace(config.workspace);
+    if let Some(unit_prefix) = config.unit_prefix {
+        builder = builder.unit_prefix(unit_prefix);
+    }
+    if let Some(slice) = config.slice {
+        builder = builder.slice(slice);
+    }
+    if let Some(start_mode) = config.start_mode {
+        builder = builder.start_mode(start_mode);
+    }
+    if let Some(stop_mode) = config.stop_mode {
+        builder = builder.stop_mode(stop_mode);
+    }
+    builder.build()
 }
 
 fn build_daemon(config: DaemonConfig) -> Daemon {
     let pipe_manager_config = config.pipe;
     let repository_manager_config = config.repository;
+    let reconciler_config = config.reconciler;
     let pipe_manager = build_pipe_manager(pipe_manager_config);
     let repository_manager = build_repository_manager(repository_manager_config);
-    Daemon::builder()
+    let daemon = Daemon::builder()
         .pipe_manager(pipe_manager)
         .repository_manager(repository_manager)
-        .build()
+        .build();
+    info!("migrate pipe register ...");
+    if let Err(err) = daemon.migrate_pipe_register() {
+        warn!("migrate pipe register failed, error: {:#?}", err);
+    }
+    daemon.watch_pipes();
+    daemon.probe_pipes();
+    daemon.reconcile(reconciler_config);
+    daemon
 }
 
 pub fn bootstrap(config: DaemonConfig) -> DaemonService {
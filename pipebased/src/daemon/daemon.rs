@@ -1,5 +1,12 @@
-use pipebased_common::{grpc, AppDescriptor, CatalogsDescriptor, Daemon, Descriptor};
-use tracing::{error, info};
+use pipebased_common::{
+    grpc, AppDescriptor, CatalogsDescriptor, Daemon, Descriptor, LabelSelector, PipeLogQuery,
+};
+use std::pin::Pin;
+use tokio_stream::{
+    wrappers::{BroadcastStream, ReceiverStream},
+    Stream, StreamExt,
+};
+use tracing::{error, info, warn};
 
 pub struct DaemonServiceBuilder {
     pub daemon: Option<Daemon>,
@@ -206,36 +213,15 @@ impl grpc::daemon::daemon_server::Daemon for DaemonService {
         &self,
         request: tonic::Request<grpc::daemon::CreatePipeRequest>,
     ) -> Result<tonic::Response<grpc::daemon::CreatePipeResponse>, tonic::Status> {
-        let request = request.into_inner();
-        let id = request.id;
-        let app: AppDescriptor = match request.app {
-            Some(app) => app.into(),
-            None => return Err(tonic::Status::invalid_argument("app descriptor undefined")),
-        };
-        let catalogs: CatalogsDescriptor = match request.catalogs {
-            Some(catalogs) => catalogs.into(),
-            None => {
-                return Err(tonic::Status::invalid_argument(
-                    "catalogs descriptor undefined",
-                ))
+        let desc: Descriptor = match request.into_inner().try_into() {
+            Ok(desc) => desc,
+            Err(err) => {
+                return Err(tonic::Status::invalid_argument(format!(
+                    "create pipe failed, error: {:#?}",
+                    err
+                )))
             }
         };
-        let mut builder = Descriptor::builder()
-            .id(id)
-            .app_descriptor(app)
-            .catalogs_descriptor(catalogs);
-        builder = match request.user {
-            Some(user) => builder.user(user),
-            None => builder,
-        };
-        builder = match request.group {
-            Some(group) => builder.group(group),
-            None => builder,
-        };
-        for env in request.envs {
-            builder = builder.env(env.key, env.value);
-        }
-        let desc = builder.build();
         match self.daemon.create_pipe(desc) {
             Ok(_) => Ok(tonic::Response::new(grpc::daemon::CreatePipeResponse {})),
             Err(err) => Err(tonic::Status::invalid_argument(format!(
@@ -250,8 +236,24 @@ impl grpc::daemon::daemon_server::Daemon for DaemonService {
         request: tonic::Request<grpc::daemon::StartPipeRequest>,
     ) -> Result<tonic::Response<grpc::daemon::StartPipeResponse>, tonic::Status> {
         let request = request.into_inner();
-        match self.daemon.start_pipe(request.id.as_str()) {
-            Ok(_) => Ok(tonic::Response::new(grpc::daemon::StartPipeResponse {})),
+        match self
+            .daemon
+            .start_pipe(
+                request.id.as_str(),
+                request.mode.as_deref(),
+                request.wait,
+                request.timeout,
+            )
+            .await
+        {
+            Ok(result) => Ok(tonic::Response::new(grpc::daemon::StartPipeResponse {
+                job_result: result.map(|result| result.to_string()),
+            })),
+            // conflicts with pending jobs, retry later
+            Err(err) if err.is_job_conflict() => Err(tonic::Status::aborted(format!(
+                "start pipe failed, error: {:#?}",
+                err
+            ))),
             Err(err) => Err(tonic::Status::invalid_argument(format!(
                 "start pipe failed, error: {:#?}",
                 err
@@ -259,13 +261,50 @@ impl grpc::daemon::daemon_server::Daemon for DaemonService {
         }
     }
 
+    async fn start_pipes(
+        &self,
+        request: tonic::Request<grpc::daemon::StartPipesRequest>,
+    ) -> Result<tonic::Response<grpc::daemon::StartPipesResponse>, tonic::Status> {
+        let request = request.into_inner();
+        match self.daemon.start_pipes(&request.ids) {
+            Ok(ids) => Ok(tonic::Response::new(grpc::daemon::StartPipesResponse {
+                ids,
+            })),
+            Err(err) => Err(tonic::Status::invalid_argument(format!(
+                "start pipes failed, error: {:#?}",
+                err
+            ))),
+        }
+    }
+
     async fn stop_pipe(
         &self,
         request: tonic::Request<grpc::daemon::StopPipeRequest>,
     ) -> Result<tonic::Response<grpc::daemon::StopPipeResponse>, tonic::Status> {
         let request = request.into_inner();
-        match self.daemon.stop_pipe(request.id.as_str()) {
-            Ok(_) => Ok(tonic::Response::new(grpc::daemon::StopPipeResponse {})),
+        match self
+            .daemon
+            .stop_pipe(
+                request.id.as_str(),
+                request.mode.as_deref(),
+                request.wait,
+                request.timeout,
+            )
+            .await
+        {
+            Ok(Some((result, exit))) => Ok(tonic::Response::new(grpc::daemon::StopPipeResponse {
+                exit: Some(exit.into()),
+                job_result: Some(result.to_string()),
+            })),
+            Ok(None) => Ok(tonic::Response::new(grpc::daemon::StopPipeResponse {
+                exit: None,
+                job_result: None,
+            })),
+            // conflicts with pending jobs, retry later
+            Err(err) if err.is_job_conflict() => Err(tonic::Status::aborted(format!(
+                "stop pipe failed, error: {:#?}",
+                err
+            ))),
             Err(err) => Err(tonic::Status::invalid_argument(format!(
                 "stop pipe failed, error: {:#?}",
                 err
@@ -273,12 +312,188 @@ impl grpc::daemon::daemon_server::Daemon for DaemonService {
         }
     }
 
+    async fn restart_pipe(
+        &self,
+        request: tonic::Request<grpc::daemon::RestartPipeRequest>,
+    ) -> Result<tonic::Response<grpc::daemon::RestartPipeResponse>, tonic::Status> {
+        let request = request.into_inner();
+        match self.daemon.restart_pipe(request.id.as_str()).await {
+            Ok(_) => Ok(tonic::Response::new(grpc::daemon::RestartPipeResponse {})),
+            Err(err) => Err(tonic::Status::invalid_argument(format!(
+                "restart pipe failed, error: {:#?}",
+                err
+            ))),
+        }
+    }
+
+    async fn reload_pipe(
+        &self,
+        request: tonic::Request<grpc::daemon::ReloadPipeRequest>,
+    ) -> Result<tonic::Response<grpc::daemon::ReloadPipeResponse>, tonic::Status> {
+        let request = request.into_inner();
+        match self.daemon.reload_pipe(request.id.as_str()) {
+            Ok(_) => Ok(tonic::Response::new(grpc::daemon::ReloadPipeResponse {})),
+            Err(err) => Err(tonic::Status::invalid_argument(format!(
+                "reload pipe failed, error: {:#?}",
+                err
+            ))),
+        }
+    }
+
+    async fn kill_pipe(
+        &self,
+        request: tonic::Request<grpc::daemon::KillPipeRequest>,
+    ) -> Result<tonic::Response<grpc::daemon::KillPipeResponse>, tonic::Status> {
+        let request = request.into_inner();
+        match self.daemon.kill_pipe(
+            request.id.as_str(),
+            request.signal.as_str(),
+            request.whom.as_deref(),
+        ) {
+            Ok(_) => Ok(tonic::Response::new(grpc::daemon::KillPipeResponse {})),
+            Err(err) => Err(tonic::Status::invalid_argument(format!(
+                "kill pipe failed, error: {:#?}",
+                err
+            ))),
+        }
+    }
+
+    async fn enable_pipe(
+        &self,
+        request: tonic::Request<grpc::daemon::EnablePipeRequest>,
+    ) -> Result<tonic::Response<grpc::daemon::EnablePipeResponse>, tonic::Status> {
+        let request = request.into_inner();
+        match self.daemon.enable_pipe(request.id.as_str()) {
+            Ok(_) => Ok(tonic::Response::new(grpc::daemon::EnablePipeResponse {})),
+            Err(err) => Err(tonic::Status::invalid_argument(format!(
+                "enable pipe failed, error: {:#?}",
+                err
+            ))),
+        }
+    }
+
+    async fn disable_pipe(
+        &self,
+        request: tonic::Request<grpc::daemon::DisablePipeRequest>,
+    ) -> Result<tonic::Response<grpc::daemon::DisablePipeResponse>, tonic::Status> {
+        let request = request.into_inner();
+        match self.daemon.disable_pipe(request.id.as_str()) {
+            Ok(_) => Ok(tonic::Response::new(grpc::daemon::DisablePipeResponse {})),
+            Err(err) => Err(tonic::Status::invalid_argument(format!(
+                "disable pipe failed, error: {:#?}",
+                err
+            ))),
+        }
+    }
+
+    async fn update_pipe(
+        &self,
+        request: tonic::Request<grpc::daemon::UpdatePipeRequest>,
+    ) -> Result<tonic::Response<grpc::daemon::UpdatePipeResponse>, tonic::Status> {
+        let request = request.into_inner();
+        let id = request.id;
+        let app: Option<AppDescriptor> = request.app.map(|app| app.into());
+        let catalogs: Option<CatalogsDescriptor> = request.catalogs.map(|catalogs| catalogs.into());
+        info!(pipe_id = id.as_str(), "update pipe");
+        match self
+            .daemon
+            .update_pipe(
+                id.as_str(),
+                app.as_ref(),
+                catalogs.as_ref(),
+                request.timeout,
+            )
+            .await
+        {
+            Ok(_) => Ok(tonic::Response::new(grpc::daemon::UpdatePipeResponse {})),
+            Err(err) => Err(tonic::Status::invalid_argument(format!(
+                "update pipe failed, error: {:#?}",
+                err
+            ))),
+        }
+    }
+
+    async fn inspect_pipe(
+        &self,
+        request: tonic::Request<grpc::daemon::InspectPipeRequest>,
+    ) -> Result<tonic::Response<grpc::daemon::InspectPipeResponse>, tonic::Status> {
+        let request = request.into_inner();
+        match self.daemon.inspect_pipe(request.id.as_str()) {
+            Ok(inspection) => Ok(tonic::Response::new(inspection.into())),
+            Err(err) => Err(tonic::Status::invalid_argument(format!(
+                "inspect pipe failed, error: {:#?}",
+                err
+            ))),
+        }
+    }
+
+    type StreamPipeLogsStream =
+        Pin<Box<dyn Stream<Item = Result<grpc::daemon::PipeLogEntry, tonic::Status>> + Send>>;
+
+    async fn stream_pipe_logs(
+        &self,
+        request: tonic::Request<grpc::daemon::StreamPipeLogsRequest>,
+    ) -> Result<tonic::Response<Self::StreamPipeLogsStream>, tonic::Status> {
+        let request = request.into_inner();
+        let id = request.id.to_owned();
+        let query: PipeLogQuery = request.into();
+        info!(pipe_id = id.as_str(), "stream pipe logs");
+        match self.daemon.stream_pipe_logs(id.as_str(), query) {
+            Ok(entries) => {
+                #[allow(clippy::result_large_err)]
+                let entries = ReceiverStream::new(entries).map(|entry| match entry {
+                    Ok(entry) => Ok(entry.into()),
+                    Err(err) => Err(tonic::Status::internal(format!(
+                        "stream pipe logs failed, error: {:#?}",
+                        err
+                    ))),
+                });
+                Ok(tonic::Response::new(Box::pin(entries)))
+            }
+            Err(err) => Err(tonic::Status::invalid_argument(format!(
+                "stream pipe logs failed, error: {:#?}",
+                err
+            ))),
+        }
+    }
+
+    type WatchPipesStream =
+        Pin<Box<dyn Stream<Item = Result<grpc::daemon::PipeEvent, tonic::Status>> + Send>>;
+
+    async fn watch_pipes(
+        &self,
+        request: tonic::Request<grpc::daemon::WatchPipesRequest>,
+    ) -> Result<tonic::Response<Self::WatchPipesStream>, tonic::Status> {
+        let ids = request.into_inner().ids;
+        info!("watch pipes {:?}", ids);
+        let events = BroadcastStream::new(self.daemon.subscribe_pipe_events());
+        let events = events.filter_map(move |event| match event {
+            Ok(event) if ids.is_empty() || ids.contains(&event.id) => Some(Ok(event.into())),
+            Ok(_) => None,
+            Err(err) => {
+                // slow subscriber
+                warn!("watch pipes lagged, error: {:#?}", err);
+                None
+            }
+        });
+        Ok(tonic::Response::new(Box::pin(events)))
+    }
+
     async fn remove_pipe(
         &self,
         request: tonic::Request<grpc::daemon::RemovePipeRequest>,
     ) -> Result<tonic::Response<grpc::daemon::RemovePipeResponse>, tonic::Status> {
         let request = request.into_inner();
-        match self.daemon.remove_pipe(request.id.as_str()) {
+        match self
+            .daemon
+            .remove_pipe(
+                request.id.as_str(),
+                request.force,
+                request.keep_data,
+                request.purge_failed,
+            )
+            .await
+        {
             Ok(_) => Ok(tonic::Response::new(grpc::daemon::RemovePipeResponse {})),
             Err(err) => Err(tonic::Status::invalid_argument(format!(
                 "remove pipe failed, error: {:#?}",
@@ -289,9 +504,20 @@ impl grpc::daemon::daemon_server::Daemon for DaemonService {
 
     async fn list_pipe(
         &self,
-        _request: tonic::Request<grpc::daemon::ListPipeRequest>,
+        request: tonic::Request<grpc::daemon::ListPipeRequest>,
     ) -> Result<tonic::Response<grpc::daemon::ListPipeResponse>, tonic::Status> {
-        match self.daemon.list_pipe_status() {
+        let request = request.into_inner();
+        let selector: Option<LabelSelector> = match request.selector.as_deref().map(str::parse) {
+            Some(Ok(selector)) => Some(selector),
+            Some(Err(err)) => {
+                return Err(tonic::Status::invalid_argument(format!(
+                    "list pipe failed, error: {:#?}",
+                    err
+                )))
+            }
+            None => None,
+        };
+        match self.daemon.list_pipe_status(selector.as_ref()) {
             Ok(pipes) => {
                 let pipes: Vec<grpc::daemon::PipeState> = pipes
                     .into_iter()
@@ -313,4 +539,109 @@ impl grpc::daemon::daemon_server::Daemon for DaemonService {
             }
         }
     }
+
+    async fn get_pipe_stats(
+        &self,
+        request: tonic::Request<grpc::daemon::GetPipeStatsRequest>,
+    ) -> Result<tonic::Response<grpc::daemon::GetPipeStatsResponse>, tonic::Status> {
+        let request = request.into_inner();
+        match self.daemon.pipe_stats(&request.ids) {
+            Ok(stats) => {
+                let stats: Vec<grpc::daemon::PipeStats> =
+                    stats.into_iter().map(|stats| stats.into()).collect();
+                Ok(tonic::Response::new(grpc::daemon::GetPipeStatsResponse {
+                    stats,
+                }))
+            }
+            Err(err) => Err(tonic::Status::invalid_argument(format!(
+                "get pipe stats failed, error: {:#?}",
+                err
+            ))),
+        }
+    }
+
+    async fn list_pipe_runs(
+        &self,
+        request: tonic::Request<grpc::daemon::ListPipeRunsRequest>,
+    ) -> Result<tonic::Response<grpc::daemon::ListPipeRunsResponse>, tonic::Status> {
+        let request = request.into_inner();
+        match self.daemon.list_pipe_runs(request.id.as_str()) {
+            Ok(runs) => {
+                let runs: Vec<grpc::daemon::PipeRun> =
+                    runs.into_iter().map(|run| run.into()).collect();
+                Ok(tonic::Response::new(grpc::daemon::ListPipeRunsResponse {
+                    runs,
+                }))
+            }
+            Err(err) => Err(tonic::Status::invalid_argument(format!(
+                "list pipe runs failed, error: {:#?}",
+                err
+            ))),
+        }
+    }
+
+    async fn apply_pipes(
+        &self,
+        request: tonic::Request<grpc::daemon::ApplyPipesRequest>,
+    ) -> Result<tonic::Response<grpc::daemon::ApplyPipesResponse>, tonic::Status> {
+        let request = request.into_inner();
+        let mut descs: Vec<Descriptor> = vec![];
+        for pipe in request.pipes {
+            match pipe.try_into() {
+                Ok(desc) => descs.push(desc),
+                Err(err) => {
+                    return Err(tonic::Status::invalid_argument(format!(
+                        "apply pipes failed, error: {:#?}",
+                        err
+                    )))
+                }
+            }
+        }
+        info!(
+            dry_run = request.dry_run,
+            prune = request.prune,
+            "apply {} pipes",
+            descs.len()
+        );
+        match self
+            .daemon
+            .apply_pipes(descs, request.dry_run, request.prune, request.timeout)
+            .await
+        {
+            Ok(changes) => {
+                let changes: Vec<grpc::daemon::PipeChange> =
+                    changes.into_iter().map(|change| change.into()).collect();
+                Ok(tonic::Response::new(grpc::daemon::ApplyPipesResponse {
+                    changes,
+                }))
+            }
+            Err(err) => Err(tonic::Status::invalid_argument(format!(
+                "apply pipes failed, error: {:#?}",
+                err
+            ))),
+        }
+    }
+
+    async fn diagnose(
+        &self,
+        request: tonic::Request<grpc::daemon::DiagnoseRequest>,
+    ) -> Result<tonic::Response<grpc::daemon::DiagnoseResponse>, tonic::Status> {
+        let request = request.into_inner();
+        match self.daemon.diagnose(request.repair, request.purge) {
+            Ok(drifts) => {
+                let drifts: Vec<grpc::daemon::Drift> =
+                    drifts.into_iter().map(|drift| drift.into()).collect();
+                Ok(tonic::Response::new(grpc::daemon::DiagnoseResponse {
+                    drifts,
+                }))
+            }
+            Err(err) => {
+                error!("diagnose failed, error {:#?}", err);
+                Err(tonic::Status::internal(format!(
+                    "diagnose failed, error: {:#?}",
+                    err
+                )))
+            }
+        }
+    }
 }
@@ -0,0 +1,88 @@
+use crate::{grpc, Descriptor, PipeRecord};
+use serde_json::Value;
+use std::fmt::Display;
+
+#[derive(Clone, Debug)]
+pub enum PipeChangeType {
+    Create,
+    Update,
+    Remove,
+    Unchanged,
+}
+
+impl Display for PipeChangeType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let change_type = match self {
+            PipeChangeType::Create => "create",
+            PipeChangeType::Update => "update",
+            PipeChangeType::Remove => "remove",
+            PipeChangeType::Unchanged => "unchanged",
+        };
+        write!(f, "{}", change_type)
+    }
+}
+
+// planned change of a pipe between desired and live state
+#[derive(Clone, Debug)]
+pub struct PipeChange {
+    pub id: String,
+    pub change_type: PipeChangeType,
+    // artifacts to pull and fields changed
+    pub details: Vec<String>,
+    pub applied: bool,
+    // apply attempted but failed
+    pub error: Option<String>,
+}
+
+impl PipeChange {
+    pub(crate) fn new(id: String, change_type: PipeChangeType, details: Vec<String>) -> Self {
+        PipeChange {
+            id,
+            change_type,
+            details,
+            applied: false,
+            error: None,
+        }
+    }
+}
+
+impl From<PipeChange> for grpc::daemon::PipeChange {
+    fn from(origin: PipeChange) -> Self {
+        grpc::daemon::PipeChange {
+            id: origin.id,
+            change_type: origin.change_type.to_string(),
+            details: origin.details,
+            applied: origin.applied,
+            error: origin.error,
+        }
+    }
+}
+
+// fields of live pipe record differ from desired descriptor
+pub(crate) fn diff_pipe_record(desc: &Descriptor, record: &PipeRecord) -> Vec<String> {
+    let mut details: Vec<String> = vec![];
+    match record.app.as_ref() {
+        Some(app) if app == &desc.app_descriptor => (),
+        Some(app) => details.push(format!("app {} -> {}", app, desc.app_descriptor)),
+        None => details.push(format!("app unknown -> {}", desc.app_descriptor)),
+    };
+    match record.catalogs.as_ref() {
+        Some(catalogs) if catalogs == &desc.catalogs_descriptor => (),
+        Some(catalogs) => details.push(format!(
+            "catalogs {} -> {}",
+            catalogs, desc.catalogs_descriptor
+        )),
+        None => details.push(format!("catalogs unknown -> {}", desc.catalogs_descriptor)),
+    };
+    // compare options field by field in serialized form
+    let live = serde_json::to_value(&record.options).unwrap_or_default();
+    let desired = serde_json::to_value(desc.options()).unwrap_or_default();
+    if let (Value::Object(live), Value::Object(desired)) = (live, desired) {
+        for (field, value) in desired.iter() {
+            if live.get(field) != Some(value) {
+                details.push(format!("{} changed", field));
+            }
+        }
+    }
+    details
+}
@@ -8,12 +8,62 @@ pub(crate) const PATH_CATALOGS_REGISTER: &str = "catalogs.reg";
 pub(crate) const PATH_CATALOGS_LOCK: &str = "catalogs.lock";
 pub(crate) const PATH_PIPE_LOCK: &str = "pipe.lock";
 pub(crate) const PATH_PIPE_REGISTER: &str = "pipe.reg";
+// run history of oneshot pipe in working directory
+pub(crate) const PATH_PIPE_RUNS: &str = ".runs.yml";
+// record of removed pipe in kept working directory, skipped by diagnose
+pub(crate) const PATH_PIPE_KEPT: &str = ".kept.yml";
+// version of pipe register format, id only register is version 0
+pub(crate) const PIPE_REGISTER_VERSION: u32 = 1;
 pub(crate) const SYSTEMD_DEFAULT_USER: &str = "pipebase";
 pub(crate) const SYSTEMD_DEFAULT_GROUP: &str = "pipebase";
 // https://www.freedesktop.org/wiki/Software/systemd/dbus/
-pub(crate) const SYSTEMD_DEFAULT_START_UNIT_MODE: &str = "replace";
-pub(crate) const SYSTEMD_DEFAULT_STOP_UNIT_MODE: &str = "replace";
+pub(crate) const SYSTEMD_SERVICE: &str = "org.freedesktop.systemd1";
+pub(crate) const SIGNAL_JOB_REMOVED: &str = "JobRemoved";
+pub(crate) const SYSTEMD_DEFAULT_RESTART_UNIT_MODE: &str = "replace";
+pub(crate) const SYSTEMD_DEFAULT_RELOAD_UNIT_MODE: &str = "replace";
 pub(crate) const SYSTEMD_DEFAULT_DESCRIPTION: &str = "a pipebase application";
+pub(crate) const SYSTEMD_UNIT_CONFIGURATION_DIRECTORY: &str = "/etc/systemd/system";
+// https://www.freedesktop.org/software/systemd/man/systemd.unit.html, UNIT_NAME_MAX excluding NUL
+pub(crate) const SYSTEMD_UNIT_NAME_MAX_LENGTH: usize = 255;
+pub(crate) const SYSTEMD_SERVICE_UNIT_SUFFIX: &str = ".service";
+pub(crate) const SYSTEMD_SLICE_UNIT_SUFFIX: &str = ".slice";
+pub(crate) const SYSTEMD_TIMER_UNIT_SUFFIX: &str = ".timer";
+// marks unit files generated for pipes, extension keys are ignored by systemd
+pub(crate) const SYSTEMD_PIPE_ID_KEY: &str = "X-Pipebase-Id";
+// pipe unit named as '<prefix><id>.service' and placed in slice
+pub(crate) const PIPE_DEFAULT_UNIT_PREFIX: &str = "pipebase-";
+pub(crate) const PIPE_DEFAULT_SLICE: &str = "pipebase.slice";
+// pipe update
+pub(crate) const PIPE_DEFAULT_UPDATE_TIMEOUT_SECS: u64 = 30;
+pub(crate) const PIPE_STATUS_POLL_INTERVAL_MILLIS: u64 = 500;
+// wait for start / stop job, systemd default stop timeout
+pub(crate) const PIPE_DEFAULT_JOB_TIMEOUT_SECS: u64 = 90;
+// env keys containing any of patterns are considered secret and masked on inspect
+pub(crate) const PIPE_SECRET_ENV_KEY_PATTERNS: &[&str] = &[
+    "SECRET",
+    "PASSWORD",
+    "PASSWD",
+    "TOKEN",
+    "CREDENTIAL",
+    "PRIVATE_KEY",
+    "API_KEY",
+    "ACCESS_KEY",
+];
+pub(crate) const PIPE_SECRET_ENV_MASK: &str = "******";
+// pipe events
+pub(crate) const PIPE_EVENT_CHANNEL_CAPACITY: usize = 256;
+pub(crate) const PIPE_WATCH_RETRY_INTERVAL_SECS: u64 = 5;
+// background reconciler
+pub(crate) const PIPE_RECONCILE_DEFAULT_INTERVAL_SECS: u64 = 300;
+// health probes
+pub(crate) const PIPE_PROBE_DEFAULT_HOST: &str = "127.0.0.1";
+pub(crate) const PIPE_PROBE_DEFAULT_INTERVAL_SECS: u64 = 10;
+pub(crate) const PIPE_PROBE_DEFAULT_TIMEOUT_SECS: u64 = 1;
+pub(crate) const PIPE_PROBE_DEFAULT_FAILURE_THRESHOLD: u32 = 3;
+// probe tasks synced with pipe register
+pub(crate) const PIPE_PROBE_SYNC_INTERVAL_SECS: u64 = 5;
+// oneshot pipe runs kept in history
+pub(crate) const PIPE_RUN_HISTORY_CAPACITY: usize = 100;
 // logging
 pub(crate) const FORMATTER_FULL: &str = "full";
 pub(crate) const FORMATTER_PRETTY: &str = "pretty";
@@ -1,21 +1,42 @@
 use crate::{
-    register_error, AppDescriptor, CatalogsDescriptor, EnvironmentVariable, PipeDescriptor,
-    PipeManager, PipeManagerConfig, PipeState, RepositoryManager, RepositoryManagerConfig,
-    ResourceType, Result,
+    diagnose, diff_pipe_record, grpc, order_by_dependencies, parse_signal, pipe_error,
+    register_error, run_probes, validate_labels, AppDescriptor, CatalogsDescriptor, Drift,
+    EnvironmentVariable, Error, JobListener, JobMode, JobResult, KillWhom, LabelSelector,
+    LimitDescriptor, PipeChange, PipeChangeType, PipeDependency, PipeDescriptor, PipeEvent,
+    PipeExit, PipeHealthRegistry, PipeInspection, PipeLogQuery, PipeLogReceiver, PipeManager,
+    PipeManagerConfig, PipeOperation, PipeOptions, PipeRecord, PipeRun, PipeState, PipeStats,
+    PipeWatcher, ProbeDescriptor, ReconcilerConfig, RepositoryManager, RepositoryManagerConfig,
+    ResourceType, RestartDescriptor, Result, ScheduleDescriptor, SecurityDescriptor, ServiceType,
+    StopDescriptor, PIPE_DEFAULT_JOB_TIMEOUT_SECS, PIPE_DEFAULT_UPDATE_TIMEOUT_SECS,
+    PIPE_EVENT_CHANNEL_CAPACITY, PIPE_RECONCILE_DEFAULT_INTERVAL_SECS,
+    PIPE_WATCH_RETRY_INTERVAL_SECS,
 };
 use serde::Deserialize;
-use std::path::PathBuf;
-use tracing::warn;
+use std::{
+    collections::{HashMap, HashSet},
+    path::{Path, PathBuf},
+    sync::Arc,
+    thread,
+    time::Duration,
+};
+use tokio::sync::broadcast;
+use tracing::{error, info, warn};
 
 #[derive(Deserialize)]
 pub struct DaemonConfig {
     pub repository: RepositoryManagerConfig,
     pub pipe: PipeManagerConfig,
+    #[serde(default)]
+    pub reconciler: ReconcilerConfig,
 }
 
 pub struct Daemon {
-    repository_manager: RepositoryManager,
+    // shared with background reconciler
+    repository_manager: Arc<RepositoryManager>,
     pipe_manager: PipeManager,
+    events: broadcast::Sender<PipeEvent>,
+    // shared with background probes
+    health: PipeHealthRegistry,
 }
 
 pub struct DaemonBuilder {
@@ -52,9 +73,12 @@ impl DaemonBuilder {
             .repository_manager
             .expect("repository manager undefined");
         let pipe_manager = self.pipe_manager.expect("pipe manager undefined");
+        let (events, _) = broadcast::channel(PIPE_EVENT_CHANNEL_CAPACITY);
         Daemon {
-            repository_manager,
+            repository_manager: Arc::new(repository_manager),
             pipe_manager,
+            events,
+            health: PipeHealthRegistry::default(),
         }
     }
 }
@@ -66,6 +90,19 @@ pub struct Descriptor {
     pub user: Option<String>,
     pub group: Option<String>,
     pub envs: Vec<EnvironmentVariable>,
+    pub env_files: Vec<String>,
+    pub restart: Option<RestartDescriptor>,
+    pub stop: Option<StopDescriptor>,
+    pub limits: Option<LimitDescriptor>,
+    pub security: Option<SecurityDescriptor>,
+    pub autostart: bool,
+    pub labels: HashMap<String, String>,
+    pub depends_on: Vec<PipeDependency>,
+    pub schedule: Option<ScheduleDescriptor>,
+    pub service_type: ServiceType,
+    pub remain_after_exit: Option<bool>,
+    pub liveness: Option<ProbeDescriptor>,
+    pub readiness: Option<ProbeDescriptor>,
     pub app_descriptor: AppDescriptor,
     pub catalogs_descriptor: CatalogsDescriptor,
 }
@@ -74,6 +111,147 @@ impl Descriptor {
     pub fn builder() -> DescriptorBuilder {
         DescriptorBuilder::new()
     }
+
+    // options persisted in pipe record
+    pub fn options(&self) -> PipeOptions {
+        PipeOptions {
+            description: self.description.to_owned(),
+            user: self.user.to_owned(),
+            group: self.group.to_owned(),
+            envs: self.envs.to_owned(),
+            env_files: self.env_files.to_owned(),
+            restart: self.restart.to_owned(),
+            stop: self.stop.to_owned(),
+            limits: self.limits.to_owned(),
+            security: self.security.to_owned(),
+            autostart: self.autostart,
+            labels: self.labels.to_owned(),
+            depends_on: self.depends_on.to_owned(),
+            schedule: self.schedule.to_owned(),
+            service_type: self.service_type.to_owned(),
+            remain_after_exit: self.remain_after_exit,
+            liveness: self.liveness.to_owned(),
+            readiness: self.readiness.to_owned(),
+        }
+    }
+
+    fn dependency_ids(&self) -> Vec<String> {
+        self.depends_on
+            .iter()
+            .map(|dependency| dependency.id.to_owned())
+            .collect()
+    }
+
+    fn record(&self) -> PipeRecord {
+        PipeRecord::new(
+            self.id.to_owned(),
+            self.app_descriptor.to_owned(),
+            self.catalogs_descriptor.to_owned(),
+            self.options(),
+        )
+    }
+
+    fn pipe_descriptor<'a>(
+        &self,
+        app_path: &'a Path,
+        catalogs_path: &'a Path,
+    ) -> PipeDescriptor<'a> {
+        PipeDescriptor::builder()
+            .id(self.id.to_owned())
+            .app_path(app_path)
+            .catalogs_path(catalogs_path)
+            .options(self.options())
+            .build()
+    }
+
+    // validate descriptor before any artifact pulled or file created
+    pub fn validate(&self) -> Result<()> {
+        validate_labels(&self.labels)?;
+        // paths resolved later, validate options only
+        self.pipe_descriptor(Path::new(""), Path::new(""))
+            .validate()
+    }
+}
+
+impl TryFrom<grpc::daemon::CreatePipeRequest> for Descriptor {
+    type Error = Error;
+
+    fn try_from(origin: grpc::daemon::CreatePipeRequest) -> Result<Self> {
+        let app: AppDescriptor = match origin.app {
+            Some(app) => app.into(),
+            None => {
+                return Err(pipe_error(
+                    PipeOperation::Validate,
+                    String::from("app descriptor undefined"),
+                ))
+            }
+        };
+        let catalogs: CatalogsDescriptor = match origin.catalogs {
+            Some(catalogs) => catalogs.into(),
+            None => {
+                return Err(pipe_error(
+                    PipeOperation::Validate,
+                    String::from("catalogs descriptor undefined"),
+                ))
+            }
+        };
+        let mut builder = Descriptor::builder()
+            .id(origin.id)
+            .app_descriptor(app)
+            .catalogs_descriptor(catalogs);
+        if let Some(description) = origin.description {
+            builder = builder.description(description);
+        }
+        if let Some(user) = origin.user {
+            builder = builder.user(user);
+        }
+        if let Some(group) = origin.group {
+            builder = builder.group(group);
+        }
+        for env in origin.envs {
+            builder = builder.env(env.key, env.value);
+        }
+        for env_file in origin.env_files {
+            builder = builder.env_file(env_file);
+        }
+        if let Some(restart) = origin.restart {
+            builder = builder.restart(restart.try_into()?);
+        }
+        if let Some(limits) = origin.limits {
+            builder = builder.limits(limits.into());
+        }
+        if let Some(security) = origin.security {
+            builder = builder.security(security.try_into()?);
+        }
+        if let Some(autostart) = origin.autostart {
+            builder = builder.autostart(autostart);
+        }
+        for (key, value) in origin.labels {
+            builder = builder.label(key, value);
+        }
+        for dependency in origin.depends_on {
+            builder = builder.depends_on(dependency.try_into()?);
+        }
+        if let Some(schedule) = origin.schedule {
+            builder = builder.schedule(schedule.into());
+        }
+        if let Some(service_type) = origin.service_type {
+            builder = builder.service_type(service_type.parse()?);
+        }
+        if let Some(remain_after_exit) = origin.remain_after_exit {
+            builder = builder.remain_after_exit(remain_after_exit);
+        }
+        if let Some(liveness) = origin.liveness {
+            builder = builder.liveness(liveness.try_into()?);
+        }
+        if let Some(readiness) = origin.readiness {
+            builder = builder.readiness(readiness.try_into()?);
+        }
+        if let Some(stop) = origin.stop {
+            builder = builder.stop(stop.try_into()?);
+        }
+        Ok(builder.build())
+    }
 }
 
 pub struct DescriptorBuilder {
@@ -82,6 +260,19 @@ pub struct DescriptorBuilder {
     pub user: Option<String>,
     pub group: Option<String>,
     pub envs: Vec<EnvironmentVariable>,
+    pub env_files: Vec<String>,
+    pub restart: Option<RestartDescriptor>,
+    pub stop: Option<StopDescriptor>,
+    pub limits: Option<LimitDescriptor>,
+    pub security: Option<SecurityDescriptor>,
+    pub autostart: bool,
+    pub labels: HashMap<String, String>,
+    pub depends_on: Vec<PipeDependency>,
+    pub schedule: Option<ScheduleDescriptor>,
+    pub service_type: ServiceType,
+    pub remain_after_exit: Option<bool>,
+    pub liveness: Option<ProbeDescriptor>,
+    pub readiness: Option<ProbeDescriptor>,
     pub app_descriptor: Option<AppDescriptor>,
     pub catalogs_descriptor: Option<CatalogsDescriptor>,
 }
@@ -94,6 +285,19 @@ impl DescriptorBuilder {
             user: None,
             group: None,
             envs: vec![],
+            env_files: vec![],
+            restart: None,
+            stop: None,
+            limits: None,
+            security: None,
+            autostart: false,
+            labels: HashMap::new(),
+            depends_on: vec![],
+            schedule: None,
+            service_type: ServiceType::default(),
+            remain_after_exit: None,
+            liveness: None,
+            readiness: None,
             app_descriptor: None,
             catalogs_descriptor: None,
         }
@@ -124,6 +328,71 @@ impl DescriptorBuilder {
         self
     }
 
+    pub fn env_file(mut self, env_file: String) -> Self {
+        self.env_files.push(env_file);
+        self
+    }
+
+    pub fn restart(mut self, restart: RestartDescriptor) -> Self {
+        self.restart = Some(restart);
+        self
+    }
+
+    pub fn stop(mut self, stop: StopDescriptor) -> Self {
+        self.stop = Some(stop);
+        self
+    }
+
+    pub fn limits(mut self, limits: LimitDescriptor) -> Self {
+        self.limits = Some(limits);
+        self
+    }
+
+    pub fn security(mut self, security: SecurityDescriptor) -> Self {
+        self.security = Some(security);
+        self
+    }
+
+    pub fn autostart(mut self, autostart: bool) -> Self {
+        self.autostart = autostart;
+        self
+    }
+
+    pub fn label(mut self, key: String, value: String) -> Self {
+        self.labels.insert(key, value);
+        self
+    }
+
+    pub fn depends_on(mut self, dependency: PipeDependency) -> Self {
+        self.depends_on.push(dependency);
+        self
+    }
+
+    pub fn schedule(mut self, schedule: ScheduleDescriptor) -> Self {
+        self.schedule = Some(schedule);
+        self
+    }
+
+    pub fn service_type(mut self, service_type: ServiceType) -> Self {
+        self.service_type = service_type;
+        self
+    }
+
+    pub fn remain_after_exit(mut self, remain_after_exit: bool) -> Self {
+        self.remain_after_exit = Some(remain_after_exit);
+        self
+    }
+
+    pub fn liveness(mut self, liveness: ProbeDescriptor) -> Self {
+        self.liveness = Some(liveness);
+        self
+    }
+
+    pub fn readiness(mut self, readiness: ProbeDescriptor) -> Self {
+        self.readiness = Some(readiness);
+        self
+    }
+
     pub fn app_descriptor(mut self, desc: AppDescriptor) -> Self {
         self.app_descriptor = Some(desc);
         self
@@ -140,6 +409,19 @@ impl DescriptorBuilder {
         let user = self.user;
         let group = self.group;
         let envs = self.envs;
+        let env_files = self.env_files;
+        let restart = self.restart;
+        let stop = self.stop;
+        let limits = self.limits;
+        let security = self.security;
+        let autostart = self.autostart;
+        let labels = self.labels;
+        let depends_on = self.depends_on;
+        let schedule = self.schedule;
+        let service_type = self.service_type;
+        let remain_after_exit = self.remain_after_exit;
+        let liveness = self.liveness;
+        let readiness = self.readiness;
         let app_descriptor = self.app_descriptor.expect("app descriptor undefined");
         let catalogs_descriptor = self
             .catalogs_descriptor
@@ -150,6 +432,19 @@ impl DescriptorBuilder {
             user,
             group,
             envs,
+            env_files,
+            restart,
+            stop,
+            limits,
+            security,
+            autostart,
+            labels,
+            depends_on,
+            schedule,
+            service_type,
+            remain_after_exit,
+            liveness,
+            readiness,
             app_descriptor,
             catalogs_descriptor,
         }
@@ -202,6 +497,18 @@ impl Daemon {
 
     // pipe operations
     pub fn create_pipe(&self, desc: Descriptor) -> Result<()> {
+        self.init_pipe(&desc)
+    }
+
+    fn init_pipe(&self, desc: &Descriptor) -> Result<()> {
+        desc.validate()?;
+        let (app_path, catalogs_path) = self.resolve_artifact_paths(desc)?;
+        let pipe_descriptor = desc.pipe_descriptor(app_path.as_path(), catalogs_path.as_path());
+        self.pipe_manager.init(&pipe_descriptor, desc.record())
+    }
+
+    // paths of registered app and catalogs of descriptor
+    fn resolve_artifact_paths(&self, desc: &Descriptor) -> Result<(PathBuf, PathBuf)> {
         let app_descriptor = &desc.app_descriptor;
         let app_path = match self.check_app_registered(app_descriptor)? {
             Some(path) => path,
@@ -222,51 +529,495 @@ impl Daemon {
                 ))
             }
         };
-        let mut builder = PipeDescriptor::builder()
-            .id(desc.id)
-            .app_path(app_path.as_path())
-            .catalogs_path(catalogs_path.as_path());
-        for env in desc.envs {
-            builder = builder.env(env);
-        }
-        let builder = match desc.description {
-            Some(description) => builder.description(description),
-            None => builder,
+        Ok((app_path, catalogs_path))
+    }
+
+    // start pipe, block until start job removed and report job result if wait
+    pub async fn start_pipe(
+        &self,
+        id: &str,
+        mode: Option<&str>,
+        wait: bool,
+        timeout: Option<u64>,
+    ) -> Result<Option<JobResult>> {
+        let mode = mode.map(str::parse::<JobMode>).transpose()?;
+        if !wait {
+            self.pipe_manager.start(id, mode.as_ref())?;
+            return Ok(None);
+        }
+        let listener = JobListener::new().await?;
+        let job = self.pipe_manager.start(id, mode.as_ref())?;
+        let result = Self::wait_job(listener, &job, PipeOperation::Start, id, timeout).await?;
+        Ok(Some(result))
+    }
+
+    // start pipes with dependencies first, returns ids in start order
+    pub fn start_pipes(&self, ids: &[String]) -> Result<Vec<String>> {
+        let mut pipes: Vec<(String, Vec<String>)> = vec![];
+        for id in ids {
+            let record = self.get_pipe_record(id)?;
+            let dependency_ids = record
+                .options
+                .depends_on
+                .into_iter()
+                .map(|dependency| dependency.id)
+                .collect();
+            pipes.push((record.id, dependency_ids));
+        }
+        let ordered = order_by_dependencies(&pipes)?;
+        for id in ordered.iter() {
+            self.pipe_manager.start(id, None)?;
+        }
+        Ok(ordered)
+    }
+
+    // stop pipe, block until stop job removed and report job result and exit if wait
+    pub async fn stop_pipe(
+        &self,
+        id: &str,
+        mode: Option<&str>,
+        wait: bool,
+        timeout: Option<u64>,
+    ) -> Result<Option<(JobResult, PipeExit)>> {
+        let mode = mode.map(str::parse::<JobMode>).transpose()?;
+        if !wait {
+            self.pipe_manager.stop(id, mode.as_ref())?;
+            return Ok(None);
+        }
+        let listener = JobListener::new().await?;
+        let job = self.pipe_manager.stop(id, mode.as_ref())?;
+        let result = Self::wait_job(listener, &job, PipeOperation::Stop, id, timeout).await?;
+        let exit = self.pipe_manager.exit(id)?;
+        Ok(Some((result, exit)))
+    }
+
+    async fn wait_job(
+        listener: JobListener,
+        job: &zvariant::OwnedObjectPath,
+        op: PipeOperation,
+        id: &str,
+        timeout: Option<u64>,
+    ) -> Result<JobResult> {
+        let timeout = timeout.unwrap_or(PIPE_DEFAULT_JOB_TIMEOUT_SECS);
+        match listener.wait(job, Duration::from_secs(timeout)).await? {
+            Some(result) => Ok(result),
+            None => Err(pipe_error(
+                op,
+                format!("job of pipe '{}' not completed in {} seconds", id, timeout),
+            )),
+        }
+    }
+
+    // restart of legacy unit waits until stopped for migration, off the async runtime
+    pub async fn restart_pipe(&self, id: &str) -> Result<()> {
+        let pipe_manager = self.pipe_manager.clone();
+        let id = id.to_owned();
+        match tokio::task::spawn_blocking(move || pipe_manager.restart(id.as_str())).await {
+            Ok(restarted) => restarted,
+            Err(err) => Err(pipe_error(
+                PipeOperation::Restart,
+                format!("restart task failed, error: {:#?}", err),
+            )),
+        }
+    }
+
+    pub fn reload_pipe(&self, id: &str) -> Result<()> {
+        self.pipe_manager.reload(id)
+    }
+
+    pub fn kill_pipe(&self, id: &str, signal: &str, whom: Option<&str>) -> Result<()> {
+        let signal = parse_signal(signal)?;
+        let whom = match whom {
+            Some(whom) => whom.parse::<KillWhom>()?,
+            None => KillWhom::default(),
         };
-        let builder = match desc.user {
-            Some(user) => builder.user(user),
-            None => builder,
+        self.pipe_manager.kill(id, &whom, signal)
+    }
+
+    pub fn enable_pipe(&self, id: &str) -> Result<()> {
+        self.pipe_manager.enable(id)
+    }
+
+    pub fn disable_pipe(&self, id: &str) -> Result<()> {
+        self.pipe_manager.disable(id)
+    }
+
+    // swap pipe app and / or catalogs with pulled versions
+    pub async fn update_pipe(
+        &self,
+        id: &str,
+        app_descriptor: Option<&AppDescriptor>,
+        catalogs_descriptor: Option<&CatalogsDescriptor>,
+        timeout: Option<u64>,
+    ) -> Result<()> {
+        if app_descriptor.is_none() && catalogs_descriptor.is_none() {
+            return Err(pipe_error(
+                PipeOperation::Update,
+                String::from("neither app nor catalogs given"),
+            ));
+        }
+        let app_path = match app_descriptor {
+            Some(app_descriptor) => match self.check_app_registered(app_descriptor)? {
+                Some(path) => Some(path),
+                None => {
+                    return Err(register_error(
+                        ResourceType::App,
+                        format!("app {} not found", app_descriptor),
+                    ))
+                }
+            },
+            None => None,
         };
-        let builder = match desc.group {
-            Some(group) => builder.group(group),
-            None => builder,
+        let catalogs_path = match catalogs_descriptor {
+            Some(catalogs_descriptor) => {
+                match self.check_catalogs_registered(catalogs_descriptor)? {
+                    Some(path) => Some(path),
+                    None => {
+                        return Err(register_error(
+                            ResourceType::Catalogs,
+                            format!("catalogs {} not found", catalogs_descriptor),
+                        ))
+                    }
+                }
+            }
+            None => None,
         };
-        let pipe_descriptor = builder.build();
-        self.pipe_manager.init(&pipe_descriptor)
+        let timeout = Duration::from_secs(timeout.unwrap_or(PIPE_DEFAULT_UPDATE_TIMEOUT_SECS));
+        let app = app_descriptor.zip(app_path.as_deref());
+        let catalogs = catalogs_descriptor.zip(catalogs_path.as_deref());
+        self.pipe_manager.update(id, app, catalogs, timeout).await
+    }
+
+    // converge live pipes to desired descriptors, plan only if dry run,
+    // remove live pipes not declared if prune
+    pub async fn apply_pipes(
+        &self,
+        descs: Vec<Descriptor>,
+        dry_run: bool,
+        prune: bool,
+        timeout: Option<u64>,
+    ) -> Result<Vec<PipeChange>> {
+        let mut ids: HashSet<&str> = HashSet::new();
+        for desc in descs.iter() {
+            if !ids.insert(desc.id.as_str()) {
+                return Err(pipe_error(
+                    PipeOperation::Apply,
+                    format!("duplicate pipe id '{}'", desc.id),
+                ));
+            }
+            desc.validate()?;
+        }
+        // create dependencies before dependents
+        let pipes: Vec<(String, Vec<String>)> = descs
+            .iter()
+            .map(|desc| (desc.id.to_owned(), desc.dependency_ids()))
+            .collect();
+        let ordered = order_by_dependencies(&pipes)?;
+        let descs: Vec<&Descriptor> = ordered
+            .iter()
+            .filter_map(|id| descs.iter().find(|desc| &desc.id == id))
+            .collect();
+        let records = self.list_pipe_records()?;
+        let timeout = Duration::from_secs(timeout.unwrap_or(PIPE_DEFAULT_UPDATE_TIMEOUT_SECS));
+        let mut changes: Vec<PipeChange> = vec![];
+        for desc in descs {
+            let mut details = self.plan_artifact_pulls(desc)?;
+            let change_type = match records.iter().find(|record| record.id == desc.id) {
+                Some(record) => {
+                    let diffs = diff_pipe_record(desc, record);
+                    let change_type = match diffs.is_empty() {
+                        true => PipeChangeType::Unchanged,
+                        false => PipeChangeType::Update,
+                    };
+                    details.extend(diffs);
+                    change_type
+                }
+                None => PipeChangeType::Create,
+            };
+            let mut change = PipeChange::new(desc.id.to_owned(), change_type, details);
+            if !dry_run {
+                match self.apply_pipe(desc, &change.change_type, timeout).await {
+                    Ok(_) => change.applied = true,
+                    Err(err) => change.error = Some(err.to_string()),
+                }
+            }
+            changes.push(change);
+        }
+        if !prune {
+            return Ok(changes);
+        }
+        // remove dependents before dependencies
+        let pipes: Vec<(String, Vec<String>)> = records
+            .iter()
+            .filter(|record| !ids.contains(record.id.as_str()))
+            .map(|record| {
+                let dependency_ids = record
+                    .options
+                    .depends_on
+                    .iter()
+                    .map(|dependency| dependency.id.to_owned())
+                    .collect();
+                (record.id.to_owned(), dependency_ids)
+            })
+            .collect();
+        for id in order_by_dependencies(&pipes)?.iter().rev() {
+            let id = id.as_str();
+            let mut change = PipeChange::new(id.to_owned(), PipeChangeType::Remove, vec![]);
+            if !dry_run {
+                match self.prune_pipe(id).await {
+                    Ok(_) => change.applied = true,
+                    Err(err) => change.error = Some(err.to_string()),
+                }
+            }
+            changes.push(change);
+        }
+        Ok(changes)
     }
 
-    pub fn start_pipe(&self, id: &str) -> Result<()> {
-        self.pipe_manager.start(id)
+    // artifacts of descriptor not pulled yet
+    fn plan_artifact_pulls(&self, desc: &Descriptor) -> Result<Vec<String>> {
+        let mut details: Vec<String> = vec![];
+        if self.check_app_registered(&desc.app_descriptor)?.is_none() {
+            details.push(format!("pull app {}", desc.app_descriptor));
+        }
+        if self
+            .check_catalogs_registered(&desc.catalogs_descriptor)?
+            .is_none()
+        {
+            details.push(format!("pull catalogs {}", desc.catalogs_descriptor));
+        }
+        Ok(details)
     }
 
-    pub fn stop_pipe(&self, id: &str) -> Result<()> {
-        self.pipe_manager.stop(id)
+    async fn apply_pipe(
+        &self,
+        desc: &Descriptor,
+        change_type: &PipeChangeType,
+        timeout: Duration,
+    ) -> Result<()> {
+        if self.check_app_registered(&desc.app_descriptor)?.is_none() {
+            self.pull_app(&desc.app_descriptor).await?;
+        }
+        if self
+            .check_catalogs_registered(&desc.catalogs_descriptor)?
+            .is_none()
+        {
+            self.pull_catalogs(&desc.catalogs_descriptor).await?;
+        }
+        match change_type {
+            PipeChangeType::Create => self.init_pipe(desc),
+            PipeChangeType::Update => {
+                let (app_path, catalogs_path) = self.resolve_artifact_paths(desc)?;
+                let pipe_descriptor =
+                    desc.pipe_descriptor(app_path.as_path(), catalogs_path.as_path());
+                self.pipe_manager
+                    .reconfigure(&pipe_descriptor, desc.record(), timeout)
+                    .await
+            }
+            PipeChangeType::Remove | PipeChangeType::Unchanged => Ok(()),
+        }
     }
 
-    pub fn remove_pipe(&self, id: &str) -> Result<()> {
-        self.pipe_manager.remove(id)
+    // stop pipe, or timer of scheduled pipe, and remove
+    async fn prune_pipe(&self, id: &str) -> Result<()> {
+        self.stop_pipe_before_remove(id).await?;
+        self.pipe_manager.remove(id, false, false, false)
+    }
+
+    // stop pipe, or timer of scheduled pipe, and wait until stop job completed
+    async fn stop_pipe_before_remove(&self, id: &str) -> Result<()> {
+        let scheduled = self.get_pipe_record(id)?.options.schedule.is_some();
+        if !scheduled && self.pipe_status(id)?.is_inactive() {
+            return Ok(());
+        }
+        // stop job not done leaves pipe running, pipe not removable
+        if let Some((result, _)) = self.stop_pipe(id, None, true, None).await? {
+            if !result.is_done() {
+                return Err(pipe_error(
+                    PipeOperation::Remove,
+                    format!("stop job of pipe '{}' {}", id, result),
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    pub fn inspect_pipe(&self, id: &str) -> Result<PipeInspection> {
+        let mut inspection = self.pipe_manager.inspect(id)?;
+        self.fill_health(&mut inspection.state);
+        // fallback to resolve with current paths if not stored in register
+        if inspection.app.is_none() {
+            inspection.app = inspection
+                .app_path
+                .as_deref()
+                .and_then(|path| self.repository_manager.resolve_app_path(path));
+        }
+        if inspection.catalogs.is_none() {
+            inspection.catalogs = inspection
+                .catalogs_path
+                .as_deref()
+                .and_then(|path| self.repository_manager.resolve_catalogs_path(path));
+        }
+        Ok(inspection)
+    }
+
+    pub fn list_pipe_runs(&self, id: &str) -> Result<Vec<PipeRun>> {
+        self.pipe_manager.list_runs(id)
+    }
+
+    pub fn list_pipe_records(&self) -> Result<Vec<PipeRecord>> {
+        self.pipe_manager.list_pipe_records()
+    }
+
+    pub fn get_pipe_record(&self, id: &str) -> Result<PipeRecord> {
+        self.pipe_manager.get_pipe_record(id)
+    }
+
+    // watch pipe units in background, retry if watch interrupted
+    pub fn watch_pipes(&self) {
+        let pipe_manager = self.pipe_manager.clone();
+        let events = self.events.clone();
+        thread::spawn(move || loop {
+            let watcher = PipeWatcher::new(pipe_manager.clone(), events.clone());
+            if let Err(err) = watcher.run() {
+                error!("watch pipes failed, error: {:#?}", err);
+            }
+            thread::sleep(Duration::from_secs(PIPE_WATCH_RETRY_INTERVAL_SECS));
+        });
+    }
+
+    // probe pipes in background, one task per probed pipe
+    pub fn probe_pipes(&self) {
+        tokio::spawn(run_probes(self.pipe_manager.clone(), self.health.clone()));
+    }
+
+    // diagnose drifts between registers and files on disk, optionally repair
+    // and purge orphan pipe data
+    pub fn diagnose(&self, repair: bool, purge: bool) -> Result<Vec<Drift>> {
+        diagnose(&self.repository_manager, &self.pipe_manager, repair, purge)
+    }
+
+    // diagnose periodically in background and log drifts
+    pub fn reconcile(&self, config: ReconcilerConfig) {
+        let repository_manager = self.repository_manager.clone();
+        let pipe_manager = self.pipe_manager.clone();
+        let interval = config
+            .interval
+            .unwrap_or(PIPE_RECONCILE_DEFAULT_INTERVAL_SECS);
+        thread::spawn(move || loop {
+            thread::sleep(Duration::from_secs(interval));
+            let drifts = match diagnose(
+                &repository_manager,
+                &pipe_manager,
+                config.repair,
+                config.purge,
+            ) {
+                Ok(drifts) => drifts,
+                Err(err) => {
+                    error!("reconcile failed, error: {:#?}", err);
+                    continue;
+                }
+            };
+            for drift in drifts {
+                match (drift.repaired, drift.error) {
+                    (true, _) => info!(
+                        drift = %drift.drift_type,
+                        resource = drift.resource.as_str(),
+                        "drift repaired, detail: {}",
+                        drift.detail
+                    ),
+                    (false, Some(error)) => warn!(
+                        drift = %drift.drift_type,
+                        resource = drift.resource.as_str(),
+                        "repair drift failed, detail: {}, error: {}",
+                        drift.detail,
+                        error
+                    ),
+                    (false, None) => warn!(
+                        drift = %drift.drift_type,
+                        resource = drift.resource.as_str(),
+                        "drift detected, detail: {}",
+                        drift.detail
+                    ),
+                }
+            }
+        });
+    }
+
+    pub fn subscribe_pipe_events(&self) -> broadcast::Receiver<PipeEvent> {
+        self.events.subscribe()
+    }
+
+    // migrate pipe register to latest version
+    pub fn migrate_pipe_register(&self) -> Result<()> {
+        self.pipe_manager.migrate(
+            |path| self.repository_manager.resolve_app_path(path),
+            |path| self.repository_manager.resolve_catalogs_path(path),
+        )
+    }
+
+    pub fn stream_pipe_logs(&self, id: &str, query: PipeLogQuery) -> Result<PipeLogReceiver> {
+        query.validate()?;
+        self.pipe_manager.logs(id, &query)
+    }
+
+    // force stops pipe and resets failed state first, removes even if other pipes depend on it
+    pub async fn remove_pipe(
+        &self,
+        id: &str,
+        force: bool,
+        keep_data: bool,
+        purge_failed: bool,
+    ) -> Result<()> {
+        let registered = self
+            .list_pipe_register()?
+            .iter()
+            .any(|registered_id| registered_id == id);
+        if force && registered {
+            self.stop_pipe_before_remove(id).await?;
+        }
+        self.pipe_manager.remove(id, force, keep_data, purge_failed)
     }
 
     pub fn pipe_status(&self, id: &str) -> Result<PipeState> {
-        self.pipe_manager.status(id)
+        let mut state = self.pipe_manager.status(id)?;
+        self.fill_health(&mut state);
+        Ok(state)
+    }
+
+    fn fill_health(&self, state: &mut PipeState) {
+        let health = self.health.lock().expect("pipe health registry poisoned");
+        if let Some(health) = health.get(state.id.as_str()) {
+            state.liveness = health.liveness;
+            state.readiness = health.readiness;
+        }
+    }
+
+    // stats of given pipes, all pipes if none given
+    pub fn pipe_stats(&self, ids: &[String]) -> Result<Vec<PipeStats>> {
+        let ids: Vec<String> = match ids.is_empty() {
+            true => self.list_pipe_register()?,
+            false => ids.to_vec(),
+        };
+        ids.iter()
+            .map(|id| self.pipe_manager.stats(id.as_str()))
+            .collect()
     }
 
     pub fn list_pipe_register(&self) -> Result<Vec<String>> {
         self.pipe_manager.list_pipe_register()
     }
 
-    pub fn list_pipe_status(&self) -> Result<Vec<PipeState>> {
-        let pipe_ids = self.list_pipe_register()?;
+    // list status of pipes with labels matching selector, all pipes if selector undefined
+    pub fn list_pipe_status(&self, selector: Option<&LabelSelector>) -> Result<Vec<PipeState>> {
+        let pipe_ids: Vec<String> = self
+            .list_pipe_records()?
+            .into_iter()
+            .filter(|record| {
+                selector.is_none_or(|selector| selector.matches(&record.options.labels))
+            })
+            .map(|record| record.id)
+            .collect();
         let mut pipe_states: Vec<PipeState> = vec![];
         for pipe_id in pipe_ids.iter() {
             let pipe_state = match self.pipe_status(pipe_id.as_str()) {
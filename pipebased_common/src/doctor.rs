@@ -0,0 +1,128 @@
+use crate::{grpc, PipeManager, RepositoryManager, Result};
+use serde::Deserialize;
+use std::fmt::Display;
+
+#[derive(Clone, Debug)]
+pub enum DriftType {
+    // registered pipe without working directory
+    MissingWorkingDirectory,
+    // working directory without registered pipe
+    OrphanWorkingDirectory,
+    // registered pipe without unit file
+    MissingUnitFile,
+    // prefixed unit file without registered pipe
+    OrphanUnitFile,
+    // catalogs link of registered pipe missing or pointing to nothing
+    DanglingCatalogsLink,
+    // registered app or catalogs without files
+    MissingApp,
+    MissingCatalogs,
+    // app or catalogs files without register entry
+    UnregisteredApp,
+    UnregisteredCatalogs,
+}
+
+impl Display for DriftType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let drift_type = match self {
+            DriftType::MissingWorkingDirectory => "missing-working-directory",
+            DriftType::OrphanWorkingDirectory => "orphan-working-directory",
+            DriftType::MissingUnitFile => "missing-unit-file",
+            DriftType::OrphanUnitFile => "orphan-unit-file",
+            DriftType::DanglingCatalogsLink => "dangling-catalogs-link",
+            DriftType::MissingApp => "missing-app",
+            DriftType::MissingCatalogs => "missing-catalogs",
+            DriftType::UnregisteredApp => "unregistered-app",
+            DriftType::UnregisteredCatalogs => "unregistered-catalogs",
+        };
+        write!(f, "{}", drift_type)
+    }
+}
+
+// drift between registers and files on disk
+#[derive(Clone, Debug)]
+pub struct Drift {
+    pub drift_type: DriftType,
+    // pipe id, unit name, path or resource descriptor
+    pub resource: String,
+    pub detail: String,
+    pub repaired: bool,
+    // repair attempted but failed
+    pub error: Option<String>,
+}
+
+impl Drift {
+    pub(crate) fn new(drift_type: DriftType, resource: String, detail: String) -> Self {
+        Drift {
+            drift_type,
+            resource,
+            detail,
+            repaired: false,
+            error: None,
+        }
+    }
+
+    pub(crate) fn repair<F>(&mut self, f: F)
+    where
+        F: FnOnce() -> Result<()>,
+    {
+        match f() {
+            Ok(_) => self.repaired = true,
+            Err(err) => self.error = Some(err.to_string()),
+        }
+    }
+}
+
+impl From<Drift> for grpc::daemon::Drift {
+    fn from(origin: Drift) -> Self {
+        grpc::daemon::Drift {
+            drift_type: origin.drift_type.to_string(),
+            resource: origin.resource,
+            detail: origin.detail,
+            repaired: origin.repaired,
+            error: origin.error,
+        }
+    }
+}
+
+#[derive(Clone, Default, Deserialize)]
+pub struct ReconcilerConfig {
+    // seconds between two rounds, default if undefined
+    pub interval: Option<u64>,
+    // repair drifts or report only
+    #[serde(default)]
+    pub repair: bool,
+    // delete orphan working directories at repair, reported only otherwise
+    #[serde(default)]
+    pub purge: bool,
+}
+
+// diagnose repository first so that repaired registers are visible to pipes
+pub(crate) fn diagnose(
+    repository_manager: &RepositoryManager,
+    pipe_manager: &PipeManager,
+    repair: bool,
+    purge: bool,
+) -> Result<Vec<Drift>> {
+    let mut drifts = repository_manager.diagnose_apps(repair)?;
+    drifts.extend(repository_manager.diagnose_catalogs(repair)?);
+    drifts.extend(pipe_manager.diagnose(
+        repair,
+        purge,
+        |desc| {
+            repository_manager
+                .check_app_registered(desc)
+                .ok()
+                .flatten()
+                .filter(|path| path.exists())
+        },
+        |desc| {
+            repository_manager
+                .check_catalogs_registered(desc)
+                .ok()
+                .flatten()
+                .filter(|path| path.exists())
+        },
+    )?);
+    Ok(drifts)
+}
@@ -18,8 +18,20 @@ impl Display for Error {
     }
 }
 
+impl Error {
+    pub fn is_job_conflict(&self) -> bool {
+        matches!(*self.0, ErrorImpl::JobConflict { .. })
+    }
+}
+
 pub type Result<T> = result::Result<T, Error>;
 
+// systemd errors when enqueued job conflicts with pending jobs
+const SYSTEMD_JOB_CONFLICT_ERRORS: [&str; 2] = [
+    "org.freedesktop.systemd1.TransactionIsDestructive",
+    "org.freedesktop.systemd1.TransactionJobsConflicting",
+];
+
 #[derive(Debug, Error)]
 pub enum ErrorImpl {
     #[error("addr parse error, detail: {0:?}")]
@@ -41,6 +53,14 @@ pub enum ErrorImpl {
     Env(#[from] env::VarError),
     #[error("io error, detail: {0:?}")]
     Io(#[from] std::io::Error),
+    #[error("job conflict error, unit: {unit:?}, mode: {mode:?}, detail: {message:?}")]
+    JobConflict {
+        unit: String,
+        mode: String,
+        message: String,
+    },
+    #[error("json error, detail: {0:?}")]
+    Json(#[from] serde_json::Error),
     #[error("link error, from: {from:?}, to: {to:?}, detail: {message:?}")]
     Link {
         from: String,
@@ -94,6 +114,12 @@ impl From<std::io::Error> for Error {
     }
 }
 
+impl From<serde_json::Error> for Error {
+    fn from(origin: serde_json::Error) -> Self {
+        Error(Box::new(ErrorImpl::Json(origin)))
+    }
+}
+
 impl From<std::string::FromUtf8Error> for Error {
     fn from(origin: std::string::FromUtf8Error) -> Self {
         Error(Box::new(ErrorImpl::Utf8(origin)))
@@ -175,6 +201,21 @@ pub fn path_error(operation: String, message: String) -> Error {
     Error(Box::new(ErrorImpl::Path { operation, message }))
 }
 
+pub fn job_error(unit: &str, mode: &str, error: zbus::Error) -> Error {
+    match error {
+        zbus::Error::MethodError(ref name, ref detail, _)
+            if SYSTEMD_JOB_CONFLICT_ERRORS.contains(&name.as_str()) =>
+        {
+            Error(Box::new(ErrorImpl::JobConflict {
+                unit: unit.to_owned(),
+                mode: mode.to_owned(),
+                message: detail.to_owned().unwrap_or_default(),
+            }))
+        }
+        error => error.into(),
+    }
+}
+
 pub fn pipe_error(operation: PipeOperation, message: String) -> Error {
     Error(Box::new(ErrorImpl::Pipe { operation, message }))
 }
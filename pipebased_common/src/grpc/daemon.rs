@@ -100,6 +100,150 @@ pub struct EnvironmentVariable {
     pub value: ::prost::alloc::string::String,
 }
 #[derive(Clone, PartialEq, ::prost::Message)]
+pub struct RestartDescriptor {
+    /// restart policy: no, on-failure, always
+    #[prost(string, optional, tag = "1")]
+    pub policy: ::core::option::Option<::prost::alloc::string::String>,
+    /// delay before restart, a unit-less value in seconds, or a time span such as "5min 20s"
+    #[prost(string, optional, tag = "2")]
+    pub delay: ::core::option::Option<::prost::alloc::string::String>,
+    /// start rate limit, at most start_limit_burst starts within start_limit_interval
+    #[prost(uint32, optional, tag = "3")]
+    pub start_limit_burst: ::core::option::Option<u32>,
+    #[prost(string, optional, tag = "4")]
+    pub start_limit_interval: ::core::option::Option<::prost::alloc::string::String>,
+}
+/// graceful stop settings
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct StopDescriptor {
+    /// wait before final kill signal, a unit-less value in seconds, a time span, or 'infinity'
+    #[prost(string, optional, tag = "1")]
+    pub timeout: ::core::option::Option<::prost::alloc::string::String>,
+    /// signal name or number sent on stop, SIGTERM by default
+    #[prost(string, optional, tag = "2")]
+    pub kill_signal: ::core::option::Option<::prost::alloc::string::String>,
+    /// control-group, mixed, process or none
+    #[prost(string, optional, tag = "3")]
+    pub kill_mode: ::core::option::Option<::prost::alloc::string::String>,
+    /// sent on stop timeout, SIGKILL by default
+    #[prost(string, optional, tag = "4")]
+    pub final_kill_signal: ::core::option::Option<::prost::alloc::string::String>,
+    #[prost(bool, optional, tag = "5")]
+    pub send_sigkill: ::core::option::Option<bool>,
+}
+/// cgroup resource limits
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct LimitDescriptor {
+    /// bytes with optional K, M, G, T suffix, percentage of physical memory, or 'infinity'
+    #[prost(string, optional, tag = "1")]
+    pub memory_max: ::core::option::Option<::prost::alloc::string::String>,
+    #[prost(string, optional, tag = "2")]
+    pub memory_high: ::core::option::Option<::prost::alloc::string::String>,
+    /// percentage of one cpu time, such as "50%"
+    #[prost(string, optional, tag = "3")]
+    pub cpu_quota: ::core::option::Option<::prost::alloc::string::String>,
+    /// 1 - 10000
+    #[prost(uint64, optional, tag = "4")]
+    pub cpu_weight: ::core::option::Option<u64>,
+    /// number, percentage of system limit, or 'infinity'
+    #[prost(string, optional, tag = "5")]
+    pub tasks_max: ::core::option::Option<::prost::alloc::string::String>,
+    /// 1 - 10000
+    #[prost(uint64, optional, tag = "6")]
+    pub io_weight: ::core::option::Option<u64>,
+}
+/// sandboxing and hardening settings, overrides take precedence over preset
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SecurityDescriptor {
+    /// preset: none (default), default, strict
+    #[prost(string, optional, tag = "1")]
+    pub preset: ::core::option::Option<::prost::alloc::string::String>,
+    /// yes, no, full, strict
+    #[prost(string, optional, tag = "2")]
+    pub protect_system: ::core::option::Option<::prost::alloc::string::String>,
+    /// yes, no, read-only, tmpfs
+    #[prost(string, optional, tag = "3")]
+    pub protect_home: ::core::option::Option<::prost::alloc::string::String>,
+    #[prost(bool, optional, tag = "4")]
+    pub private_tmp: ::core::option::Option<bool>,
+    #[prost(bool, optional, tag = "5")]
+    pub no_new_privileges: ::core::option::Option<bool>,
+    /// space separated capabilities, empty to drop all capabilities
+    #[prost(string, optional, tag = "6")]
+    pub capability_bounding_set: ::core::option::Option<::prost::alloc::string::String>,
+    /// writable paths in addition to working directory
+    #[prost(string, repeated, tag = "7")]
+    pub read_write_paths: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
+    /// space separated address families, such as "AF_UNIX AF_INET AF_INET6"
+    #[prost(string, optional, tag = "8")]
+    pub restrict_address_families: ::core::option::Option<::prost::alloc::string::String>,
+}
+/// trigger schedule, pipe started by timer unit instead of at start
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ScheduleDescriptor {
+    /// calendar event expression, such as "hourly" or "*-*-* 02:00:00"
+    #[prost(string, optional, tag = "1")]
+    pub on_calendar: ::core::option::Option<::prost::alloc::string::String>,
+    /// time span after boot
+    #[prost(string, optional, tag = "2")]
+    pub on_boot_sec: ::core::option::Option<::prost::alloc::string::String>,
+    /// time span after last trigger
+    #[prost(string, optional, tag = "3")]
+    pub on_unit_active_sec: ::core::option::Option<::prost::alloc::string::String>,
+}
+/// dependency on another pipe, started before and ordered after
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct PipeDependency {
+    #[prost(string, tag = "1")]
+    pub id: ::prost::alloc::string::String,
+    /// requires, wants, default requires
+    #[prost(string, optional, tag = "2")]
+    pub dependency_type: ::core::option::Option<::prost::alloc::string::String>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct TcpProbe {
+    /// loopback if undefined
+    #[prost(string, optional, tag = "1")]
+    pub host: ::core::option::Option<::prost::alloc::string::String>,
+    #[prost(uint32, tag = "2")]
+    pub port: u32,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct HttpProbe {
+    /// loopback if undefined
+    #[prost(string, optional, tag = "1")]
+    pub host: ::core::option::Option<::prost::alloc::string::String>,
+    #[prost(uint32, tag = "2")]
+    pub port: u32,
+    /// '/' if undefined
+    #[prost(string, optional, tag = "3")]
+    pub path: ::core::option::Option<::prost::alloc::string::String>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ExecProbe {
+    /// run as pipe user
+    #[prost(string, repeated, tag = "1")]
+    pub command: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
+}
+/// exactly one of tcp, http or exec
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ProbeDescriptor {
+    #[prost(message, optional, tag = "1")]
+    pub tcp: ::core::option::Option<TcpProbe>,
+    #[prost(message, optional, tag = "2")]
+    pub http: ::core::option::Option<HttpProbe>,
+    #[prost(message, optional, tag = "3")]
+    pub exec: ::core::option::Option<ExecProbe>,
+    /// seconds
+    #[prost(uint64, optional, tag = "4")]
+    pub interval: ::core::option::Option<u64>,
+    #[prost(uint64, optional, tag = "5")]
+    pub timeout: ::core::option::Option<u64>,
+    /// consecutive failures before unhealthy
+    #[prost(uint32, optional, tag = "6")]
+    pub failure_threshold: ::core::option::Option<u32>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
 pub struct CreatePipeRequest {
     #[prost(string, tag = "1")]
     pub id: ::prost::alloc::string::String,
@@ -115,6 +259,38 @@ pub struct CreatePipeRequest {
     pub app: ::core::option::Option<AppDescriptor>,
     #[prost(message, optional, tag = "7")]
     pub catalogs: ::core::option::Option<CatalogsDescriptor>,
+    /// path to environment files, optional if prefixed with '-'
+    #[prost(string, repeated, tag = "8")]
+    pub env_files: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
+    #[prost(message, optional, tag = "9")]
+    pub restart: ::core::option::Option<RestartDescriptor>,
+    #[prost(message, optional, tag = "10")]
+    pub limits: ::core::option::Option<LimitDescriptor>,
+    #[prost(message, optional, tag = "11")]
+    pub security: ::core::option::Option<SecurityDescriptor>,
+    /// enable pipe to start at boot
+    #[prost(bool, optional, tag = "12")]
+    pub autostart: ::core::option::Option<bool>,
+    #[prost(map = "string, string", tag = "13")]
+    pub labels:
+        ::std::collections::HashMap<::prost::alloc::string::String, ::prost::alloc::string::String>,
+    #[prost(message, repeated, tag = "14")]
+    pub depends_on: ::prost::alloc::vec::Vec<PipeDependency>,
+    #[prost(message, optional, tag = "15")]
+    pub schedule: ::core::option::Option<ScheduleDescriptor>,
+    /// simple or oneshot
+    #[prost(string, optional, tag = "16")]
+    pub service_type: ::core::option::Option<::prost::alloc::string::String>,
+    /// oneshot pipe stays active after exit
+    #[prost(bool, optional, tag = "17")]
+    pub remain_after_exit: ::core::option::Option<bool>,
+    /// probed by daemon, pipe restarted after repeated liveness failures
+    #[prost(message, optional, tag = "18")]
+    pub liveness: ::core::option::Option<ProbeDescriptor>,
+    #[prost(message, optional, tag = "19")]
+    pub readiness: ::core::option::Option<ProbeDescriptor>,
+    #[prost(message, optional, tag = "20")]
+    pub stop: ::core::option::Option<StopDescriptor>,
 }
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct CreatePipeResponse {}
@@ -122,25 +298,275 @@ pub struct CreatePipeResponse {}
 pub struct StartPipeRequest {
     #[prost(string, tag = "1")]
     pub id: ::prost::alloc::string::String,
+    /// block until start job completed and report job result
+    #[prost(bool, tag = "2")]
+    pub wait: bool,
+    /// seconds to wait
+    #[prost(uint64, optional, tag = "3")]
+    pub timeout: ::core::option::Option<u64>,
+    /// start job mode, replace, fail, isolate, ignore-dependencies or ignore-requirements, default from daemon config
+    #[prost(string, optional, tag = "4")]
+    pub mode: ::core::option::Option<::prost::alloc::string::String>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct StartPipeResponse {
+    /// done, canceled, timeout, failed, dependency or skipped, defined if waited
+    #[prost(string, optional, tag = "1")]
+    pub job_result: ::core::option::Option<::prost::alloc::string::String>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct StartPipesRequest {
+    #[prost(string, repeated, tag = "1")]
+    pub ids: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
 }
 #[derive(Clone, PartialEq, ::prost::Message)]
-pub struct StartPipeResponse {}
+pub struct StartPipesResponse {
+    /// ids in start order
+    #[prost(string, repeated, tag = "1")]
+    pub ids: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
+}
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct StopPipeRequest {
     #[prost(string, tag = "1")]
     pub id: ::prost::alloc::string::String,
+    /// block until stop job completed and report job result and exit
+    #[prost(bool, tag = "2")]
+    pub wait: bool,
+    /// seconds to wait
+    #[prost(uint64, optional, tag = "3")]
+    pub timeout: ::core::option::Option<u64>,
+    /// stop job mode, replace, fail, ignore-dependencies or ignore-requirements, default from daemon config
+    #[prost(string, optional, tag = "4")]
+    pub mode: ::core::option::Option<::prost::alloc::string::String>,
+}
+/// exit of main process after pipe stopped
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct PipeExit {
+    /// exit code or signal number of main process
+    #[prost(int32, tag = "1")]
+    pub exit_code: i32,
+    /// exited, killed or dumped
+    #[prost(string, optional, tag = "2")]
+    pub exit_reason: ::core::option::Option<::prost::alloc::string::String>,
+    /// signal name if killed or dumped
+    #[prost(string, optional, tag = "3")]
+    pub signal: ::core::option::Option<::prost::alloc::string::String>,
+    /// service result, i.e success, exit-code, signal, timeout
+    #[prost(string, tag = "4")]
+    pub result: ::prost::alloc::string::String,
+    /// stopped before stop timeout
+    #[prost(bool, tag = "5")]
+    pub clean: bool,
+    /// killed with SIGKILL
+    #[prost(bool, tag = "6")]
+    pub sigkilled: bool,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct StopPipeResponse {
+    /// defined if waited
+    #[prost(message, optional, tag = "1")]
+    pub exit: ::core::option::Option<PipeExit>,
+    #[prost(string, optional, tag = "2")]
+    pub job_result: ::core::option::Option<::prost::alloc::string::String>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct RestartPipeRequest {
+    #[prost(string, tag = "1")]
+    pub id: ::prost::alloc::string::String,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct RestartPipeResponse {}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ReloadPipeRequest {
+    #[prost(string, tag = "1")]
+    pub id: ::prost::alloc::string::String,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ReloadPipeResponse {}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct KillPipeRequest {
+    #[prost(string, tag = "1")]
+    pub id: ::prost::alloc::string::String,
+    /// signal name or number, i.e SIGUSR1 or 10
+    #[prost(string, tag = "2")]
+    pub signal: ::prost::alloc::string::String,
+    /// main, control or all (default)
+    #[prost(string, optional, tag = "3")]
+    pub whom: ::core::option::Option<::prost::alloc::string::String>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct KillPipeResponse {}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct EnablePipeRequest {
+    #[prost(string, tag = "1")]
+    pub id: ::prost::alloc::string::String,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct EnablePipeResponse {}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct DisablePipeRequest {
+    #[prost(string, tag = "1")]
+    pub id: ::prost::alloc::string::String,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct DisablePipeResponse {}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct UpdatePipeRequest {
+    #[prost(string, tag = "1")]
+    pub id: ::prost::alloc::string::String,
+    /// pulled app version to run, keep current app if undefined
+    #[prost(message, optional, tag = "2")]
+    pub app: ::core::option::Option<AppDescriptor>,
+    /// pulled catalogs version to link, keep current catalogs if undefined
+    #[prost(message, optional, tag = "3")]
+    pub catalogs: ::core::option::Option<CatalogsDescriptor>,
+    /// seconds to wait for pipe running before rollback
+    #[prost(uint64, optional, tag = "4")]
+    pub timeout: ::core::option::Option<u64>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct UpdatePipeResponse {}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct InspectPipeRequest {
+    #[prost(string, tag = "1")]
+    pub id: ::prost::alloc::string::String,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct InspectPipeResponse {
+    #[prost(message, optional, tag = "1")]
+    pub state: ::core::option::Option<PipeState>,
+    #[prost(string, tag = "2")]
+    pub description: ::prost::alloc::string::String,
+    #[prost(string, tag = "3")]
+    pub user: ::prost::alloc::string::String,
+    #[prost(string, tag = "4")]
+    pub group: ::prost::alloc::string::String,
+    /// secret values masked
+    #[prost(message, repeated, tag = "5")]
+    pub envs: ::prost::alloc::vec::Vec<EnvironmentVariable>,
+    /// undefined if app path is not in app repository
+    #[prost(message, optional, tag = "6")]
+    pub app: ::core::option::Option<AppDescriptor>,
+    /// undefined if catalogs path is not in catalogs repository
+    #[prost(message, optional, tag = "7")]
+    pub catalogs: ::core::option::Option<CatalogsDescriptor>,
+    #[prost(string, optional, tag = "8")]
+    pub app_path: ::core::option::Option<::prost::alloc::string::String>,
+    #[prost(string, optional, tag = "9")]
+    pub catalogs_path: ::core::option::Option<::prost::alloc::string::String>,
+    #[prost(string, tag = "10")]
+    pub working_directory: ::prost::alloc::string::String,
+    /// unit configuration file path and contents
+    #[prost(string, tag = "11")]
+    pub unit_path: ::prost::alloc::string::String,
+    #[prost(string, tag = "12")]
+    pub unit: ::prost::alloc::string::String,
+    #[prost(uint32, tag = "13")]
+    pub main_pid: u32,
+    /// main process exit reason: 1 exited, 2 killed, 3 dumped
+    #[prost(int32, tag = "14")]
+    pub exec_main_code: i32,
+    /// main process exit code or signal number
+    #[prost(int32, tag = "15")]
+    pub exec_main_status: i32,
+    #[prost(uint32, tag = "16")]
+    pub n_restarts: u32,
+    /// microseconds since epoch, 0 if never entered
+    #[prost(uint64, tag = "17")]
+    pub active_enter_timestamp: u64,
+    #[prost(uint64, tag = "18")]
+    pub inactive_enter_timestamp: u64,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct StreamPipeLogsRequest {
+    #[prost(string, tag = "1")]
+    pub id: ::prost::alloc::string::String,
+    /// number of most recent entries
+    #[prost(uint64, optional, tag = "2")]
+    pub tail: ::core::option::Option<u64>,
+    /// journalctl time spec, i.e '2022-01-01 00:00:00', 'yesterday', '-1h'
+    #[prost(string, optional, tag = "3")]
+    pub since: ::core::option::Option<::prost::alloc::string::String>,
+    #[prost(string, optional, tag = "4")]
+    pub until: ::core::option::Option<::prost::alloc::string::String>,
+    #[prost(bool, tag = "5")]
+    pub follow: bool,
+    /// level or range 'from..to', i.e 'err', '3', 'warning..err'
+    #[prost(string, optional, tag = "6")]
+    pub priority: ::core::option::Option<::prost::alloc::string::String>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct PipeLogEntry {
+    /// microseconds since epoch
+    #[prost(uint64, tag = "1")]
+    pub timestamp: u64,
+    #[prost(uint32, optional, tag = "2")]
+    pub priority: ::core::option::Option<u32>,
+    #[prost(string, tag = "3")]
+    pub message: ::prost::alloc::string::String,
+    /// journal fields
+    #[prost(map = "string, string", tag = "4")]
+    pub fields:
+        ::std::collections::HashMap<::prost::alloc::string::String, ::prost::alloc::string::String>,
+    /// parsed if app logs in json format
+    #[prost(string, optional, tag = "5")]
+    pub level: ::core::option::Option<::prost::alloc::string::String>,
+    #[prost(map = "string, string", tag = "6")]
+    pub app_fields:
+        ::std::collections::HashMap<::prost::alloc::string::String, ::prost::alloc::string::String>,
 }
 #[derive(Clone, PartialEq, ::prost::Message)]
-pub struct StopPipeResponse {}
+pub struct WatchPipesRequest {
+    /// pipe ids to watch, all pipes if empty
+    #[prost(string, repeated, tag = "1")]
+    pub ids: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct PipeEvent {
+    #[prost(string, tag = "1")]
+    pub id: ::prost::alloc::string::String,
+    /// started, stopped, failed, restarted
+    #[prost(string, tag = "2")]
+    pub event_type: ::prost::alloc::string::String,
+    /// microseconds since epoch
+    #[prost(uint64, tag = "3")]
+    pub timestamp: u64,
+    #[prost(string, tag = "4")]
+    pub active_state: ::prost::alloc::string::String,
+    #[prost(string, tag = "5")]
+    pub sub_state: ::prost::alloc::string::String,
+    /// main process exit code or signal number
+    #[prost(int32, optional, tag = "6")]
+    pub exit_code: ::core::option::Option<i32>,
+    /// main process exit reason: exited, killed, dumped
+    #[prost(string, optional, tag = "7")]
+    pub exit_reason: ::core::option::Option<::prost::alloc::string::String>,
+    /// result of failed job, i.e failed, timeout, dependency
+    #[prost(string, optional, tag = "8")]
+    pub job_result: ::core::option::Option<::prost::alloc::string::String>,
+}
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct RemovePipeRequest {
     #[prost(string, tag = "1")]
     pub id: ::prost::alloc::string::String,
+    /// stop pipe and wait, reset failed state, remove even if other pipes depend on it
+    #[prost(bool, tag = "2")]
+    pub force: bool,
+    /// deregister but keep working directory, skipped by diagnose
+    #[prost(bool, tag = "3")]
+    pub keep_data: bool,
+    /// reset failed state of pipe before removal
+    #[prost(bool, tag = "4")]
+    pub purge_failed: bool,
 }
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct RemovePipeResponse {}
 #[derive(Clone, PartialEq, ::prost::Message)]
-pub struct ListPipeRequest {}
+pub struct ListPipeRequest {
+    /// label selector, i.e 'env=prod,team!=ingest,tier in (a,b)'
+    #[prost(string, optional, tag = "1")]
+    pub selector: ::core::option::Option<::prost::alloc::string::String>,
+}
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct PipeState {
     #[prost(string, tag = "1")]
@@ -151,12 +577,160 @@ pub struct PipeState {
     pub active_state: ::prost::alloc::string::String,
     #[prost(string, tag = "4")]
     pub sub_state: ::prost::alloc::string::String,
+    #[prost(bool, tag = "5")]
+    pub enabled: bool,
+    #[prost(map = "string, string", tag = "6")]
+    pub labels:
+        ::std::collections::HashMap<::prost::alloc::string::String, ::prost::alloc::string::String>,
+    /// microseconds since epoch, scheduled pipes only
+    #[prost(uint64, optional, tag = "7")]
+    pub next_trigger: ::core::option::Option<u64>,
+    #[prost(uint64, optional, tag = "8")]
+    pub last_trigger: ::core::option::Option<u64>,
+    /// unknown, healthy or unhealthy, probed pipes only
+    #[prost(string, optional, tag = "9")]
+    pub liveness: ::core::option::Option<::prost::alloc::string::String>,
+    #[prost(string, optional, tag = "10")]
+    pub readiness: ::core::option::Option<::prost::alloc::string::String>,
 }
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct ListPipeResponse {
     #[prost(message, repeated, tag = "1")]
     pub pipes: ::prost::alloc::vec::Vec<PipeState>,
 }
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GetPipeStatsRequest {
+    /// all pipes if empty
+    #[prost(string, repeated, tag = "1")]
+    pub ids: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
+}
+/// sampled from cgroup accounting, undefined if unavailable
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct PipeStats {
+    #[prost(string, tag = "1")]
+    pub id: ::prost::alloc::string::String,
+    /// microseconds since epoch
+    #[prost(uint64, tag = "2")]
+    pub timestamp: u64,
+    #[prost(uint64, optional, tag = "3")]
+    pub memory_current: ::core::option::Option<u64>,
+    /// cumulative
+    #[prost(uint64, optional, tag = "4")]
+    pub cpu_usage_nsec: ::core::option::Option<u64>,
+    #[prost(uint64, optional, tag = "5")]
+    pub tasks_current: ::core::option::Option<u64>,
+    #[prost(uint64, optional, tag = "6")]
+    pub io_read_bytes: ::core::option::Option<u64>,
+    #[prost(uint64, optional, tag = "7")]
+    pub io_write_bytes: ::core::option::Option<u64>,
+    #[prost(uint64, optional, tag = "8")]
+    pub ip_ingress_bytes: ::core::option::Option<u64>,
+    #[prost(uint64, optional, tag = "9")]
+    pub ip_egress_bytes: ::core::option::Option<u64>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GetPipeStatsResponse {
+    #[prost(message, repeated, tag = "1")]
+    pub stats: ::prost::alloc::vec::Vec<PipeStats>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ListPipeRunsRequest {
+    #[prost(string, tag = "1")]
+    pub id: ::prost::alloc::string::String,
+}
+/// completed run of oneshot pipe
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct PipeRun {
+    /// microseconds since epoch
+    #[prost(uint64, tag = "1")]
+    pub started_at: u64,
+    #[prost(uint64, tag = "2")]
+    pub ended_at: u64,
+    /// exit code or signal number of main process
+    #[prost(int32, tag = "3")]
+    pub exit_code: i32,
+    /// exited, killed or dumped
+    #[prost(string, optional, tag = "4")]
+    pub exit_reason: ::core::option::Option<::prost::alloc::string::String>,
+    #[prost(string, tag = "5")]
+    pub active_state: ::prost::alloc::string::String,
+    #[prost(string, tag = "6")]
+    pub sub_state: ::prost::alloc::string::String,
+    #[prost(bool, tag = "7")]
+    pub succeeded: bool,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ListPipeRunsResponse {
+    /// oldest first
+    #[prost(message, repeated, tag = "1")]
+    pub runs: ::prost::alloc::vec::Vec<PipeRun>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ApplyPipesRequest {
+    /// desired pipes
+    #[prost(message, repeated, tag = "1")]
+    pub pipes: ::prost::alloc::vec::Vec<CreatePipeRequest>,
+    /// plan changes without applying
+    #[prost(bool, tag = "2")]
+    pub dry_run: bool,
+    /// remove pipes not declared
+    #[prost(bool, tag = "3")]
+    pub prune: bool,
+    /// seconds to wait for updated pipe running before rollback
+    #[prost(uint64, optional, tag = "4")]
+    pub timeout: ::core::option::Option<u64>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct PipeChange {
+    #[prost(string, tag = "1")]
+    pub id: ::prost::alloc::string::String,
+    /// create, update, remove, unchanged
+    #[prost(string, tag = "2")]
+    pub change_type: ::prost::alloc::string::String,
+    /// artifacts to pull and fields changed
+    #[prost(string, repeated, tag = "3")]
+    pub details: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
+    #[prost(bool, tag = "4")]
+    pub applied: bool,
+    /// apply attempted but failed
+    #[prost(string, optional, tag = "5")]
+    pub error: ::core::option::Option<::prost::alloc::string::String>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ApplyPipesResponse {
+    #[prost(message, repeated, tag = "1")]
+    pub changes: ::prost::alloc::vec::Vec<PipeChange>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct DiagnoseRequest {
+    /// repair drifts or report only
+    #[prost(bool, tag = "1")]
+    pub repair: bool,
+    /// delete orphan working directories and unit files at repair
+    #[prost(bool, tag = "2")]
+    pub purge: bool,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct Drift {
+    /// i.e missing-working-directory, orphan-unit-file, dangling-catalogs-link, missing-app
+    #[prost(string, tag = "1")]
+    pub drift_type: ::prost::alloc::string::String,
+    /// pipe id, unit name or resource descriptor
+    #[prost(string, tag = "2")]
+    pub resource: ::prost::alloc::string::String,
+    #[prost(string, tag = "3")]
+    pub detail: ::prost::alloc::string::String,
+    #[prost(bool, tag = "4")]
+    pub repaired: bool,
+    /// repair attempted but failed
+    #[prost(string, optional, tag = "5")]
+    pub error: ::core::option::Option<::prost::alloc::string::String>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct DiagnoseResponse {
+    #[prost(message, repeated, tag = "1")]
+    pub drifts: ::prost::alloc::vec::Vec<Drift>,
+}
 #[doc = r" Generated client implementations."]
 pub mod daemon_client {
     #![allow(unused_variables, dead_code, missing_docs, clippy::let_unit_value)]
@@ -331,6 +905,20 @@ pub mod daemon_client {
             let path = http::uri::PathAndQuery::from_static("/daemon.Daemon/StartPipe");
             self.inner.unary(request.into_request(), path, codec).await
         }
+        pub async fn start_pipes(
+            &mut self,
+            request: impl tonic::IntoRequest<super::StartPipesRequest>,
+        ) -> Result<tonic::Response<super::StartPipesResponse>, tonic::Status> {
+            self.inner.ready().await.map_err(|e| {
+                tonic::Status::new(
+                    tonic::Code::Unknown,
+                    format!("Service was not ready: {}", e.into()),
+                )
+            })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static("/daemon.Daemon/StartPipes");
+            self.inner.unary(request.into_request(), path, codec).await
+        }
         pub async fn stop_pipe(
             &mut self,
             request: impl tonic::IntoRequest<super::StopPipeRequest>,
@@ -345,10 +933,10 @@ pub mod daemon_client {
             let path = http::uri::PathAndQuery::from_static("/daemon.Daemon/StopPipe");
             self.inner.unary(request.into_request(), path, codec).await
         }
-        pub async fn remove_pipe(
+        pub async fn restart_pipe(
             &mut self,
-            request: impl tonic::IntoRequest<super::RemovePipeRequest>,
-        ) -> Result<tonic::Response<super::RemovePipeResponse>, tonic::Status> {
+            request: impl tonic::IntoRequest<super::RestartPipeRequest>,
+        ) -> Result<tonic::Response<super::RestartPipeResponse>, tonic::Status> {
             self.inner.ready().await.map_err(|e| {
                 tonic::Status::new(
                     tonic::Code::Unknown,
@@ -356,13 +944,13 @@ pub mod daemon_client {
                 )
             })?;
             let codec = tonic::codec::ProstCodec::default();
-            let path = http::uri::PathAndQuery::from_static("/daemon.Daemon/RemovePipe");
+            let path = http::uri::PathAndQuery::from_static("/daemon.Daemon/RestartPipe");
             self.inner.unary(request.into_request(), path, codec).await
         }
-        pub async fn list_pipe(
+        pub async fn reload_pipe(
             &mut self,
-            request: impl tonic::IntoRequest<super::ListPipeRequest>,
-        ) -> Result<tonic::Response<super::ListPipeResponse>, tonic::Status> {
+            request: impl tonic::IntoRequest<super::ReloadPipeRequest>,
+        ) -> Result<tonic::Response<super::ReloadPipeResponse>, tonic::Status> {
             self.inner.ready().await.map_err(|e| {
                 tonic::Status::new(
                     tonic::Code::Unknown,
@@ -370,86 +958,338 @@ pub mod daemon_client {
                 )
             })?;
             let codec = tonic::codec::ProstCodec::default();
-            let path = http::uri::PathAndQuery::from_static("/daemon.Daemon/ListPipe");
+            let path = http::uri::PathAndQuery::from_static("/daemon.Daemon/ReloadPipe");
             self.inner.unary(request.into_request(), path, codec).await
         }
-    }
-}
-#[doc = r" Generated server implementations."]
-pub mod daemon_server {
-    #![allow(unused_variables, dead_code, missing_docs, clippy::let_unit_value)]
-    use tonic::codegen::*;
-    #[doc = "Generated trait containing gRPC methods that should be implemented for use with DaemonServer."]
-    #[async_trait]
-    pub trait Daemon: Send + Sync + 'static {
-        #[doc = " repository operations"]
-        async fn list_app(
-            &self,
-            request: tonic::Request<super::ListAppRequest>,
-        ) -> Result<tonic::Response<super::ListAppResponse>, tonic::Status>;
-        async fn list_catalogs(
-            &self,
-            request: tonic::Request<super::ListCatalogsRequest>,
-        ) -> Result<tonic::Response<super::ListCatalogsResponse>, tonic::Status>;
-        async fn pull_app(
-            &self,
-            request: tonic::Request<super::PullAppRequest>,
-        ) -> Result<tonic::Response<super::PullAppResponse>, tonic::Status>;
-        async fn pull_catalogs(
-            &self,
-            request: tonic::Request<super::PullCatalogsRequest>,
-        ) -> Result<tonic::Response<super::PullCatalogsResponse>, tonic::Status>;
-        async fn remove_app(
-            &self,
-            request: tonic::Request<super::RemoveAppRequest>,
-        ) -> Result<tonic::Response<super::RemoveAppResponse>, tonic::Status>;
-        async fn remove_catalogs(
-            &self,
-            request: tonic::Request<super::RemoveCatalogsRequest>,
-        ) -> Result<tonic::Response<super::RemoveCatalogsResponse>, tonic::Status>;
-        #[doc = " pipe operations"]
-        async fn create_pipe(
-            &self,
-            request: tonic::Request<super::CreatePipeRequest>,
-        ) -> Result<tonic::Response<super::CreatePipeResponse>, tonic::Status>;
-        async fn start_pipe(
-            &self,
-            request: tonic::Request<super::StartPipeRequest>,
-        ) -> Result<tonic::Response<super::StartPipeResponse>, tonic::Status>;
-        async fn stop_pipe(
-            &self,
-            request: tonic::Request<super::StopPipeRequest>,
-        ) -> Result<tonic::Response<super::StopPipeResponse>, tonic::Status>;
-        async fn remove_pipe(
-            &self,
-            request: tonic::Request<super::RemovePipeRequest>,
-        ) -> Result<tonic::Response<super::RemovePipeResponse>, tonic::Status>;
-        async fn list_pipe(
-            &self,
-            request: tonic::Request<super::ListPipeRequest>,
-        ) -> Result<tonic::Response<super::ListPipeResponse>, tonic::Status>;
-    }
-    #[derive(Debug)]
-    pub struct DaemonServer<T: Daemon> {
-        inner: _Inner<T>,
-        accept_compression_encodings: (),
-        send_compression_encodings: (),
-    }
-    struct _Inner<T>(Arc<T>);
-    impl<T: Daemon> DaemonServer<T> {
-        pub fn new(inner: T) -> Self {
-            let inner = Arc::new(inner);
-            let inner = _Inner(inner);
-            Self {
-                inner,
-                accept_compression_encodings: Default::default(),
-                send_compression_encodings: Default::default(),
-            }
-        }
-        pub fn with_interceptor<F>(inner: T, interceptor: F) -> InterceptedService<Self, F>
-        where
-            F: tonic::service::Interceptor,
-        {
+        pub async fn kill_pipe(
+            &mut self,
+            request: impl tonic::IntoRequest<super::KillPipeRequest>,
+        ) -> Result<tonic::Response<super::KillPipeResponse>, tonic::Status> {
+            self.inner.ready().await.map_err(|e| {
+                tonic::Status::new(
+                    tonic::Code::Unknown,
+                    format!("Service was not ready: {}", e.into()),
+                )
+            })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static("/daemon.Daemon/KillPipe");
+            self.inner.unary(request.into_request(), path, codec).await
+        }
+        pub async fn enable_pipe(
+            &mut self,
+            request: impl tonic::IntoRequest<super::EnablePipeRequest>,
+        ) -> Result<tonic::Response<super::EnablePipeResponse>, tonic::Status> {
+            self.inner.ready().await.map_err(|e| {
+                tonic::Status::new(
+                    tonic::Code::Unknown,
+                    format!("Service was not ready: {}", e.into()),
+                )
+            })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static("/daemon.Daemon/EnablePipe");
+            self.inner.unary(request.into_request(), path, codec).await
+        }
+        pub async fn disable_pipe(
+            &mut self,
+            request: impl tonic::IntoRequest<super::DisablePipeRequest>,
+        ) -> Result<tonic::Response<super::DisablePipeResponse>, tonic::Status> {
+            self.inner.ready().await.map_err(|e| {
+                tonic::Status::new(
+                    tonic::Code::Unknown,
+                    format!("Service was not ready: {}", e.into()),
+                )
+            })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static("/daemon.Daemon/DisablePipe");
+            self.inner.unary(request.into_request(), path, codec).await
+        }
+        pub async fn update_pipe(
+            &mut self,
+            request: impl tonic::IntoRequest<super::UpdatePipeRequest>,
+        ) -> Result<tonic::Response<super::UpdatePipeResponse>, tonic::Status> {
+            self.inner.ready().await.map_err(|e| {
+                tonic::Status::new(
+                    tonic::Code::Unknown,
+                    format!("Service was not ready: {}", e.into()),
+                )
+            })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static("/daemon.Daemon/UpdatePipe");
+            self.inner.unary(request.into_request(), path, codec).await
+        }
+        pub async fn inspect_pipe(
+            &mut self,
+            request: impl tonic::IntoRequest<super::InspectPipeRequest>,
+        ) -> Result<tonic::Response<super::InspectPipeResponse>, tonic::Status> {
+            self.inner.ready().await.map_err(|e| {
+                tonic::Status::new(
+                    tonic::Code::Unknown,
+                    format!("Service was not ready: {}", e.into()),
+                )
+            })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static("/daemon.Daemon/InspectPipe");
+            self.inner.unary(request.into_request(), path, codec).await
+        }
+        pub async fn stream_pipe_logs(
+            &mut self,
+            request: impl tonic::IntoRequest<super::StreamPipeLogsRequest>,
+        ) -> Result<tonic::Response<tonic::codec::Streaming<super::PipeLogEntry>>, tonic::Status>
+        {
+            self.inner.ready().await.map_err(|e| {
+                tonic::Status::new(
+                    tonic::Code::Unknown,
+                    format!("Service was not ready: {}", e.into()),
+                )
+            })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static("/daemon.Daemon/StreamPipeLogs");
+            self.inner
+                .server_streaming(request.into_request(), path, codec)
+                .await
+        }
+        pub async fn watch_pipes(
+            &mut self,
+            request: impl tonic::IntoRequest<super::WatchPipesRequest>,
+        ) -> Result<tonic::Response<tonic::codec::Streaming<super::PipeEvent>>, tonic::Status>
+        {
+            self.inner.ready().await.map_err(|e| {
+                tonic::Status::new(
+                    tonic::Code::Unknown,
+                    format!("Service was not ready: {}", e.into()),
+                )
+            })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static("/daemon.Daemon/WatchPipes");
+            self.inner
+                .server_streaming(request.into_request(), path, codec)
+                .await
+        }
+        pub async fn remove_pipe(
+            &mut self,
+            request: impl tonic::IntoRequest<super::RemovePipeRequest>,
+        ) -> Result<tonic::Response<super::RemovePipeResponse>, tonic::Status> {
+            self.inner.ready().await.map_err(|e| {
+                tonic::Status::new(
+                    tonic::Code::Unknown,
+                    format!("Service was not ready: {}", e.into()),
+                )
+            })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static("/daemon.Daemon/RemovePipe");
+            self.inner.unary(request.into_request(), path, codec).await
+        }
+        pub async fn list_pipe(
+            &mut self,
+            request: impl tonic::IntoRequest<super::ListPipeRequest>,
+        ) -> Result<tonic::Response<super::ListPipeResponse>, tonic::Status> {
+            self.inner.ready().await.map_err(|e| {
+                tonic::Status::new(
+                    tonic::Code::Unknown,
+                    format!("Service was not ready: {}", e.into()),
+                )
+            })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static("/daemon.Daemon/ListPipe");
+            self.inner.unary(request.into_request(), path, codec).await
+        }
+        pub async fn list_pipe_runs(
+            &mut self,
+            request: impl tonic::IntoRequest<super::ListPipeRunsRequest>,
+        ) -> Result<tonic::Response<super::ListPipeRunsResponse>, tonic::Status> {
+            self.inner.ready().await.map_err(|e| {
+                tonic::Status::new(
+                    tonic::Code::Unknown,
+                    format!("Service was not ready: {}", e.into()),
+                )
+            })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static("/daemon.Daemon/ListPipeRuns");
+            self.inner.unary(request.into_request(), path, codec).await
+        }
+        pub async fn get_pipe_stats(
+            &mut self,
+            request: impl tonic::IntoRequest<super::GetPipeStatsRequest>,
+        ) -> Result<tonic::Response<super::GetPipeStatsResponse>, tonic::Status> {
+            self.inner.ready().await.map_err(|e| {
+                tonic::Status::new(
+                    tonic::Code::Unknown,
+                    format!("Service was not ready: {}", e.into()),
+                )
+            })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static("/daemon.Daemon/GetPipeStats");
+            self.inner.unary(request.into_request(), path, codec).await
+        }
+        pub async fn apply_pipes(
+            &mut self,
+            request: impl tonic::IntoRequest<super::ApplyPipesRequest>,
+        ) -> Result<tonic::Response<super::ApplyPipesResponse>, tonic::Status> {
+            self.inner.ready().await.map_err(|e| {
+                tonic::Status::new(
+                    tonic::Code::Unknown,
+                    format!("Service was not ready: {}", e.into()),
+                )
+            })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static("/daemon.Daemon/ApplyPipes");
+            self.inner.unary(request.into_request(), path, codec).await
+        }
+        pub async fn diagnose(
+            &mut self,
+            request: impl tonic::IntoRequest<super::DiagnoseRequest>,
+        ) -> Result<tonic::Response<super::DiagnoseResponse>, tonic::Status> {
+            self.inner.ready().await.map_err(|e| {
+                tonic::Status::new(
+                    tonic::Code::Unknown,
+                    format!("Service was not ready: {}", e.into()),
+                )
+            })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static("/daemon.Daemon/Diagnose");
+            self.inner.unary(request.into_request(), path, codec).await
+        }
+    }
+}
+#[doc = r" Generated server implementations."]
+pub mod daemon_server {
+    #![allow(unused_variables, dead_code, missing_docs, clippy::let_unit_value)]
+    use tonic::codegen::*;
+    #[doc = "Generated trait containing gRPC methods that should be implemented for use with DaemonServer."]
+    #[async_trait]
+    pub trait Daemon: Send + Sync + 'static {
+        #[doc = " repository operations"]
+        async fn list_app(
+            &self,
+            request: tonic::Request<super::ListAppRequest>,
+        ) -> Result<tonic::Response<super::ListAppResponse>, tonic::Status>;
+        async fn list_catalogs(
+            &self,
+            request: tonic::Request<super::ListCatalogsRequest>,
+        ) -> Result<tonic::Response<super::ListCatalogsResponse>, tonic::Status>;
+        async fn pull_app(
+            &self,
+            request: tonic::Request<super::PullAppRequest>,
+        ) -> Result<tonic::Response<super::PullAppResponse>, tonic::Status>;
+        async fn pull_catalogs(
+            &self,
+            request: tonic::Request<super::PullCatalogsRequest>,
+        ) -> Result<tonic::Response<super::PullCatalogsResponse>, tonic::Status>;
+        async fn remove_app(
+            &self,
+            request: tonic::Request<super::RemoveAppRequest>,
+        ) -> Result<tonic::Response<super::RemoveAppResponse>, tonic::Status>;
+        async fn remove_catalogs(
+            &self,
+            request: tonic::Request<super::RemoveCatalogsRequest>,
+        ) -> Result<tonic::Response<super::RemoveCatalogsResponse>, tonic::Status>;
+        #[doc = " pipe operations"]
+        async fn create_pipe(
+            &self,
+            request: tonic::Request<super::CreatePipeRequest>,
+        ) -> Result<tonic::Response<super::CreatePipeResponse>, tonic::Status>;
+        async fn start_pipe(
+            &self,
+            request: tonic::Request<super::StartPipeRequest>,
+        ) -> Result<tonic::Response<super::StartPipeResponse>, tonic::Status>;
+        async fn start_pipes(
+            &self,
+            request: tonic::Request<super::StartPipesRequest>,
+        ) -> Result<tonic::Response<super::StartPipesResponse>, tonic::Status>;
+        async fn stop_pipe(
+            &self,
+            request: tonic::Request<super::StopPipeRequest>,
+        ) -> Result<tonic::Response<super::StopPipeResponse>, tonic::Status>;
+        async fn restart_pipe(
+            &self,
+            request: tonic::Request<super::RestartPipeRequest>,
+        ) -> Result<tonic::Response<super::RestartPipeResponse>, tonic::Status>;
+        async fn reload_pipe(
+            &self,
+            request: tonic::Request<super::ReloadPipeRequest>,
+        ) -> Result<tonic::Response<super::ReloadPipeResponse>, tonic::Status>;
+        async fn kill_pipe(
+            &self,
+            request: tonic::Request<super::KillPipeRequest>,
+        ) -> Result<tonic::Response<super::KillPipeResponse>, tonic::Status>;
+        async fn enable_pipe(
+            &self,
+            request: tonic::Request<super::EnablePipeRequest>,
+        ) -> Result<tonic::Response<super::EnablePipeResponse>, tonic::Status>;
+        async fn disable_pipe(
+            &self,
+            request: tonic::Request<super::DisablePipeRequest>,
+        ) -> Result<tonic::Response<super::DisablePipeResponse>, tonic::Status>;
+        async fn update_pipe(
+            &self,
+            request: tonic::Request<super::UpdatePipeRequest>,
+        ) -> Result<tonic::Response<super::UpdatePipeResponse>, tonic::Status>;
+        async fn inspect_pipe(
+            &self,
+            request: tonic::Request<super::InspectPipeRequest>,
+        ) -> Result<tonic::Response<super::InspectPipeResponse>, tonic::Status>;
+        #[doc = "Server streaming response type for the StreamPipeLogs method."]
+        type StreamPipeLogsStream: futures_core::Stream<Item = Result<super::PipeLogEntry, tonic::Status>>
+            + Send
+            + 'static;
+        async fn stream_pipe_logs(
+            &self,
+            request: tonic::Request<super::StreamPipeLogsRequest>,
+        ) -> Result<tonic::Response<Self::StreamPipeLogsStream>, tonic::Status>;
+        #[doc = "Server streaming response type for the WatchPipes method."]
+        type WatchPipesStream: futures_core::Stream<Item = Result<super::PipeEvent, tonic::Status>>
+            + Send
+            + 'static;
+        async fn watch_pipes(
+            &self,
+            request: tonic::Request<super::WatchPipesRequest>,
+        ) -> Result<tonic::Response<Self::WatchPipesStream>, tonic::Status>;
+        async fn remove_pipe(
+            &self,
+            request: tonic::Request<super::RemovePipeRequest>,
+        ) -> Result<tonic::Response<super::RemovePipeResponse>, tonic::Status>;
+        async fn list_pipe(
+            &self,
+            request: tonic::Request<super::ListPipeRequest>,
+        ) -> Result<tonic::Response<super::ListPipeResponse>, tonic::Status>;
+        async fn list_pipe_runs(
+            &self,
+            request: tonic::Request<super::ListPipeRunsRequest>,
+        ) -> Result<tonic::Response<super::ListPipeRunsResponse>, tonic::Status>;
+        async fn get_pipe_stats(
+            &self,
+            request: tonic::Request<super::GetPipeStatsRequest>,
+        ) -> Result<tonic::Response<super::GetPipeStatsResponse>, tonic::Status>;
+        async fn apply_pipes(
+            &self,
+            request: tonic::Request<super::ApplyPipesRequest>,
+        ) -> Result<tonic::Response<super::ApplyPipesResponse>, tonic::Status>;
+        async fn diagnose(
+            &self,
+            request: tonic::Request<super::DiagnoseRequest>,
+        ) -> Result<tonic::Response<super::DiagnoseResponse>, tonic::Status>;
+    }
+    #[derive(Debug)]
+    pub struct DaemonServer<T: Daemon> {
+        inner: _Inner<T>,
+        accept_compression_encodings: (),
+        send_compression_encodings: (),
+    }
+    struct _Inner<T>(Arc<T>);
+    impl<T: Daemon> DaemonServer<T> {
+        pub fn new(inner: T) -> Self {
+            let inner = Arc::new(inner);
+            let inner = _Inner(inner);
+            Self {
+                inner,
+                accept_compression_encodings: Default::default(),
+                send_compression_encodings: Default::default(),
+            }
+        }
+        pub fn with_interceptor<F>(inner: T, interceptor: F) -> InterceptedService<Self, F>
+        where
+            F: tonic::service::Interceptor,
+        {
             InterceptedService::new(Self::new(inner), interceptor)
         }
     }
@@ -470,16 +1310,233 @@ pub mod daemon_server {
             match req.uri().path() {
                 "/daemon.Daemon/ListApp" => {
                     #[allow(non_camel_case_types)]
-                    struct ListAppSvc<T: Daemon>(pub Arc<T>);
-                    impl<T: Daemon> tonic::server::UnaryService<super::ListAppRequest> for ListAppSvc<T> {
-                        type Response = super::ListAppResponse;
+                    struct ListAppSvc<T: Daemon>(pub Arc<T>);
+                    impl<T: Daemon> tonic::server::UnaryService<super::ListAppRequest> for ListAppSvc<T> {
+                        type Response = super::ListAppResponse;
+                        type Future = BoxFuture<tonic::Response<Self::Response>, tonic::Status>;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::ListAppRequest>,
+                        ) -> Self::Future {
+                            let inner = self.0.clone();
+                            let fut = async move { (*inner).list_app(request).await };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = ListAppSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec).apply_compression_config(
+                            accept_compression_encodings,
+                            send_compression_encodings,
+                        );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/daemon.Daemon/ListCatalogs" => {
+                    #[allow(non_camel_case_types)]
+                    struct ListCatalogsSvc<T: Daemon>(pub Arc<T>);
+                    impl<T: Daemon> tonic::server::UnaryService<super::ListCatalogsRequest> for ListCatalogsSvc<T> {
+                        type Response = super::ListCatalogsResponse;
+                        type Future = BoxFuture<tonic::Response<Self::Response>, tonic::Status>;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::ListCatalogsRequest>,
+                        ) -> Self::Future {
+                            let inner = self.0.clone();
+                            let fut = async move { (*inner).list_catalogs(request).await };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = ListCatalogsSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec).apply_compression_config(
+                            accept_compression_encodings,
+                            send_compression_encodings,
+                        );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/daemon.Daemon/PullApp" => {
+                    #[allow(non_camel_case_types)]
+                    struct PullAppSvc<T: Daemon>(pub Arc<T>);
+                    impl<T: Daemon> tonic::server::UnaryService<super::PullAppRequest> for PullAppSvc<T> {
+                        type Response = super::PullAppResponse;
+                        type Future = BoxFuture<tonic::Response<Self::Response>, tonic::Status>;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::PullAppRequest>,
+                        ) -> Self::Future {
+                            let inner = self.0.clone();
+                            let fut = async move { (*inner).pull_app(request).await };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = PullAppSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec).apply_compression_config(
+                            accept_compression_encodings,
+                            send_compression_encodings,
+                        );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/daemon.Daemon/PullCatalogs" => {
+                    #[allow(non_camel_case_types)]
+                    struct PullCatalogsSvc<T: Daemon>(pub Arc<T>);
+                    impl<T: Daemon> tonic::server::UnaryService<super::PullCatalogsRequest> for PullCatalogsSvc<T> {
+                        type Response = super::PullCatalogsResponse;
+                        type Future = BoxFuture<tonic::Response<Self::Response>, tonic::Status>;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::PullCatalogsRequest>,
+                        ) -> Self::Future {
+                            let inner = self.0.clone();
+                            let fut = async move { (*inner).pull_catalogs(request).await };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = PullCatalogsSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec).apply_compression_config(
+                            accept_compression_encodings,
+                            send_compression_encodings,
+                        );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/daemon.Daemon/RemoveApp" => {
+                    #[allow(non_camel_case_types)]
+                    struct RemoveAppSvc<T: Daemon>(pub Arc<T>);
+                    impl<T: Daemon> tonic::server::UnaryService<super::RemoveAppRequest> for RemoveAppSvc<T> {
+                        type Response = super::RemoveAppResponse;
+                        type Future = BoxFuture<tonic::Response<Self::Response>, tonic::Status>;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::RemoveAppRequest>,
+                        ) -> Self::Future {
+                            let inner = self.0.clone();
+                            let fut = async move { (*inner).remove_app(request).await };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = RemoveAppSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec).apply_compression_config(
+                            accept_compression_encodings,
+                            send_compression_encodings,
+                        );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/daemon.Daemon/RemoveCatalogs" => {
+                    #[allow(non_camel_case_types)]
+                    struct RemoveCatalogsSvc<T: Daemon>(pub Arc<T>);
+                    impl<T: Daemon> tonic::server::UnaryService<super::RemoveCatalogsRequest> for RemoveCatalogsSvc<T> {
+                        type Response = super::RemoveCatalogsResponse;
+                        type Future = BoxFuture<tonic::Response<Self::Response>, tonic::Status>;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::RemoveCatalogsRequest>,
+                        ) -> Self::Future {
+                            let inner = self.0.clone();
+                            let fut = async move { (*inner).remove_catalogs(request).await };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = RemoveCatalogsSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec).apply_compression_config(
+                            accept_compression_encodings,
+                            send_compression_encodings,
+                        );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/daemon.Daemon/CreatePipe" => {
+                    #[allow(non_camel_case_types)]
+                    struct CreatePipeSvc<T: Daemon>(pub Arc<T>);
+                    impl<T: Daemon> tonic::server::UnaryService<super::CreatePipeRequest> for CreatePipeSvc<T> {
+                        type Response = super::CreatePipeResponse;
+                        type Future = BoxFuture<tonic::Response<Self::Response>, tonic::Status>;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::CreatePipeRequest>,
+                        ) -> Self::Future {
+                            let inner = self.0.clone();
+                            let fut = async move { (*inner).create_pipe(request).await };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = CreatePipeSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec).apply_compression_config(
+                            accept_compression_encodings,
+                            send_compression_encodings,
+                        );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/daemon.Daemon/StartPipe" => {
+                    #[allow(non_camel_case_types)]
+                    struct StartPipeSvc<T: Daemon>(pub Arc<T>);
+                    impl<T: Daemon> tonic::server::UnaryService<super::StartPipeRequest> for StartPipeSvc<T> {
+                        type Response = super::StartPipeResponse;
                         type Future = BoxFuture<tonic::Response<Self::Response>, tonic::Status>;
                         fn call(
                             &mut self,
-                            request: tonic::Request<super::ListAppRequest>,
+                            request: tonic::Request<super::StartPipeRequest>,
                         ) -> Self::Future {
                             let inner = self.0.clone();
-                            let fut = async move { (*inner).list_app(request).await };
+                            let fut = async move { (*inner).start_pipe(request).await };
                             Box::pin(fut)
                         }
                     }
@@ -488,7 +1545,7 @@ pub mod daemon_server {
                     let inner = self.inner.clone();
                     let fut = async move {
                         let inner = inner.0;
-                        let method = ListAppSvc(inner);
+                        let method = StartPipeSvc(inner);
                         let codec = tonic::codec::ProstCodec::default();
                         let mut grpc = tonic::server::Grpc::new(codec).apply_compression_config(
                             accept_compression_encodings,
@@ -499,18 +1556,18 @@ pub mod daemon_server {
                     };
                     Box::pin(fut)
                 }
-                "/daemon.Daemon/ListCatalogs" => {
+                "/daemon.Daemon/StartPipes" => {
                     #[allow(non_camel_case_types)]
-                    struct ListCatalogsSvc<T: Daemon>(pub Arc<T>);
-                    impl<T: Daemon> tonic::server::UnaryService<super::ListCatalogsRequest> for ListCatalogsSvc<T> {
-                        type Response = super::ListCatalogsResponse;
+                    struct StartPipesSvc<T: Daemon>(pub Arc<T>);
+                    impl<T: Daemon> tonic::server::UnaryService<super::StartPipesRequest> for StartPipesSvc<T> {
+                        type Response = super::StartPipesResponse;
                         type Future = BoxFuture<tonic::Response<Self::Response>, tonic::Status>;
                         fn call(
                             &mut self,
-                            request: tonic::Request<super::ListCatalogsRequest>,
+                            request: tonic::Request<super::StartPipesRequest>,
                         ) -> Self::Future {
                             let inner = self.0.clone();
-                            let fut = async move { (*inner).list_catalogs(request).await };
+                            let fut = async move { (*inner).start_pipes(request).await };
                             Box::pin(fut)
                         }
                     }
@@ -519,7 +1576,7 @@ pub mod daemon_server {
                     let inner = self.inner.clone();
                     let fut = async move {
                         let inner = inner.0;
-                        let method = ListCatalogsSvc(inner);
+                        let method = StartPipesSvc(inner);
                         let codec = tonic::codec::ProstCodec::default();
                         let mut grpc = tonic::server::Grpc::new(codec).apply_compression_config(
                             accept_compression_encodings,
@@ -530,18 +1587,18 @@ pub mod daemon_server {
                     };
                     Box::pin(fut)
                 }
-                "/daemon.Daemon/PullApp" => {
+                "/daemon.Daemon/StopPipe" => {
                     #[allow(non_camel_case_types)]
-                    struct PullAppSvc<T: Daemon>(pub Arc<T>);
-                    impl<T: Daemon> tonic::server::UnaryService<super::PullAppRequest> for PullAppSvc<T> {
-                        type Response = super::PullAppResponse;
+                    struct StopPipeSvc<T: Daemon>(pub Arc<T>);
+                    impl<T: Daemon> tonic::server::UnaryService<super::StopPipeRequest> for StopPipeSvc<T> {
+                        type Response = super::StopPipeResponse;
                         type Future = BoxFuture<tonic::Response<Self::Response>, tonic::Status>;
                         fn call(
                             &mut self,
-                            request: tonic::Request<super::PullAppRequest>,
+                            request: tonic::Request<super::StopPipeRequest>,
                         ) -> Self::Future {
                             let inner = self.0.clone();
-                            let fut = async move { (*inner).pull_app(request).await };
+                            let fut = async move { (*inner).stop_pipe(request).await };
                             Box::pin(fut)
                         }
                     }
@@ -550,7 +1607,7 @@ pub mod daemon_server {
                     let inner = self.inner.clone();
                     let fut = async move {
                         let inner = inner.0;
-                        let method = PullAppSvc(inner);
+                        let method = StopPipeSvc(inner);
                         let codec = tonic::codec::ProstCodec::default();
                         let mut grpc = tonic::server::Grpc::new(codec).apply_compression_config(
                             accept_compression_encodings,
@@ -561,18 +1618,18 @@ pub mod daemon_server {
                     };
                     Box::pin(fut)
                 }
-                "/daemon.Daemon/PullCatalogs" => {
+                "/daemon.Daemon/RestartPipe" => {
                     #[allow(non_camel_case_types)]
-                    struct PullCatalogsSvc<T: Daemon>(pub Arc<T>);
-                    impl<T: Daemon> tonic::server::UnaryService<super::PullCatalogsRequest> for PullCatalogsSvc<T> {
-                        type Response = super::PullCatalogsResponse;
+                    struct RestartPipeSvc<T: Daemon>(pub Arc<T>);
+                    impl<T: Daemon> tonic::server::UnaryService<super::RestartPipeRequest> for RestartPipeSvc<T> {
+                        type Response = super::RestartPipeResponse;
                         type Future = BoxFuture<tonic::Response<Self::Response>, tonic::Status>;
                         fn call(
                             &mut self,
-                            request: tonic::Request<super::PullCatalogsRequest>,
+                            request: tonic::Request<super::RestartPipeRequest>,
                         ) -> Self::Future {
                             let inner = self.0.clone();
-                            let fut = async move { (*inner).pull_catalogs(request).await };
+                            let fut = async move { (*inner).restart_pipe(request).await };
                             Box::pin(fut)
                         }
                     }
@@ -581,7 +1638,7 @@ pub mod daemon_server {
                     let inner = self.inner.clone();
                     let fut = async move {
                         let inner = inner.0;
-                        let method = PullCatalogsSvc(inner);
+                        let method = RestartPipeSvc(inner);
                         let codec = tonic::codec::ProstCodec::default();
                         let mut grpc = tonic::server::Grpc::new(codec).apply_compression_config(
                             accept_compression_encodings,
@@ -592,18 +1649,18 @@ pub mod daemon_server {
                     };
                     Box::pin(fut)
                 }
-                "/daemon.Daemon/RemoveApp" => {
+                "/daemon.Daemon/ReloadPipe" => {
                     #[allow(non_camel_case_types)]
-                    struct RemoveAppSvc<T: Daemon>(pub Arc<T>);
-                    impl<T: Daemon> tonic::server::UnaryService<super::RemoveAppRequest> for RemoveAppSvc<T> {
-                        type Response = super::RemoveAppResponse;
+                    struct ReloadPipeSvc<T: Daemon>(pub Arc<T>);
+                    impl<T: Daemon> tonic::server::UnaryService<super::ReloadPipeRequest> for ReloadPipeSvc<T> {
+                        type Response = super::ReloadPipeResponse;
                         type Future = BoxFuture<tonic::Response<Self::Response>, tonic::Status>;
                         fn call(
                             &mut self,
-                            request: tonic::Request<super::RemoveAppRequest>,
+                            request: tonic::Request<super::ReloadPipeRequest>,
                         ) -> Self::Future {
                             let inner = self.0.clone();
-                            let fut = async move { (*inner).remove_app(request).await };
+                            let fut = async move { (*inner).reload_pipe(request).await };
                             Box::pin(fut)
                         }
                     }
@@ -612,7 +1669,7 @@ pub mod daemon_server {
                     let inner = self.inner.clone();
                     let fut = async move {
                         let inner = inner.0;
-                        let method = RemoveAppSvc(inner);
+                        let method = ReloadPipeSvc(inner);
                         let codec = tonic::codec::ProstCodec::default();
                         let mut grpc = tonic::server::Grpc::new(codec).apply_compression_config(
                             accept_compression_encodings,
@@ -623,18 +1680,18 @@ pub mod daemon_server {
                     };
                     Box::pin(fut)
                 }
-                "/daemon.Daemon/RemoveCatalogs" => {
+                "/daemon.Daemon/KillPipe" => {
                     #[allow(non_camel_case_types)]
-                    struct RemoveCatalogsSvc<T: Daemon>(pub Arc<T>);
-                    impl<T: Daemon> tonic::server::UnaryService<super::RemoveCatalogsRequest> for RemoveCatalogsSvc<T> {
-                        type Response = super::RemoveCatalogsResponse;
+                    struct KillPipeSvc<T: Daemon>(pub Arc<T>);
+                    impl<T: Daemon> tonic::server::UnaryService<super::KillPipeRequest> for KillPipeSvc<T> {
+                        type Response = super::KillPipeResponse;
                         type Future = BoxFuture<tonic::Response<Self::Response>, tonic::Status>;
                         fn call(
                             &mut self,
-                            request: tonic::Request<super::RemoveCatalogsRequest>,
+                            request: tonic::Request<super::KillPipeRequest>,
                         ) -> Self::Future {
                             let inner = self.0.clone();
-                            let fut = async move { (*inner).remove_catalogs(request).await };
+                            let fut = async move { (*inner).kill_pipe(request).await };
                             Box::pin(fut)
                         }
                     }
@@ -643,7 +1700,7 @@ pub mod daemon_server {
                     let inner = self.inner.clone();
                     let fut = async move {
                         let inner = inner.0;
-                        let method = RemoveCatalogsSvc(inner);
+                        let method = KillPipeSvc(inner);
                         let codec = tonic::codec::ProstCodec::default();
                         let mut grpc = tonic::server::Grpc::new(codec).apply_compression_config(
                             accept_compression_encodings,
@@ -654,18 +1711,18 @@ pub mod daemon_server {
                     };
                     Box::pin(fut)
                 }
-                "/daemon.Daemon/CreatePipe" => {
+                "/daemon.Daemon/EnablePipe" => {
                     #[allow(non_camel_case_types)]
-                    struct CreatePipeSvc<T: Daemon>(pub Arc<T>);
-                    impl<T: Daemon> tonic::server::UnaryService<super::CreatePipeRequest> for CreatePipeSvc<T> {
-                        type Response = super::CreatePipeResponse;
+                    struct EnablePipeSvc<T: Daemon>(pub Arc<T>);
+                    impl<T: Daemon> tonic::server::UnaryService<super::EnablePipeRequest> for EnablePipeSvc<T> {
+                        type Response = super::EnablePipeResponse;
                         type Future = BoxFuture<tonic::Response<Self::Response>, tonic::Status>;
                         fn call(
                             &mut self,
-                            request: tonic::Request<super::CreatePipeRequest>,
+                            request: tonic::Request<super::EnablePipeRequest>,
                         ) -> Self::Future {
                             let inner = self.0.clone();
-                            let fut = async move { (*inner).create_pipe(request).await };
+                            let fut = async move { (*inner).enable_pipe(request).await };
                             Box::pin(fut)
                         }
                     }
@@ -674,7 +1731,7 @@ pub mod daemon_server {
                     let inner = self.inner.clone();
                     let fut = async move {
                         let inner = inner.0;
-                        let method = CreatePipeSvc(inner);
+                        let method = EnablePipeSvc(inner);
                         let codec = tonic::codec::ProstCodec::default();
                         let mut grpc = tonic::server::Grpc::new(codec).apply_compression_config(
                             accept_compression_encodings,
@@ -685,18 +1742,18 @@ pub mod daemon_server {
                     };
                     Box::pin(fut)
                 }
-                "/daemon.Daemon/StartPipe" => {
+                "/daemon.Daemon/DisablePipe" => {
                     #[allow(non_camel_case_types)]
-                    struct StartPipeSvc<T: Daemon>(pub Arc<T>);
-                    impl<T: Daemon> tonic::server::UnaryService<super::StartPipeRequest> for StartPipeSvc<T> {
-                        type Response = super::StartPipeResponse;
+                    struct DisablePipeSvc<T: Daemon>(pub Arc<T>);
+                    impl<T: Daemon> tonic::server::UnaryService<super::DisablePipeRequest> for DisablePipeSvc<T> {
+                        type Response = super::DisablePipeResponse;
                         type Future = BoxFuture<tonic::Response<Self::Response>, tonic::Status>;
                         fn call(
                             &mut self,
-                            request: tonic::Request<super::StartPipeRequest>,
+                            request: tonic::Request<super::DisablePipeRequest>,
                         ) -> Self::Future {
                             let inner = self.0.clone();
-                            let fut = async move { (*inner).start_pipe(request).await };
+                            let fut = async move { (*inner).disable_pipe(request).await };
                             Box::pin(fut)
                         }
                     }
@@ -705,7 +1762,7 @@ pub mod daemon_server {
                     let inner = self.inner.clone();
                     let fut = async move {
                         let inner = inner.0;
-                        let method = StartPipeSvc(inner);
+                        let method = DisablePipeSvc(inner);
                         let codec = tonic::codec::ProstCodec::default();
                         let mut grpc = tonic::server::Grpc::new(codec).apply_compression_config(
                             accept_compression_encodings,
@@ -716,18 +1773,18 @@ pub mod daemon_server {
                     };
                     Box::pin(fut)
                 }
-                "/daemon.Daemon/StopPipe" => {
+                "/daemon.Daemon/UpdatePipe" => {
                     #[allow(non_camel_case_types)]
-                    struct StopPipeSvc<T: Daemon>(pub Arc<T>);
-                    impl<T: Daemon> tonic::server::UnaryService<super::StopPipeRequest> for StopPipeSvc<T> {
-                        type Response = super::StopPipeResponse;
+                    struct UpdatePipeSvc<T: Daemon>(pub Arc<T>);
+                    impl<T: Daemon> tonic::server::UnaryService<super::UpdatePipeRequest> for UpdatePipeSvc<T> {
+                        type Response = super::UpdatePipeResponse;
                         type Future = BoxFuture<tonic::Response<Self::Response>, tonic::Status>;
                         fn call(
                             &mut self,
-                            request: tonic::Request<super::StopPipeRequest>,
+                            request: tonic::Request<super::UpdatePipeRequest>,
                         ) -> Self::Future {
                             let inner = self.0.clone();
-                            let fut = async move { (*inner).stop_pipe(request).await };
+                            let fut = async move { (*inner).update_pipe(request).await };
                             Box::pin(fut)
                         }
                     }
@@ -736,7 +1793,38 @@ pub mod daemon_server {
                     let inner = self.inner.clone();
                     let fut = async move {
                         let inner = inner.0;
-                        let method = StopPipeSvc(inner);
+                        let method = UpdatePipeSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec).apply_compression_config(
+                            accept_compression_encodings,
+                            send_compression_encodings,
+                        );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/daemon.Daemon/InspectPipe" => {
+                    #[allow(non_camel_case_types)]
+                    struct InspectPipeSvc<T: Daemon>(pub Arc<T>);
+                    impl<T: Daemon> tonic::server::UnaryService<super::InspectPipeRequest> for InspectPipeSvc<T> {
+                        type Response = super::InspectPipeResponse;
+                        type Future = BoxFuture<tonic::Response<Self::Response>, tonic::Status>;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::InspectPipeRequest>,
+                        ) -> Self::Future {
+                            let inner = self.0.clone();
+                            let fut = async move { (*inner).inspect_pipe(request).await };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = InspectPipeSvc(inner);
                         let codec = tonic::codec::ProstCodec::default();
                         let mut grpc = tonic::server::Grpc::new(codec).apply_compression_config(
                             accept_compression_encodings,
@@ -747,6 +1835,77 @@ pub mod daemon_server {
                     };
                     Box::pin(fut)
                 }
+                "/daemon.Daemon/StreamPipeLogs" => {
+                    #[allow(non_camel_case_types)]
+                    struct StreamPipeLogsSvc<T: Daemon>(pub Arc<T>);
+                    impl<T: Daemon>
+                        tonic::server::ServerStreamingService<super::StreamPipeLogsRequest>
+                        for StreamPipeLogsSvc<T>
+                    {
+                        type Response = super::PipeLogEntry;
+                        type ResponseStream = T::StreamPipeLogsStream;
+                        type Future =
+                            BoxFuture<tonic::Response<Self::ResponseStream>, tonic::Status>;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::StreamPipeLogsRequest>,
+                        ) -> Self::Future {
+                            let inner = self.0.clone();
+                            let fut = async move { (*inner).stream_pipe_logs(request).await };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = StreamPipeLogsSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec).apply_compression_config(
+                            accept_compression_encodings,
+                            send_compression_encodings,
+                        );
+                        let res = grpc.server_streaming(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/daemon.Daemon/WatchPipes" => {
+                    #[allow(non_camel_case_types)]
+                    struct WatchPipesSvc<T: Daemon>(pub Arc<T>);
+                    impl<T: Daemon> tonic::server::ServerStreamingService<super::WatchPipesRequest>
+                        for WatchPipesSvc<T>
+                    {
+                        type Response = super::PipeEvent;
+                        type ResponseStream = T::WatchPipesStream;
+                        type Future =
+                            BoxFuture<tonic::Response<Self::ResponseStream>, tonic::Status>;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::WatchPipesRequest>,
+                        ) -> Self::Future {
+                            let inner = self.0.clone();
+                            let fut = async move { (*inner).watch_pipes(request).await };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = WatchPipesSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec).apply_compression_config(
+                            accept_compression_encodings,
+                            send_compression_encodings,
+                        );
+                        let res = grpc.server_streaming(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
                 "/daemon.Daemon/RemovePipe" => {
                     #[allow(non_camel_case_types)]
                     struct RemovePipeSvc<T: Daemon>(pub Arc<T>);
@@ -809,6 +1968,130 @@ pub mod daemon_server {
                     };
                     Box::pin(fut)
                 }
+                "/daemon.Daemon/ListPipeRuns" => {
+                    #[allow(non_camel_case_types)]
+                    struct ListPipeRunsSvc<T: Daemon>(pub Arc<T>);
+                    impl<T: Daemon> tonic::server::UnaryService<super::ListPipeRunsRequest> for ListPipeRunsSvc<T> {
+                        type Response = super::ListPipeRunsResponse;
+                        type Future = BoxFuture<tonic::Response<Self::Response>, tonic::Status>;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::ListPipeRunsRequest>,
+                        ) -> Self::Future {
+                            let inner = self.0.clone();
+                            let fut = async move { (*inner).list_pipe_runs(request).await };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = ListPipeRunsSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec).apply_compression_config(
+                            accept_compression_encodings,
+                            send_compression_encodings,
+                        );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/daemon.Daemon/GetPipeStats" => {
+                    #[allow(non_camel_case_types)]
+                    struct GetPipeStatsSvc<T: Daemon>(pub Arc<T>);
+                    impl<T: Daemon> tonic::server::UnaryService<super::GetPipeStatsRequest> for GetPipeStatsSvc<T> {
+                        type Response = super::GetPipeStatsResponse;
+                        type Future = BoxFuture<tonic::Response<Self::Response>, tonic::Status>;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::GetPipeStatsRequest>,
+                        ) -> Self::Future {
+                            let inner = self.0.clone();
+                            let fut = async move { (*inner).get_pipe_stats(request).await };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = GetPipeStatsSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec).apply_compression_config(
+                            accept_compression_encodings,
+                            send_compression_encodings,
+                        );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/daemon.Daemon/ApplyPipes" => {
+                    #[allow(non_camel_case_types)]
+                    struct ApplyPipesSvc<T: Daemon>(pub Arc<T>);
+                    impl<T: Daemon> tonic::server::UnaryService<super::ApplyPipesRequest> for ApplyPipesSvc<T> {
+                        type Response = super::ApplyPipesResponse;
+                        type Future = BoxFuture<tonic::Response<Self::Response>, tonic::Status>;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::ApplyPipesRequest>,
+                        ) -> Self::Future {
+                            let inner = self.0.clone();
+                            let fut = async move { (*inner).apply_pipes(request).await };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = ApplyPipesSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec).apply_compression_config(
+                            accept_compression_encodings,
+                            send_compression_encodings,
+                        );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/daemon.Daemon/Diagnose" => {
+                    #[allow(non_camel_case_types)]
+                    struct DiagnoseSvc<T: Daemon>(pub Arc<T>);
+                    impl<T: Daemon> tonic::server::UnaryService<super::DiagnoseRequest> for DiagnoseSvc<T> {
+                        type Response = super::DiagnoseResponse;
+                        type Future = BoxFuture<tonic::Response<Self::Response>, tonic::Status>;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::DiagnoseRequest>,
+                        ) -> Self::Future {
+                            let inner = self.0.clone();
+                            let fut = async move { (*inner).diagnose(request).await };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = DiagnoseSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec).apply_compression_config(
+                            accept_compression_encodings,
+                            send_compression_encodings,
+                        );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
                 _ => Box::pin(async move {
                     Ok(http::Response::builder()
                         .status(200)
@@ -0,0 +1,162 @@
+use crate::{
+    pipe_error, Error, ManagerProxy, PipeOperation, Result, SIGNAL_JOB_REMOVED, SYSTEMD_SERVICE,
+};
+use serde::{Deserialize, Serialize};
+use std::{fmt::Display, str::FromStr, time::Duration};
+use tokio_stream::StreamExt;
+
+// mode of enqueued systemd job, isolate only applicable for start
+// https://www.freedesktop.org/software/systemd/man/systemctl.html#--job-mode=
+#[derive(Clone, Default, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum JobMode {
+    #[default]
+    Replace,
+    Fail,
+    Isolate,
+    IgnoreDependencies,
+    IgnoreRequirements,
+}
+
+impl JobMode {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            JobMode::Replace => "replace",
+            JobMode::Fail => "fail",
+            JobMode::Isolate => "isolate",
+            JobMode::IgnoreDependencies => "ignore-dependencies",
+            JobMode::IgnoreRequirements => "ignore-requirements",
+        }
+    }
+
+    pub fn is_isolate(&self) -> bool {
+        matches!(self, JobMode::Isolate)
+    }
+}
+
+impl Display for JobMode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+impl FromStr for JobMode {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "replace" => Ok(JobMode::Replace),
+            "fail" => Ok(JobMode::Fail),
+            "isolate" => Ok(JobMode::Isolate),
+            "ignore-dependencies" => Ok(JobMode::IgnoreDependencies),
+            "ignore-requirements" => Ok(JobMode::IgnoreRequirements),
+            _ => Err(pipe_error(
+                PipeOperation::Validate,
+                format!("invalid job mode '{}'", s),
+            )),
+        }
+    }
+}
+
+// result of removed systemd job
+// https://www.freedesktop.org/wiki/Software/systemd/dbus/
+pub enum JobResult {
+    Done,
+    Canceled,
+    Timeout,
+    Failed,
+    Dependency,
+    Skipped,
+    Other(String),
+}
+
+impl JobResult {
+    pub fn is_done(&self) -> bool {
+        matches!(self, JobResult::Done)
+    }
+}
+
+impl From<String> for JobResult {
+    fn from(origin: String) -> Self {
+        match origin.as_str() {
+            "done" => JobResult::Done,
+            "canceled" => JobResult::Canceled,
+            "timeout" => JobResult::Timeout,
+            "failed" => JobResult::Failed,
+            "dependency" => JobResult::Dependency,
+            "skipped" => JobResult::Skipped,
+            _ => JobResult::Other(origin),
+        }
+    }
+}
+
+impl Display for JobResult {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let result = match self {
+            JobResult::Done => "done",
+            JobResult::Canceled => "canceled",
+            JobResult::Timeout => "timeout",
+            JobResult::Failed => "failed",
+            JobResult::Dependency => "dependency",
+            JobResult::Skipped => "skipped",
+            JobResult::Other(result) => result,
+        };
+        write!(f, "{}", result)
+    }
+}
+
+// listen job removal, created before job enqueued so that removal is not missed
+pub(crate) struct JobListener {
+    stream: zbus::MessageStream,
+    // keep subscription alive
+    _connection: zbus::Connection,
+}
+
+impl JobListener {
+    pub(crate) async fn new() -> Result<Self> {
+        let connection = zbus::Connection::system().await?;
+        let stream = zbus::MessageStream::from(&connection);
+        // systemd emits job signals only if any client subscribed
+        ManagerProxy::new(&connection).await?.subscribe().await?;
+        let dbus = zbus::fdo::DBusProxy::new(&connection).await?;
+        let rule = format!(
+            "type='signal',sender='{}',member='{}'",
+            SYSTEMD_SERVICE, SIGNAL_JOB_REMOVED
+        );
+        dbus.add_match(rule.as_str())
+            .await
+            .map_err(zbus::Error::from)?;
+        Ok(JobListener {
+            stream,
+            _connection: connection,
+        })
+    }
+
+    // block until job removed, undefined if not removed within timeout
+    pub(crate) async fn wait(
+        mut self,
+        job: &zvariant::OwnedObjectPath,
+        timeout: Duration,
+    ) -> Result<Option<JobResult>> {
+        let removed = async {
+            while let Some(message) = self.stream.next().await {
+                let message = message?;
+                if message.member().as_ref().map(|member| member.as_str())
+                    != Some(SIGNAL_JOB_REMOVED)
+                {
+                    continue;
+                }
+                let (_, path, _, result): (u32, zvariant::OwnedObjectPath, String, String) =
+                    message.body()?;
+                if &path == job {
+                    return Ok(Some(JobResult::from(result)));
+                }
+            }
+            Ok(None)
+        };
+        match tokio::time::timeout(timeout, removed).await {
+            Ok(result) => result,
+            Err(_) => Ok(None),
+        }
+    }
+}
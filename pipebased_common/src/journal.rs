@@ -0,0 +1,291 @@
+use crate::{grpc, pipe_error, PipeOperation, Result};
+use serde_json::{Map, Value};
+use std::{collections::HashMap, ffi::OsString, process::Stdio};
+use tokio::{
+    io::{AsyncBufReadExt, AsyncReadExt, BufReader},
+    process::Command,
+    sync::mpsc,
+};
+use tracing::warn;
+
+const JOURNAL_FIELD_MESSAGE: &str = "MESSAGE";
+const JOURNAL_FIELD_PRIORITY: &str = "PRIORITY";
+const JOURNAL_FIELD_REALTIME_TIMESTAMP: &str = "__REALTIME_TIMESTAMP";
+// journal priority levels, index is the numeric level
+const JOURNAL_PRIORITIES: [&str; 8] = [
+    "emerg", "alert", "crit", "err", "warning", "notice", "info", "debug",
+];
+const JOURNAL_STREAM_BUFFER: usize = 64;
+
+fn is_valid_priority_level(level: &str) -> bool {
+    match level.parse::<usize>() {
+        Ok(level) => level < JOURNAL_PRIORITIES.len(),
+        Err(_) => JOURNAL_PRIORITIES.contains(&level),
+    }
+}
+
+// single level or range 'from..to', same as journalctl --priority
+fn is_valid_priority(priority: &str) -> bool {
+    match priority.split_once("..") {
+        Some((from, to)) => is_valid_priority_level(from) && is_valid_priority_level(to),
+        None => is_valid_priority_level(priority),
+    }
+}
+
+#[derive(Default)]
+pub struct PipeLogQuery {
+    // number of most recent entries, all entries if undefined
+    pub tail: Option<u64>,
+    pub since: Option<String>,
+    pub until: Option<String>,
+    pub follow: bool,
+    pub priority: Option<String>,
+}
+
+impl PipeLogQuery {
+    pub fn validate(&self) -> Result<()> {
+        if let Some(priority) = self.priority.as_ref() {
+            if !is_valid_priority(priority) {
+                return Err(pipe_error(
+                    PipeOperation::Logs,
+                    format!("invalid priority '{}'", priority),
+                ));
+            }
+        }
+        if self.follow && self.until.is_some() {
+            return Err(pipe_error(
+                PipeOperation::Logs,
+                String::from("until not allowed when follow"),
+            ));
+        }
+        Ok(())
+    }
+
+    fn args(&self, unit_name: &str) -> Vec<OsString> {
+        let mut args: Vec<OsString> = vec![
+            "--unit".into(),
+            unit_name.into(),
+            "--output".into(),
+            "json".into(),
+            "--no-pager".into(),
+        ];
+        if let Some(tail) = self.tail {
+            args.push("--lines".into());
+            args.push(tail.to_string().into());
+        }
+        if let Some(since) = self.since.as_ref() {
+            args.push("--since".into());
+            args.push(since.into());
+        }
+        if let Some(until) = self.until.as_ref() {
+            args.push("--until".into());
+            args.push(until.into());
+        }
+        if let Some(priority) = self.priority.as_ref() {
+            args.push("--priority".into());
+            args.push(priority.into());
+        }
+        if self.follow {
+            args.push("--follow".into());
+        }
+        args
+    }
+}
+
+impl From<grpc::daemon::StreamPipeLogsRequest> for PipeLogQuery {
+    fn from(origin: grpc::daemon::StreamPipeLogsRequest) -> Self {
+        PipeLogQuery {
+            tail: origin.tail,
+            since: origin.since,
+            until: origin.until,
+            follow: origin.follow,
+            priority: origin.priority,
+        }
+    }
+}
+
+pub struct PipeLogEntry {
+    // microseconds since epoch
+    pub timestamp: u64,
+    pub priority: Option<u32>,
+    pub message: String,
+    // journal fields
+    pub fields: HashMap<String, String>,
+    // parsed from message if app logs in json format
+    pub level: Option<String>,
+    pub app_fields: HashMap<String, String>,
+}
+
+impl PipeLogEntry {
+    fn parse(line: &str) -> Result<Self> {
+        let entry: Map<String, Value> = serde_json::from_str(line)?;
+        let mut fields: HashMap<String, String> = HashMap::new();
+        let mut timestamp: u64 = 0;
+        let mut priority: Option<u32> = None;
+        let mut message = String::new();
+        for (key, value) in entry {
+            match key.as_str() {
+                JOURNAL_FIELD_REALTIME_TIMESTAMP => {
+                    timestamp = journal_value_to_string(&value).parse().unwrap_or_default()
+                }
+                JOURNAL_FIELD_PRIORITY => {
+                    priority = journal_value_to_string(&value).parse().ok();
+                }
+                JOURNAL_FIELD_MESSAGE => message = journal_value_to_string(&value),
+                // skip journal internal fields
+                _ if key.starts_with("__") => continue,
+                _ => {
+                    fields.insert(key, journal_value_to_string(&value));
+                }
+            };
+        }
+        let mut entry = PipeLogEntry {
+            timestamp,
+            priority,
+            message,
+            fields,
+            level: None,
+            app_fields: HashMap::new(),
+        };
+        entry.parse_app_message();
+        Ok(entry)
+    }
+
+    // app message formatted by tracing json formatter, i.e
+    // {"timestamp":"..","level":"INFO","fields":{"message":".."},"target":".."}
+    fn parse_app_message(&mut self) {
+        if !self.message.starts_with('{') {
+            return;
+        }
+        let app_message: Map<String, Value> = match serde_json::from_str(self.message.as_str()) {
+            Ok(app_message) => app_message,
+            Err(_) => return,
+        };
+        let mut app_fields: HashMap<String, String> = HashMap::new();
+        let mut level: Option<String> = None;
+        let mut message: Option<String> = None;
+        for (key, value) in app_message {
+            match (key.as_str(), value) {
+                ("level", value) => level = Some(json_value_to_string(&value)),
+                ("message", value) => message = Some(json_value_to_string(&value)),
+                ("fields", Value::Object(fields)) => {
+                    for (key, value) in fields {
+                        match key.as_str() {
+                            "message" => message = Some(json_value_to_string(&value)),
+                            _ => {
+                                app_fields.insert(key, json_value_to_string(&value));
+                            }
+                        }
+                    }
+                }
+                (_, value) => {
+                    app_fields.insert(key, json_value_to_string(&value));
+                }
+            }
+        }
+        if let Some(message) = message {
+            self.message = message;
+        }
+        self.level = level;
+        self.app_fields = app_fields;
+    }
+}
+
+impl From<PipeLogEntry> for grpc::daemon::PipeLogEntry {
+    fn from(origin: PipeLogEntry) -> Self {
+        grpc::daemon::PipeLogEntry {
+            timestamp: origin.timestamp,
+            priority: origin.priority,
+            message: origin.message,
+            fields: origin.fields,
+            level: origin.level,
+            app_fields: origin.app_fields,
+        }
+    }
+}
+
+fn json_value_to_string(value: &Value) -> String {
+    match value {
+        Value::String(value) => value.to_owned(),
+        value => value.to_string(),
+    }
+}
+
+// non utf8 journal field value is serialized as byte array
+fn journal_value_to_string(value: &Value) -> String {
+    match value {
+        Value::Array(bytes) => {
+            let bytes: Vec<u8> = bytes
+                .iter()
+                .filter_map(|byte| byte.as_u64())
+                .map(|byte| byte as u8)
+                .collect();
+            String::from_utf8_lossy(&bytes).into_owned()
+        }
+        value => json_value_to_string(value),
+    }
+}
+
+fn journalctl_binary() -> OsString {
+    "journalctl".to_owned().into()
+}
+
+pub type PipeLogReceiver = mpsc::Receiver<Result<PipeLogEntry>>;
+
+// spawn journalctl and stream parsed entries, journalctl is killed once receiver dropped
+pub(crate) fn stream_journal(unit_name: &str, query: &PipeLogQuery) -> Result<PipeLogReceiver> {
+    let mut cmd = Command::new(journalctl_binary());
+    cmd.args(query.args(unit_name))
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .kill_on_drop(true);
+    let mut child = cmd.spawn()?;
+    let stdout = child.stdout.take().expect("journalctl stdout not piped");
+    let mut child_stderr = child.stderr.take().expect("journalctl stderr not piped");
+    // drain stderr concurrently so that journalctl never blocks on a full pipe
+    let stderr = tokio::spawn(async move {
+        let mut stderr = String::new();
+        let _ = child_stderr.read_to_string(&mut stderr).await;
+        stderr
+    });
+    let (tx, rx) = mpsc::channel(JOURNAL_STREAM_BUFFER);
+    let unit_name = unit_name.to_owned();
+    tokio::spawn(async move {
+        let mut lines = BufReader::new(stdout).lines();
+        loop {
+            // stop following quiet unit once receiver dropped
+            let line = tokio::select! {
+                line = lines.next_line() => line,
+                _ = tx.closed() => return,
+            };
+            let entry = match line {
+                Ok(Some(line)) => PipeLogEntry::parse(line.as_str()),
+                Ok(None) => break,
+                Err(err) => Err(err.into()),
+            };
+            if tx.send(entry).await.is_err() {
+                // receiver dropped
+                return;
+            }
+        }
+        let status = match child.wait().await {
+            Ok(status) => status,
+            Err(err) => {
+                let _ = tx.send(Err(err.into())).await;
+                return;
+            }
+        };
+        if status.success() {
+            return;
+        }
+        let stderr = stderr.await.unwrap_or_default();
+        warn!(unit = unit_name.as_str(), "journalctl exit with {}", status);
+        let _ = tx
+            .send(Err(pipe_error(
+                PipeOperation::Logs,
+                format!("journalctl exit with {}, detail: {}", status, stderr.trim()),
+            )))
+            .await;
+    });
+    Ok(rx)
+}
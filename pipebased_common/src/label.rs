@@ -0,0 +1,312 @@
+use crate::{pipe_error, Error, PipeOperation, Result};
+use std::{collections::HashMap, str::FromStr};
+
+const LABEL_KEY_MAX_LENGTH: usize = 63;
+const LABEL_VALUE_MAX_LENGTH: usize = 63;
+
+// alphanumeric at both ends, '-', '_', '.' in between, key allows '/' for prefix
+fn is_valid_label_token(token: &str, extra: &[char], max_length: usize) -> bool {
+    if token.len() > max_length {
+        return false;
+    }
+    let first = token.chars().next();
+    let last = token.chars().last();
+    if !first.is_some_and(|c| c.is_ascii_alphanumeric())
+        || !last.is_some_and(|c| c.is_ascii_alphanumeric())
+    {
+        return false;
+    }
+    token
+        .chars()
+        .all(|c| c.is_ascii_alphanumeric() || extra.contains(&c))
+}
+
+pub fn is_valid_label_key(key: &str) -> bool {
+    is_valid_label_token(key, &['-', '_', '.', '/'], LABEL_KEY_MAX_LENGTH)
+}
+
+// empty value allowed
+pub fn is_valid_label_value(value: &str) -> bool {
+    value.is_empty() || is_valid_label_token(value, &['-', '_', '.'], LABEL_VALUE_MAX_LENGTH)
+}
+
+pub fn validate_labels(labels: &HashMap<String, String>) -> Result<()> {
+    for (key, value) in labels {
+        if !is_valid_label_key(key) {
+            return Err(pipe_error(
+                PipeOperation::Validate,
+                format!("invalid label key '{}'", key),
+            ));
+        }
+        if !is_valid_label_value(value) {
+            return Err(pipe_error(
+                PipeOperation::Validate,
+                format!("invalid label value '{}' of key '{}'", value, key),
+            ));
+        }
+    }
+    Ok(())
+}
+
+#[derive(Debug, PartialEq)]
+pub enum LabelRequirement {
+    // key=value or key==value
+    Equals(String, String),
+    // key!=value, matches if key absent
+    NotEquals(String, String),
+    // key in (v1,v2)
+    In(String, Vec<String>),
+    // key notin (v1,v2), matches if key absent
+    NotIn(String, Vec<String>),
+    // key
+    Exists(String),
+    // !key
+    NotExists(String),
+}
+
+impl LabelRequirement {
+    fn matches(&self, labels: &HashMap<String, String>) -> bool {
+        match self {
+            LabelRequirement::Equals(key, value) => labels.get(key) == Some(value),
+            LabelRequirement::NotEquals(key, value) => labels.get(key) != Some(value),
+            LabelRequirement::In(key, values) => {
+                labels.get(key).is_some_and(|value| values.contains(value))
+            }
+            LabelRequirement::NotIn(key, values) => {
+                labels.get(key).is_none_or(|value| !values.contains(value))
+            }
+            LabelRequirement::Exists(key) => labels.contains_key(key),
+            LabelRequirement::NotExists(key) => !labels.contains_key(key),
+        }
+    }
+}
+
+impl FromStr for LabelRequirement {
+    type Err = Error;
+
+    fn from_str(requirement: &str) -> Result<Self> {
+        let requirement = requirement.trim();
+        let parsed = if let Some(key) = requirement.strip_prefix('!') {
+            parse_key(key).map(LabelRequirement::NotExists)
+        } else if let Some((key, value)) = requirement.split_once("!=") {
+            parse_key(key)
+                .zip(parse_value(value))
+                .map(|(key, value)| LabelRequirement::NotEquals(key, value))
+        } else if let Some((key, value)) = requirement.split_once('=') {
+            // '==' same as '='
+            let value = value.strip_prefix('=').unwrap_or(value);
+            parse_key(key)
+                .zip(parse_value(value))
+                .map(|(key, value)| LabelRequirement::Equals(key, value))
+        } else if let Some((key, set)) = requirement.split_once(char::is_whitespace) {
+            let set = set.trim_start();
+            if let Some(values) = set.strip_prefix("notin") {
+                parse_key(key)
+                    .zip(parse_values(values))
+                    .map(|(key, values)| LabelRequirement::NotIn(key, values))
+            } else if let Some(values) = set.strip_prefix("in") {
+                parse_key(key)
+                    .zip(parse_values(values))
+                    .map(|(key, values)| LabelRequirement::In(key, values))
+            } else {
+                None
+            }
+        } else {
+            parse_key(requirement).map(LabelRequirement::Exists)
+        };
+        parsed.ok_or_else(|| {
+            pipe_error(
+                PipeOperation::List,
+                format!("invalid label selector requirement '{}'", requirement),
+            )
+        })
+    }
+}
+
+fn parse_key(key: &str) -> Option<String> {
+    let key = key.trim();
+    is_valid_label_key(key).then(|| key.to_owned())
+}
+
+fn parse_value(value: &str) -> Option<String> {
+    let value = value.trim();
+    is_valid_label_value(value).then(|| value.to_owned())
+}
+
+// '(v1, v2)'
+fn parse_values(values: &str) -> Option<Vec<String>> {
+    let values = values.trim().strip_prefix('(')?.strip_suffix(')')?;
+    values.split(',').map(parse_value).collect()
+}
+
+// comma separated requirements, all must match, i.e 'env=prod,team in (ingest,etl),!canary'
+pub struct LabelSelector {
+    requirements: Vec<LabelRequirement>,
+}
+
+impl LabelSelector {
+    pub fn matches(&self, labels: &HashMap<String, String>) -> bool {
+        self.requirements
+            .iter()
+            .all(|requirement| requirement.matches(labels))
+    }
+}
+
+impl FromStr for LabelSelector {
+    type Err = Error;
+
+    fn from_str(selector: &str) -> Result<Self> {
+        // split on commas outside of parentheses
+        let mut requirements: Vec<LabelRequirement> = vec![];
+        let mut depth: usize = 0;
+        let mut start: usize = 0;
+        for (i, c) in selector.char_indices() {
+            match c {
+                '(' => depth += 1,
+                ')' => depth = depth.saturating_sub(1),
+                ',' if depth == 0 => {
+                    requirements.push(selector[start..i].parse()?);
+                    start = i + 1;
+                }
+                _ => (),
+            }
+        }
+        if !selector[start..].trim().is_empty() || !requirements.is_empty() {
+            requirements.push(selector[start..].parse()?);
+        }
+        Ok(LabelSelector { requirements })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{LabelRequirement, LabelSelector};
+    use std::collections::HashMap;
+
+    fn parse(requirement: &str) -> LabelRequirement {
+        requirement.parse().expect("parse requirement failed")
+    }
+
+    fn values(values: &[&str]) -> Vec<String> {
+        values.iter().map(|value| value.to_string()).collect()
+    }
+
+    fn labels(labels: &[(&str, &str)]) -> HashMap<String, String> {
+        labels
+            .iter()
+            .map(|(key, value)| (key.to_string(), value.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn test_parse_requirement() {
+        let equals = LabelRequirement::Equals("env".to_owned(), "prod".to_owned());
+        assert_eq!(equals, parse("env=prod"));
+        assert_eq!(equals, parse("env==prod"));
+        assert_eq!(equals, parse(" env = prod "));
+        assert_eq!(
+            LabelRequirement::NotEquals("env".to_owned(), "prod".to_owned()),
+            parse("env!=prod")
+        );
+        assert_eq!(
+            LabelRequirement::In("team".to_owned(), values(&["ingest", "etl"])),
+            parse("team in (ingest, etl)")
+        );
+        assert_eq!(
+            LabelRequirement::NotIn("team".to_owned(), values(&["ingest"])),
+            parse("team notin (ingest)")
+        );
+        assert_eq!(
+            LabelRequirement::Exists("example.com/canary".to_owned()),
+            parse("example.com/canary")
+        );
+        assert_eq!(
+            LabelRequirement::NotExists("canary".to_owned()),
+            parse("!canary")
+        );
+        // empty value allowed
+        assert_eq!(
+            LabelRequirement::Equals("env".to_owned(), String::new()),
+            parse("env=")
+        );
+    }
+
+    #[test]
+    fn test_parse_malformed_requirement() {
+        let malformed = [
+            // unclosed or missing parentheses
+            "team in (ingest, etl",
+            "team in ingest, etl)",
+            "team notin ingest",
+            // empty key
+            "",
+            "=prod",
+            "!=prod",
+            "!",
+            " in (ingest)",
+            // bad key or value characters
+            "-env=prod",
+            "env=prod-",
+            "env=pr@d",
+            "env=prod=dev",
+            "team in (ingest, e t l)",
+            "team within (ingest)",
+        ];
+        for requirement in malformed {
+            assert!(
+                requirement.parse::<LabelRequirement>().is_err(),
+                "requirement '{}' should be invalid",
+                requirement
+            );
+        }
+    }
+
+    #[test]
+    fn test_parse_selector() {
+        let selector: LabelSelector = "env=prod,team in (ingest,etl),!canary"
+            .parse()
+            .expect("parse selector failed");
+        // commas inside parentheses do not split requirements
+        assert_eq!(
+            vec![
+                LabelRequirement::Equals("env".to_owned(), "prod".to_owned()),
+                LabelRequirement::In("team".to_owned(), values(&["ingest", "etl"])),
+                LabelRequirement::NotExists("canary".to_owned()),
+            ],
+            selector.requirements
+        );
+        let selector: LabelSelector = "".parse().expect("parse empty selector failed");
+        assert!(selector.requirements.is_empty());
+        for selector in [
+            "env=prod,",
+            ",env=prod",
+            "env=prod,,team=etl",
+            "team in (a,b",
+        ] {
+            assert!(
+                selector.parse::<LabelSelector>().is_err(),
+                "selector '{}' should be invalid",
+                selector
+            );
+        }
+    }
+
+    #[test]
+    fn test_match_selector() {
+        let selector: LabelSelector = "env=prod,team notin (etl),region!=eu,!canary"
+            .parse()
+            .expect("parse selector failed");
+        assert!(selector.matches(&labels(&[("env", "prod")])));
+        assert!(selector.matches(&labels(&[("env", "prod"), ("team", "ingest")])));
+        assert!(!selector.matches(&labels(&[("env", "dev")])));
+        assert!(!selector.matches(&labels(&[("env", "prod"), ("team", "etl")])));
+        assert!(!selector.matches(&labels(&[("env", "prod"), ("region", "eu")])));
+        assert!(!selector.matches(&labels(&[("env", "prod"), ("canary", "")])));
+        let selector: LabelSelector = "team in (ingest,etl),env"
+            .parse()
+            .expect("parse selector failed");
+        assert!(selector.matches(&labels(&[("env", ""), ("team", "etl")])));
+        assert!(!selector.matches(&labels(&[("team", "etl")])));
+        assert!(!selector.matches(&labels(&[("env", "prod")])));
+    }
+}
@@ -1,14 +1,38 @@
+mod apply;
 mod constants;
 mod daemon;
+mod doctor;
 mod errors;
 pub mod grpc;
+mod job;
+mod journal;
+mod label;
 mod pipe;
+mod probe;
 mod repository;
+mod run;
+mod signal;
+mod stats;
+mod systemd;
+mod templates;
 mod utils;
+mod watch;
 
+pub use apply::*;
 pub(crate) use constants::*;
 pub use daemon::*;
+pub use doctor::*;
 pub use errors::*;
+pub use job::*;
+pub use journal::*;
+pub use label::*;
 pub use pipe::*;
+pub use probe::*;
 pub use repository::*;
+pub use run::*;
+pub use signal::*;
+pub use stats::*;
+pub(crate) use systemd::*;
+pub use templates::*;
 pub use utils::*;
+pub use watch::*;
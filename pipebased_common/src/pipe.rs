@@ -1,46 +1,91 @@
 use crate::{
-    chown, create_directory, grpc, link, open_lock_file, path_error, pipe_error, read_yml,
-    remove_directory, write_yml, PathBuilder, Result, PATH_CATALOGS, PATH_PIPE_LOCK,
-    PATH_PIPE_REGISTER, SYSTEMD_DEFAULT_DESCRIPTION, SYSTEMD_DEFAULT_GROUP,
-    SYSTEMD_DEFAULT_START_UNIT_MODE, SYSTEMD_DEFAULT_STOP_UNIT_MODE, SYSTEMD_DEFAULT_USER,
+    build_blocking_manager_proxy, build_blocking_service_proxy, build_blocking_timer_proxy,
+    build_blocking_unit_proxy, chown, create_directory, exit_reason, grpc, is_valid_time_span,
+    job_error, link, monotonic_to_unix_timestamp_micros, open_lock_file, parse_signal, path_error,
+    pipe_error, read_file, read_link, read_yml, remove_directory, remove_file, stream_journal,
+    unix_timestamp_secs, write_yml, AppDescriptor, CatalogsDescriptor, Drift, DriftType, Error,
+    HealthStatus, InstallConfiguration, JobListener, JobMode, KillWhom, PathBuilder, PipeExit,
+    PipeLogQuery, PipeLogReceiver, PipeRun, PipeRunHistory, PipeStats, ProbeDescriptor, Result,
+    ServiceConfiguration, ServiceProxyBlocking, ServiceUnitConfiguration, TimerConfiguration,
+    TimerUnitConfiguration, UnitConfiguration, PATH_CATALOGS, PATH_PIPE_KEPT, PATH_PIPE_LOCK,
+    PATH_PIPE_REGISTER, PATH_PIPE_RUNS, PIPE_DEFAULT_SLICE, PIPE_DEFAULT_UNIT_PREFIX,
+    PIPE_DEFAULT_UPDATE_TIMEOUT_SECS, PIPE_REGISTER_VERSION, PIPE_RUN_HISTORY_CAPACITY,
+    PIPE_SECRET_ENV_KEY_PATTERNS, PIPE_SECRET_ENV_MASK, PIPE_STATUS_POLL_INTERVAL_MILLIS,
+    SYSTEMD_DEFAULT_DESCRIPTION, SYSTEMD_DEFAULT_GROUP, SYSTEMD_DEFAULT_RELOAD_UNIT_MODE,
+    SYSTEMD_DEFAULT_RESTART_UNIT_MODE, SYSTEMD_DEFAULT_USER, SYSTEMD_PIPE_ID_KEY,
+    SYSTEMD_SERVICE_UNIT_SUFFIX, SYSTEMD_SLICE_UNIT_SUFFIX, SYSTEMD_TIMER_UNIT_SUFFIX,
+    SYSTEMD_UNIT_CONFIGURATION_DIRECTORY, SYSTEMD_UNIT_NAME_MAX_LENGTH,
 };
 use fslock::LockFile;
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 use std::{
+    collections::HashMap,
     fmt::Display,
-    fs::canonicalize,
+    fs::{canonicalize, read_dir},
     path::{Path, PathBuf},
+    str::FromStr,
+    time::{Duration, SystemTime},
 };
 use systemd_client::{
     create_unit_configuration_file, delete_unit_configuration_file, manager, unit,
-    ServiceConfiguration, ServiceUnitConfiguration, UnitActiveStateType, UnitConfiguration,
-    UnitLoadStateType, UnitProps, UnitSubStateType,
+    UnitActiveStateType, UnitLoadStateType, UnitProps, UnitSubStateType,
 };
-use tracing::warn;
+use tokio::time::{sleep, Instant};
+use tracing::{info, warn};
 
 #[derive(Debug)]
 pub enum PipeOperation {
+    Apply,
     Deregister,
+    Disable,
+    Enable,
     Init,
+    Inspect,
+    Kill,
+    List,
     Load,
+    Logs,
+    Migrate,
+    Probe,
     Register,
+    Reload,
+    Restart,
     Start,
+    Stats,
     Status,
     Stop,
     Remove,
+    Repair,
+    Update,
+    Validate,
 }
 
 impl Display for PipeOperation {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         let op = match self {
+            PipeOperation::Apply => "apply",
             PipeOperation::Deregister => "deregister",
+            PipeOperation::Disable => "disable",
+            PipeOperation::Enable => "enable",
             PipeOperation::Init => "init",
+            PipeOperation::Inspect => "inspect",
+            PipeOperation::Kill => "kill",
+            PipeOperation::List => "list",
             PipeOperation::Load => "load",
+            PipeOperation::Logs => "logs",
+            PipeOperation::Migrate => "migrate",
+            PipeOperation::Probe => "probe",
             PipeOperation::Register => "register",
+            PipeOperation::Reload => "reload",
+            PipeOperation::Restart => "restart",
             PipeOperation::Start => "start",
+            PipeOperation::Stats => "stats",
             PipeOperation::Status => "status",
             PipeOperation::Stop => "stop",
             PipeOperation::Remove => "delete",
+            PipeOperation::Repair => "repair",
+            PipeOperation::Update => "update",
+            PipeOperation::Validate => "validate",
         };
         write!(f, "{}", op)
     }
@@ -70,17 +115,18 @@ impl From<UnitLoadStateType> for PipeLoadStateType {
     }
 }
 
-impl ToString for PipeLoadStateType {
-    fn to_string(&self) -> String {
-        match self {
-            PipeLoadStateType::Stub => String::from("stub"),
-            PipeLoadStateType::Loaded => String::from("loaded"),
-            PipeLoadStateType::NotFound => String::from("not-found"),
-            PipeLoadStateType::Error => String::from("error"),
-            PipeLoadStateType::Merged => String::from("merged"),
-            PipeLoadStateType::Masked => String::from("masked"),
-            PipeLoadStateType::Other(other) => other.clone(),
-        }
+impl Display for PipeLoadStateType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let state = match self {
+            PipeLoadStateType::Stub => "stub",
+            PipeLoadStateType::Loaded => "loaded",
+            PipeLoadStateType::NotFound => "not-found",
+            PipeLoadStateType::Error => "error",
+            PipeLoadStateType::Merged => "merged",
+            PipeLoadStateType::Masked => "masked",
+            PipeLoadStateType::Other(other) => other.as_str(),
+        };
+        write!(f, "{}", state)
     }
 }
 
@@ -108,17 +154,18 @@ impl From<UnitActiveStateType> for PipeActiveStateType {
     }
 }
 
-impl ToString for PipeActiveStateType {
-    fn to_string(&self) -> String {
-        match self {
-            PipeActiveStateType::Active => String::from("active"),
-            PipeActiveStateType::Reloading => String::from("reloading"),
-            PipeActiveStateType::Inactive => String::from("inactive"),
-            PipeActiveStateType::Failed => String::from("failed"),
-            PipeActiveStateType::Activating => String::from("activating"),
-            PipeActiveStateType::Deactivating => String::from("deactivating"),
-            PipeActiveStateType::Other(other) => other.clone(),
-        }
+impl Display for PipeActiveStateType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let state = match self {
+            PipeActiveStateType::Active => "active",
+            PipeActiveStateType::Reloading => "reloading",
+            PipeActiveStateType::Inactive => "inactive",
+            PipeActiveStateType::Failed => "failed",
+            PipeActiveStateType::Activating => "activating",
+            PipeActiveStateType::Deactivating => "deactivating",
+            PipeActiveStateType::Other(other) => other.as_str(),
+        };
+        write!(f, "{}", state)
     }
 }
 
@@ -168,28 +215,29 @@ impl From<UnitSubStateType> for PipeSubStateType {
     }
 }
 
-impl ToString for PipeSubStateType {
-    fn to_string(&self) -> String {
-        match self {
-            PipeSubStateType::AutoRestart => String::from("auto-restart"),
-            PipeSubStateType::Dead => String::from("dead"),
-            PipeSubStateType::Exited => String::from("exited"),
-            PipeSubStateType::Failed => String::from("failed"),
-            PipeSubStateType::FinalSigterm => String::from("final-sigterm"),
-            PipeSubStateType::FinalSigkill => String::from("final-sigkill"),
-            PipeSubStateType::Reload => String::from("reload"),
-            PipeSubStateType::Running => String::from("running"),
-            PipeSubStateType::Start => String::from("start"),
-            PipeSubStateType::StartPre => String::from("start-pre"),
-            PipeSubStateType::StartPost => String::from("start-post"),
-            PipeSubStateType::Stop => String::from("stop"),
-            PipeSubStateType::StopPost => String::from("stop-post"),
-            PipeSubStateType::StopSigabrt => String::from("stop-sigabrt"),
-            PipeSubStateType::StopSigterm => String::from("stop-sigterm"),
-            PipeSubStateType::StopSigkill => String::from("stop-sigkill"),
-            PipeSubStateType::Waiting => String::from("waiting"),
-            PipeSubStateType::Other(other) => other.clone(),
-        }
+impl Display for PipeSubStateType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let state = match self {
+            PipeSubStateType::AutoRestart => "auto-restart",
+            PipeSubStateType::Dead => "dead",
+            PipeSubStateType::Exited => "exited",
+            PipeSubStateType::Failed => "failed",
+            PipeSubStateType::FinalSigterm => "final-sigterm",
+            PipeSubStateType::FinalSigkill => "final-sigkill",
+            PipeSubStateType::Reload => "reload",
+            PipeSubStateType::Running => "running",
+            PipeSubStateType::Start => "start",
+            PipeSubStateType::StartPre => "start-pre",
+            PipeSubStateType::StartPost => "start-post",
+            PipeSubStateType::Stop => "stop",
+            PipeSubStateType::StopPost => "stop-post",
+            PipeSubStateType::StopSigabrt => "stop-sigabrt",
+            PipeSubStateType::StopSigterm => "stop-sigterm",
+            PipeSubStateType::StopSigkill => "stop-sigkill",
+            PipeSubStateType::Waiting => "waiting",
+            PipeSubStateType::Other(other) => other.as_str(),
+        };
+        write!(f, "{}", state)
     }
 }
 
@@ -199,6 +247,16 @@ pub struct PipeState {
     pub load_state: PipeLoadStateType,
     pub active_state: PipeActiveStateType,
     pub sub_state: PipeSubStateType,
+    // unit enabled to start at boot
+    pub enabled: bool,
+    // labels in pipe record
+    pub labels: HashMap<String, String>,
+    // microseconds since epoch of timer trigger, undefined if not scheduled or unknown
+    pub next_trigger: Option<u64>,
+    pub last_trigger: Option<u64>,
+    // daemon side probe results, undefined if probe undefined
+    pub liveness: Option<HealthStatus>,
+    pub readiness: Option<HealthStatus>,
 }
 
 impl PipeState {
@@ -209,6 +267,22 @@ impl PipeState {
     pub fn is_dead(&self) -> bool {
         matches!(self.sub_state, PipeSubStateType::Dead)
     }
+
+    pub fn is_active(&self) -> bool {
+        matches!(self.active_state, PipeActiveStateType::Active)
+    }
+
+    pub fn is_failed(&self) -> bool {
+        matches!(self.active_state, PipeActiveStateType::Failed)
+    }
+
+    pub fn is_running(&self) -> bool {
+        matches!(self.sub_state, PipeSubStateType::Running)
+    }
+
+    pub fn is_exited(&self) -> bool {
+        matches!(self.sub_state, PipeSubStateType::Exited)
+    }
 }
 
 impl From<PipeState> for grpc::daemon::PipeState {
@@ -217,322 +291,2546 @@ impl From<PipeState> for grpc::daemon::PipeState {
         let load_state = origin.load_state.to_string();
         let active_state = origin.active_state.to_string();
         let sub_state = origin.sub_state.to_string();
+        let enabled = origin.enabled;
+        let labels = origin.labels;
+        let next_trigger = origin.next_trigger;
+        let last_trigger = origin.last_trigger;
+        let liveness = origin.liveness.map(|status| status.to_string());
+        let readiness = origin.readiness.map(|status| status.to_string());
         grpc::daemon::PipeState {
             id,
             load_state,
             active_state,
             sub_state,
+            enabled,
+            labels,
+            next_trigger,
+            last_trigger,
+            liveness,
+            readiness,
         }
     }
 }
 
-#[derive(Clone)]
-pub struct EnvironmentVariable {
-    pub key: String,
-    pub value: String,
-}
-
-#[derive(Clone)]
-pub struct PipeDescriptor<'a> {
-    // pipe id
-    pub id: String,
+// pipe details read from unit and service properties
+pub struct PipeInspection {
+    pub state: PipeState,
     pub description: String,
     pub user: String,
     pub group: String,
+    // secret values masked
     pub envs: Vec<EnvironmentVariable>,
-    pub app_path: &'a Path,
-    pub catalogs_path: &'a Path,
+    pub app: Option<AppDescriptor>,
+    pub catalogs: Option<CatalogsDescriptor>,
+    pub app_path: Option<PathBuf>,
+    pub catalogs_path: Option<PathBuf>,
+    pub working_directory: PathBuf,
+    pub unit_path: PathBuf,
+    pub unit: String,
+    pub main_pid: u32,
+    // CLD_* code of main process, i.e 1 exited, 2 killed, 3 dumped
+    pub exec_main_code: i32,
+    // exit code or signal number of main process
+    pub exec_main_status: i32,
+    pub n_restarts: u32,
+    // microseconds since epoch, 0 if never entered
+    pub active_enter_timestamp: u64,
+    pub inactive_enter_timestamp: u64,
 }
 
-impl<'a> PartialEq for PipeDescriptor<'a> {
-    fn eq(&self, other: &Self) -> bool {
-        self.id == other.id
-    }
+fn path_to_string(path: &Path) -> String {
+    path.to_str().unwrap_or_default().to_owned()
 }
 
-impl<'a> PipeDescriptor<'a> {
-    pub fn builder() -> PipeDescriptorBuilder<'a> {
-        PipeDescriptorBuilder::default()
+impl From<PipeInspection> for grpc::daemon::InspectPipeResponse {
+    fn from(origin: PipeInspection) -> Self {
+        grpc::daemon::InspectPipeResponse {
+            state: Some(origin.state.into()),
+            description: origin.description,
+            user: origin.user,
+            group: origin.group,
+            envs: origin.envs.into_iter().map(|env| env.into()).collect(),
+            app: origin.app.map(|app| app.into()),
+            catalogs: origin.catalogs.map(|catalogs| catalogs.into()),
+            app_path: origin.app_path.as_deref().map(path_to_string),
+            catalogs_path: origin.catalogs_path.as_deref().map(path_to_string),
+            working_directory: path_to_string(origin.working_directory.as_path()),
+            unit_path: path_to_string(origin.unit_path.as_path()),
+            unit: origin.unit,
+            main_pid: origin.main_pid,
+            exec_main_code: origin.exec_main_code,
+            exec_main_status: origin.exec_main_status,
+            n_restarts: origin.n_restarts,
+            active_enter_timestamp: origin.active_enter_timestamp,
+            inactive_enter_timestamp: origin.inactive_enter_timestamp,
+        }
     }
 }
 
-pub struct PipeDescriptorBuilder<'a> {
-    pub id: Option<String>,
-    pub description: String,
-    pub user: String,
-    pub group: String,
-    pub envs: Vec<EnvironmentVariable>,
-    pub app_path: Option<&'a Path>,
-    pub catalogs_path: Option<&'a Path>,
+#[derive(Clone, Deserialize, Serialize)]
+pub struct EnvironmentVariable {
+    pub key: String,
+    pub value: String,
 }
 
-impl<'a> Default for PipeDescriptorBuilder<'a> {
-    fn default() -> Self {
-        PipeDescriptorBuilder {
-            id: None,
-            description: String::from(SYSTEMD_DEFAULT_DESCRIPTION),
-            user: String::from(SYSTEMD_DEFAULT_USER),
-            group: String::from(SYSTEMD_DEFAULT_GROUP),
-            envs: vec![],
-            app_path: None,
-            catalogs_path: None,
-        }
+impl Display for EnvironmentVariable {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}={}", self.key, self.value)
     }
 }
 
-impl<'a> PipeDescriptorBuilder<'a> {
-    pub fn id(mut self, id: String) -> Self {
-        self.id = Some(id);
-        self
+impl FromStr for EnvironmentVariable {
+    type Err = Error;
+
+    // parse 'key=value'
+    fn from_str(s: &str) -> Result<Self> {
+        match s.split_once('=') {
+            Some((key, value)) => Ok(EnvironmentVariable {
+                key: key.to_owned(),
+                value: value.to_owned(),
+            }),
+            None => Err(pipe_error(
+                PipeOperation::Validate,
+                format!("invalid environment variable '{}'", s),
+            )),
+        }
     }
+}
 
-    pub fn description(mut self, description: String) -> Self {
-        self.description = description;
-        self
+impl From<EnvironmentVariable> for grpc::daemon::EnvironmentVariable {
+    fn from(origin: EnvironmentVariable) -> Self {
+        grpc::daemon::EnvironmentVariable {
+            key: origin.key,
+            value: origin.value,
+        }
     }
+}
 
-    pub fn user(mut self, user: String) -> Self {
-        self.user = user;
-        self
+impl EnvironmentVariable {
+    pub fn is_secret(&self) -> bool {
+        let key = self.key.to_uppercase();
+        PIPE_SECRET_ENV_KEY_PATTERNS
+            .iter()
+            .any(|pattern| key.contains(pattern))
     }
 
-    pub fn group(mut self, group: String) -> Self {
-        self.group = group;
-        self
+    // mask value if secret
+    pub fn masked(self) -> Self {
+        match self.is_secret() {
+            true => EnvironmentVariable {
+                key: self.key,
+                value: PIPE_SECRET_ENV_MASK.to_owned(),
+            },
+            false => self,
+        }
     }
 
-    pub fn env(mut self, env: EnvironmentVariable) -> Self {
-        self.envs.push(env);
-        self
+    // https://pubs.opengroup.org/onlinepubs/9699919799/basedefs/V1_chap08.html
+    pub fn is_valid_key(&self) -> bool {
+        let mut chars = self.key.chars();
+        let leading_valid = match chars.next() {
+            Some(c) => c.is_ascii_alphabetic() || c == '_',
+            None => false,
+        };
+        leading_valid && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
     }
+}
 
-    pub fn envs(mut self, envs: Vec<EnvironmentVariable>) -> Self {
-        self.envs.extend(envs);
-        self
+#[derive(Clone, Default, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum RestartPolicy {
+    #[default]
+    No,
+    OnFailure,
+    Always,
+}
+
+impl Display for RestartPolicy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let policy = match self {
+            RestartPolicy::No => "no",
+            RestartPolicy::OnFailure => "on-failure",
+            RestartPolicy::Always => "always",
+        };
+        write!(f, "{}", policy)
     }
+}
 
-    pub fn app_path(mut self, app_path: &'a Path) -> Self {
-        self.app_path = Some(app_path);
-        self
+impl FromStr for RestartPolicy {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "no" => Ok(RestartPolicy::No),
+            "on-failure" => Ok(RestartPolicy::OnFailure),
+            "always" => Ok(RestartPolicy::Always),
+            _ => Err(pipe_error(
+                PipeOperation::Validate,
+                format!("invalid restart policy '{}'", s),
+            )),
+        }
     }
+}
 
-    pub fn catalogs_path(mut self, catalogs_path: &'a Path) -> Self {
-        self.catalogs_path = Some(catalogs_path);
-        self
+#[derive(Clone, Default, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ServiceType {
+    #[default]
+    Simple,
+    // run to completion
+    Oneshot,
+}
+
+impl Display for ServiceType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let service_type = match self {
+            ServiceType::Simple => "simple",
+            ServiceType::Oneshot => "oneshot",
+        };
+        write!(f, "{}", service_type)
     }
+}
 
-    pub fn build(self) -> PipeDescriptor<'a> {
-        let id = self.id.expect("id undefined");
-        let description = self.description;
-        let user = self.user;
-        let group = self.group;
-        let envs = self.envs;
-        let app_path = self.app_path.expect("app path undefined");
-        let catalogs_path = self.catalogs_path.expect("catalogs path undefined");
-        PipeDescriptor {
-            id,
-            description,
-            user,
-            group,
-            envs,
-            app_path,
-            catalogs_path,
+impl FromStr for ServiceType {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "simple" => Ok(ServiceType::Simple),
+            "oneshot" => Ok(ServiceType::Oneshot),
+            _ => Err(pipe_error(
+                PipeOperation::Validate,
+                format!("invalid service type '{}'", s),
+            )),
         }
     }
 }
 
-#[derive(Default)]
-pub struct PipeUnitNameBuilder<'a> {
-    id: Option<&'a str>,
+// restart and crash-loop settings
+#[derive(Clone, Default, Deserialize, Serialize)]
+pub struct RestartDescriptor {
+    pub policy: RestartPolicy,
+    // delay before restart, a unit-less value in seconds, or a time span value such as "5min 20s"
+    pub delay: Option<String>,
+    // start rate limit, at most 'burst' starts within 'interval'
+    pub start_limit_burst: Option<u32>,
+    pub start_limit_interval: Option<String>,
 }
 
-impl<'a> PipeUnitNameBuilder<'a> {
-    pub fn id(mut self, id: &'a str) -> Self {
-        self.id = Some(id);
-        self
+impl RestartDescriptor {
+    pub fn validate(&self) -> Result<()> {
+        let spans = [
+            ("restart delay", &self.delay),
+            ("start limit interval", &self.start_limit_interval),
+        ];
+        for (name, span) in spans {
+            match span {
+                Some(span) if !is_valid_time_span(span) => {
+                    return Err(pipe_error(
+                        PipeOperation::Validate,
+                        format!("invalid {} '{}'", name, span),
+                    ))
+                }
+                _ => continue,
+            }
+        }
+        Ok(())
     }
+}
 
-    pub fn build(self) -> String {
-        let id = self.id.expect("pipe id undefined");
-        format!("{}.service", id)
+impl TryFrom<grpc::daemon::RestartDescriptor> for RestartDescriptor {
+    type Error = Error;
+
+    fn try_from(origin: grpc::daemon::RestartDescriptor) -> Result<Self> {
+        let policy = match origin.policy {
+            Some(policy) => policy.parse()?,
+            None => RestartPolicy::default(),
+        };
+        let delay = origin.delay;
+        let start_limit_burst = origin.start_limit_burst;
+        let start_limit_interval = origin.start_limit_interval;
+        Ok(RestartDescriptor {
+            policy,
+            delay,
+            start_limit_burst,
+            start_limit_interval,
+        })
     }
 }
 
-#[derive(Deserialize)]
-pub struct PipeManagerConfig {
-    pub workspace: String,
+#[derive(Clone, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum KillMode {
+    // all processes in control group
+    ControlGroup,
+    // main process first, then remaining processes on final kill
+    Mixed,
+    // main process only
+    Process,
+    None,
 }
 
-pub struct PipeManager {
-    pub workspace: PathBuf,
+impl Display for KillMode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mode = match self {
+            KillMode::ControlGroup => "control-group",
+            KillMode::Mixed => "mixed",
+            KillMode::Process => "process",
+            KillMode::None => "none",
+        };
+        write!(f, "{}", mode)
+    }
 }
 
-pub struct PipeManagerBuilder {
-    workspace: Option<PathBuf>,
+impl FromStr for KillMode {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "control-group" => Ok(KillMode::ControlGroup),
+            "mixed" => Ok(KillMode::Mixed),
+            "process" => Ok(KillMode::Process),
+            "none" => Ok(KillMode::None),
+            _ => Err(pipe_error(
+                PipeOperation::Validate,
+                format!("invalid kill mode '{}'", s),
+            )),
+        }
+    }
 }
 
-impl Default for PipeManagerBuilder {
-    fn default() -> Self {
-        Self::new()
-    }
+// graceful stop settings, let pipeline flush before killed
+// https://www.freedesktop.org/software/systemd/man/systemd.kill.html
+#[derive(Clone, Default, Deserialize, Serialize)]
+pub struct StopDescriptor {
+    // wait before final kill signal, a unit-less value in seconds, a time span value, or 'infinity'
+    pub timeout: Option<String>,
+    // signal name or number sent on stop, SIGTERM by default
+    pub kill_signal: Option<String>,
+    pub kill_mode: Option<KillMode>,
+    // sent on stop timeout, SIGKILL by default
+    pub final_kill_signal: Option<String>,
+    // send final kill signal on stop timeout
+    pub send_sigkill: Option<bool>,
 }
 
-impl PipeManagerBuilder {
-    pub fn new() -> Self {
-        PipeManagerBuilder { workspace: None }
+impl StopDescriptor {
+    pub fn validate(&self) -> Result<()> {
+        if let Some(timeout) = self.timeout.as_ref() {
+            if !is_valid_time_span(timeout) {
+                return Err(pipe_error(
+                    PipeOperation::Validate,
+                    format!("invalid stop timeout '{}'", timeout),
+                ));
+            }
+        }
+        if let Some(kill_signal) = self.kill_signal.as_ref() {
+            parse_signal(kill_signal)?;
+        }
+        if let Some(final_kill_signal) = self.final_kill_signal.as_ref() {
+            parse_signal(final_kill_signal)?;
+        }
+        Ok(())
     }
+}
 
-    pub fn workspace(mut self, workspace: String) -> Self {
-        self.workspace = Some(PathBuf::from(workspace));
-        self
-    }
+impl TryFrom<grpc::daemon::StopDescriptor> for StopDescriptor {
+    type Error = Error;
 
-    pub fn build(self) -> PipeManager {
-        let workspace = self.workspace.expect("workspace undefined");
-        let workspace = canonicalize(workspace).expect("canonicalize workspace failed");
-        PipeManager { workspace }
+    fn try_from(origin: grpc::daemon::StopDescriptor) -> Result<Self> {
+        let kill_mode = match origin.kill_mode {
+            Some(kill_mode) => Some(kill_mode.parse()?),
+            None => None,
+        };
+        Ok(StopDescriptor {
+            timeout: origin.timeout,
+            kill_signal: origin.kill_signal,
+            kill_mode,
+            final_kill_signal: origin.final_kill_signal,
+            send_sigkill: origin.send_sigkill,
+        })
     }
 }
 
-impl PipeManager {
-    pub fn builder() -> PipeManagerBuilder {
-        PipeManagerBuilder::default()
-    }
+// cgroup resource limits
+// https://www.freedesktop.org/software/systemd/man/systemd.resource-control.html
+#[derive(Clone, Default, Deserialize, Serialize)]
+pub struct LimitDescriptor {
+    // bytes with optional K, M, G, T suffix, percentage of physical memory, or 'infinity'
+    pub memory_max: Option<String>,
+    pub memory_high: Option<String>,
+    // percentage of one cpu time, may exceed 100% on multi-core host
+    pub cpu_quota: Option<String>,
+    // 1 - 10000
+    pub cpu_weight: Option<u64>,
+    // number, percentage of system limit, or 'infinity'
+    pub tasks_max: Option<String>,
+    // 1 - 10000
+    pub io_weight: Option<u64>,
+}
 
-    // init service configuration file and add pipe id into register
-    pub(crate) fn init(&self, desc: &PipeDescriptor<'_>) -> Result<()> {
-        let mut lock_file = self.open_pipe_lock()?;
-        lock_file.lock()?;
-        let id = desc.id.as_str();
-        let registered = self.do_check_pipe_registered(id)?;
-        if registered {
-            return Err(pipe_error(
-                PipeOperation::Init,
-                format!("conflict pipe id '{}'", id),
-            ));
+impl LimitDescriptor {
+    pub fn validate(&self) -> Result<()> {
+        let memories = [
+            ("MemoryMax", &self.memory_max),
+            ("MemoryHigh", &self.memory_high),
+        ];
+        for (name, memory) in memories {
+            match memory {
+                Some(memory) if !Self::is_valid_memory(memory) => {
+                    return Err(Self::invalid_limit_error(name, memory))
+                }
+                _ => continue,
+            }
         }
-        let unit_name = PipeUnitNameBuilder::default().id(id).build();
-        if Self::do_get_unit(unit_name.as_str()).is_ok() {
-            return Err(pipe_error(
-                PipeOperation::Init,
-                format!("invalid pipe unit name given id '{}'", id),
-            ));
+        if let Some(cpu_quota) = self.cpu_quota.as_ref() {
+            if !Self::is_valid_cpu_quota(cpu_quota) {
+                return Err(Self::invalid_limit_error("CPUQuota", cpu_quota));
+            }
         }
-        // init working directory
-        let working_directory = self.do_create_working_directory(id)?;
-        // link catalogs
-        Self::do_link_catalogs(working_directory.as_path(), desc.catalogs_path)?;
-        Self::do_create_ownership(
-            desc.user.as_str(),
-            desc.group.as_str(),
-            working_directory.as_path(),
-        )?;
-        // create service configuration file
-        Self::do_create_pipe_configuration_file(desc, working_directory.as_path())?;
-        self.do_register_pipe(id)
-    }
-
-    pub(crate) fn start(&self, id: &str) -> Result<()> {
-        let mut lock_file = self.open_pipe_lock()?;
-        lock_file.lock()?;
-        let registered = self.do_check_pipe_registered(id)?;
-        if !registered {
-            return Err(pipe_error(
-                PipeOperation::Start,
-                format!("pipe '{}' not registered", id),
-            ));
+        if let Some(tasks_max) = self.tasks_max.as_ref() {
+            if !Self::is_valid_tasks_max(tasks_max) {
+                return Err(Self::invalid_limit_error("TasksMax", tasks_max));
+            }
+        }
+        let weights = [("CPUWeight", self.cpu_weight), ("IOWeight", self.io_weight)];
+        for (name, weight) in weights {
+            match weight {
+                Some(weight) if !(1..=10000).contains(&weight) => {
+                    return Err(Self::invalid_limit_error(name, weight))
+                }
+                _ => continue,
+            }
         }
-        let unit_name = PipeUnitNameBuilder::default().id(id).build();
-        Self::do_start_unit(unit_name.as_str())
+        Ok(())
     }
 
-    pub(crate) fn stop(&self, id: &str) -> Result<()> {
-        let mut lock_file = self.open_pipe_lock()?;
-        lock_file.lock()?;
-        let registered = self.do_check_pipe_registered(id)?;
-        if !registered {
-            return Err(pipe_error(
-                PipeOperation::Stop,
-                format!("pipe '{}' not registered", id),
-            ));
-        }
-        let unit_name = PipeUnitNameBuilder::default().id(id).build();
-        Self::do_stop_unit(unit_name.as_str())
+    fn invalid_limit_error<T: Display>(name: &str, value: T) -> Error {
+        pipe_error(
+            PipeOperation::Validate,
+            format!("invalid {} '{}'", name, value),
+        )
     }
 
-    pub(crate) fn status(&self, id: &str) -> Result<PipeState> {
-        let mut lock_file = self.open_pipe_lock()?;
-        lock_file.lock()?;
-        let registered = self.do_check_pipe_registered(id)?;
-        if !registered {
-            return Err(pipe_error(
-                PipeOperation::Status,
-                format!("pipe '{}' not registered", id),
-            ));
+    fn is_valid_percentage(value: &str, max: Option<f64>) -> bool {
+        match value.strip_suffix('%').map(|value| value.parse::<f64>()) {
+            Some(Ok(value)) => value > 0.0 && max.is_none_or(|max| value <= max),
+            _ => false,
         }
-        Self::do_status(id)
     }
 
-    // delete service configuration file and remove pipe id from register
-    pub(crate) fn remove(&self, id: &str) -> Result<()> {
-        let mut lock_file = self.open_pipe_lock()?;
-        lock_file.lock()?;
-        let registered = self.do_check_pipe_registered(id)?;
-        if !registered {
-            warn!("pipe '{}' not registered", id);
-            return Ok(());
+    fn is_valid_memory(value: &str) -> bool {
+        if value == "infinity" || Self::is_valid_percentage(value, Some(100.0)) {
+            return true;
         }
-        let state = Self::do_status(id)?;
-        // before pipe deletion, the process should be stopped first
-        if !state.is_inactive() {
-            return Err(pipe_error(
-                PipeOperation::Remove,
-                format!("pipe '{}' is not inactive", id),
-            ));
+        // bytes with optional fraction and base 1024 suffix, as systemd parse_size
+        let (value, exponent) = match value.char_indices().last() {
+            Some((i, suffix)) => match "BKMGTPE".find(suffix) {
+                Some(exponent) => (&value[..i], exponent as u32),
+                None => (value, 0),
+            },
+            None => return false,
+        };
+        let (integer, fraction) = value.split_once('.').unwrap_or((value, ""));
+        if integer.is_empty() || !integer.bytes().all(|b| b.is_ascii_digit()) {
+            return false;
         }
-        if !state.is_dead() {
-            return Err(pipe_error(
-                PipeOperation::Remove,
-                format!("pipe '{}' is not dead", id),
-            ));
+        if !fraction.bytes().all(|b| b.is_ascii_digit()) {
+            return false;
+        }
+        // size must fit in 64 bits
+        match integer.parse::<u64>() {
+            Ok(integer) => integer.checked_mul(1024u64.pow(exponent)).is_some(),
+            Err(_) => false,
         }
-        Self::do_delete_pipe_configuration_file(id)?;
-        self.do_delete_working_directory(id)?;
-        self.do_deregister_pipe(id)?;
-        Ok(())
     }
 
-    pub(crate) fn list_pipe_register(&self) -> Result<Vec<String>> {
-        let mut lock_file = self.open_pipe_lock()?;
-        lock_file.lock()?;
+    fn is_valid_cpu_quota(value: &str) -> bool {
+        Self::is_valid_percentage(value, None)
+    }
+
+    fn is_valid_tasks_max(value: &str) -> bool {
+        value == "infinity"
+            || Self::is_valid_percentage(value, Some(100.0))
+            || value.parse::<u64>().is_ok()
+    }
+}
+
+impl From<grpc::daemon::LimitDescriptor> for LimitDescriptor {
+    fn from(origin: grpc::daemon::LimitDescriptor) -> Self {
+        let memory_max = origin.memory_max;
+        let memory_high = origin.memory_high;
+        let cpu_quota = origin.cpu_quota;
+        let cpu_weight = origin.cpu_weight;
+        let tasks_max = origin.tasks_max;
+        let io_weight = origin.io_weight;
+        LimitDescriptor {
+            memory_max,
+            memory_high,
+            cpu_quota,
+            cpu_weight,
+            tasks_max,
+            io_weight,
+        }
+    }
+}
+
+#[derive(Clone, Default, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum SecurityPreset {
+    // bare unit, no sandboxing, hardening is opt-in so that existing pipes keep running
+    #[default]
+    None,
+    // read-only system and home, private tmp, no privilege escalation
+    Default,
+    // default plus full read-only file system except working directory, no capabilities
+    // and internet / unix sockets only
+    Strict,
+}
+
+impl Display for SecurityPreset {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let preset = match self {
+            SecurityPreset::None => "none",
+            SecurityPreset::Default => "default",
+            SecurityPreset::Strict => "strict",
+        };
+        write!(f, "{}", preset)
+    }
+}
+
+impl FromStr for SecurityPreset {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "none" => Ok(SecurityPreset::None),
+            "default" => Ok(SecurityPreset::Default),
+            "strict" => Ok(SecurityPreset::Strict),
+            _ => Err(pipe_error(
+                PipeOperation::Validate,
+                format!("invalid security preset '{}'", s),
+            )),
+        }
+    }
+}
+
+// sandboxing and hardening settings, overrides take precedence over preset
+// https://www.freedesktop.org/software/systemd/man/systemd.exec.html#Sandboxing
+#[derive(Clone, Default, Deserialize, Serialize)]
+pub struct SecurityDescriptor {
+    pub preset: SecurityPreset,
+    // yes, no, full, strict
+    pub protect_system: Option<String>,
+    // yes, no, read-only, tmpfs
+    pub protect_home: Option<String>,
+    pub private_tmp: Option<bool>,
+    pub no_new_privileges: Option<bool>,
+    // empty to drop all capabilities
+    pub capability_bounding_set: Option<Vec<String>>,
+    // writable paths in addition to working directory
+    pub read_write_paths: Vec<String>,
+    pub restrict_address_families: Option<Vec<String>>,
+}
+
+impl SecurityDescriptor {
+    pub fn protect_system(&self) -> Option<&str> {
+        match self.protect_system.as_ref() {
+            Some(protect_system) => Some(protect_system.as_str()),
+            None => match self.preset {
+                SecurityPreset::None => None,
+                SecurityPreset::Default => Some("full"),
+                SecurityPreset::Strict => Some("strict"),
+            },
+        }
+    }
+
+    pub fn protect_home(&self) -> Option<&str> {
+        match self.protect_home.as_ref() {
+            Some(protect_home) => Some(protect_home.as_str()),
+            // app and catalogs could be located at home directory, keep them readable
+            None => match self.preset {
+                SecurityPreset::None => None,
+                SecurityPreset::Default | SecurityPreset::Strict => Some("read-only"),
+            },
+        }
+    }
+
+    pub fn private_tmp(&self) -> Option<bool> {
+        match self.preset {
+            SecurityPreset::None => self.private_tmp,
+            _ => Some(self.private_tmp.unwrap_or(true)),
+        }
+    }
+
+    pub fn no_new_privileges(&self) -> Option<bool> {
+        match self.preset {
+            SecurityPreset::None => self.no_new_privileges,
+            _ => Some(self.no_new_privileges.unwrap_or(true)),
+        }
+    }
+
+    pub fn capability_bounding_set(&self) -> Option<Vec<&str>> {
+        match self.capability_bounding_set.as_ref() {
+            Some(capabilities) => Some(capabilities.iter().map(|c| c.as_str()).collect()),
+            None => match self.preset {
+                SecurityPreset::Strict => Some(vec![]),
+                _ => None,
+            },
+        }
+    }
+
+    pub fn restrict_address_families(&self) -> Option<Vec<&str>> {
+        match self.restrict_address_families.as_ref() {
+            Some(families) => Some(families.iter().map(|f| f.as_str()).collect()),
+            None => match self.preset {
+                SecurityPreset::Strict => Some(vec!["AF_UNIX", "AF_INET", "AF_INET6"]),
+                _ => None,
+            },
+        }
+    }
+
+    // working directory should be writable once file system is protected
+    pub fn is_file_system_protected(&self) -> bool {
+        let protect_system = !matches!(self.protect_system(), None | Some("no"));
+        let protect_home = !matches!(self.protect_home(), None | Some("no"));
+        protect_system || protect_home || !self.read_write_paths.is_empty()
+    }
+
+    pub fn validate(&self) -> Result<()> {
+        if let Some(protect_system) = self.protect_system.as_ref() {
+            if !matches!(protect_system.as_str(), "yes" | "no" | "full" | "strict") {
+                return Err(Self::invalid_security_error(
+                    "ProtectSystem",
+                    protect_system,
+                ));
+            }
+        }
+        if let Some(protect_home) = self.protect_home.as_ref() {
+            if !matches!(protect_home.as_str(), "yes" | "no" | "read-only" | "tmpfs") {
+                return Err(Self::invalid_security_error("ProtectHome", protect_home));
+            }
+        }
+        for capability in self.capability_bounding_set.iter().flatten() {
+            if !Self::is_valid_symbol(capability, "CAP_") {
+                return Err(Self::invalid_security_error(
+                    "CapabilityBoundingSet",
+                    capability,
+                ));
+            }
+        }
+        for family in self.restrict_address_families.iter().flatten() {
+            if family != "none" && !Self::is_valid_symbol(family, "AF_") {
+                return Err(Self::invalid_security_error(
+                    "RestrictAddressFamilies",
+                    family,
+                ));
+            }
+        }
+        for path in self.read_write_paths.iter() {
+            let absolute_path = path.strip_prefix('-').unwrap_or(path);
+            if !Path::new(absolute_path).is_absolute() {
+                return Err(Self::invalid_security_error("ReadWritePaths", path));
+            }
+        }
+        Ok(())
+    }
+
+    fn invalid_security_error(name: &str, value: &str) -> Error {
+        pipe_error(
+            PipeOperation::Validate,
+            format!("invalid {} '{}'", name, value),
+        )
+    }
+
+    // symbol such as CAP_NET_RAW or AF_INET, optionally inverted with '~'
+    fn is_valid_symbol(symbol: &str, prefix: &str) -> bool {
+        let symbol = symbol.strip_prefix('~').unwrap_or(symbol);
+        match symbol.strip_prefix(prefix) {
+            Some(name) => {
+                !name.is_empty()
+                    && name
+                        .chars()
+                        .all(|c| c.is_ascii_uppercase() || c.is_ascii_digit() || c == '_')
+            }
+            None => false,
+        }
+    }
+}
+
+impl TryFrom<grpc::daemon::SecurityDescriptor> for SecurityDescriptor {
+    type Error = Error;
+
+    fn try_from(origin: grpc::daemon::SecurityDescriptor) -> Result<Self> {
+        let preset = match origin.preset {
+            Some(preset) => preset.parse()?,
+            None => SecurityPreset::default(),
+        };
+        let protect_system = origin.protect_system;
+        let protect_home = origin.protect_home;
+        let private_tmp = origin.private_tmp;
+        let no_new_privileges = origin.no_new_privileges;
+        let capability_bounding_set = origin
+            .capability_bounding_set
+            .map(|capabilities| capabilities.split_whitespace().map(String::from).collect());
+        let read_write_paths = origin.read_write_paths;
+        let restrict_address_families = origin
+            .restrict_address_families
+            .map(|families| families.split_whitespace().map(String::from).collect());
+        Ok(SecurityDescriptor {
+            preset,
+            protect_system,
+            protect_home,
+            private_tmp,
+            no_new_privileges,
+            capability_bounding_set,
+            read_write_paths,
+            restrict_address_families,
+        })
+    }
+}
+
+// trigger schedule, pipe runs by companion timer unit if defined
+#[derive(Clone, Default, Deserialize, Serialize)]
+pub struct ScheduleDescriptor {
+    // calendar event expression, such as "hourly" or "*-*-* 02:00:00"
+    pub on_calendar: Option<String>,
+    // time span after boot, such as "10min"
+    pub on_boot_sec: Option<String>,
+    // time span after last trigger, such as "1h"
+    pub on_unit_active_sec: Option<String>,
+}
+
+impl ScheduleDescriptor {
+    pub fn validate(&self) -> Result<()> {
+        if self.on_calendar.is_none()
+            && self.on_boot_sec.is_none()
+            && self.on_unit_active_sec.is_none()
+        {
+            return Err(pipe_error(
+                PipeOperation::Validate,
+                String::from("schedule defines neither calendar nor interval"),
+            ));
+        }
+        if let Some(on_calendar) = self.on_calendar.as_ref() {
+            if on_calendar.trim().is_empty() || on_calendar.contains(char::is_control) {
+                return Err(pipe_error(
+                    PipeOperation::Validate,
+                    format!("invalid schedule calendar '{}'", on_calendar),
+                ));
+            }
+        }
+        let spans = [
+            ("schedule boot interval", &self.on_boot_sec),
+            ("schedule active interval", &self.on_unit_active_sec),
+        ];
+        for (name, span) in spans {
+            match span {
+                Some(span) if !is_valid_time_span(span) => {
+                    return Err(pipe_error(
+                        PipeOperation::Validate,
+                        format!("invalid {} '{}'", name, span),
+                    ))
+                }
+                _ => continue,
+            }
+        }
+        Ok(())
+    }
+}
+
+impl From<grpc::daemon::ScheduleDescriptor> for ScheduleDescriptor {
+    fn from(origin: grpc::daemon::ScheduleDescriptor) -> Self {
+        ScheduleDescriptor {
+            on_calendar: origin.on_calendar,
+            on_boot_sec: origin.on_boot_sec,
+            on_unit_active_sec: origin.on_unit_active_sec,
+        }
+    }
+}
+
+#[derive(Clone, Default, Deserialize, Serialize, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum DependencyType {
+    // dependent stopped or fails to start if dependency fails
+    #[default]
+    Requires,
+    // dependency failure tolerated
+    Wants,
+}
+
+impl Display for DependencyType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let dependency_type = match self {
+            DependencyType::Requires => "requires",
+            DependencyType::Wants => "wants",
+        };
+        write!(f, "{}", dependency_type)
+    }
+}
+
+impl FromStr for DependencyType {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "requires" => Ok(DependencyType::Requires),
+            "wants" => Ok(DependencyType::Wants),
+            _ => Err(pipe_error(
+                PipeOperation::Validate,
+                format!("invalid dependency type '{}'", s),
+            )),
+        }
+    }
+}
+
+// dependency on another pipe, rendered as After= and Requires= or Wants=
+#[derive(Clone, Deserialize, Serialize, PartialEq)]
+pub struct PipeDependency {
+    pub id: String,
+    #[serde(default)]
+    pub dependency_type: DependencyType,
+}
+
+impl TryFrom<grpc::daemon::PipeDependency> for PipeDependency {
+    type Error = Error;
+
+    fn try_from(origin: grpc::daemon::PipeDependency) -> Result<Self> {
+        let dependency_type = match origin.dependency_type {
+            Some(dependency_type) => dependency_type.parse()?,
+            None => DependencyType::default(),
+        };
+        Ok(PipeDependency {
+            id: origin.id,
+            dependency_type,
+        })
+    }
+}
+
+#[derive(Clone)]
+pub struct PipeDescriptor<'a> {
+    // pipe id
+    pub id: String,
+    pub description: String,
+    pub user: String,
+    pub group: String,
+    pub envs: Vec<EnvironmentVariable>,
+    // path to environment files, optional if prefixed with '-'
+    pub env_files: Vec<String>,
+    pub restart: RestartDescriptor,
+    pub stop: StopDescriptor,
+    pub limits: LimitDescriptor,
+    pub security: SecurityDescriptor,
+    // enable unit to start at boot
+    pub autostart: bool,
+    pub depends_on: Vec<PipeDependency>,
+    pub schedule: Option<ScheduleDescriptor>,
+    pub service_type: ServiceType,
+    // oneshot pipe stays active after exit
+    pub remain_after_exit: Option<bool>,
+    // probed by daemon, restart after repeated liveness failures
+    pub liveness: Option<ProbeDescriptor>,
+    pub readiness: Option<ProbeDescriptor>,
+    pub app_path: &'a Path,
+    pub catalogs_path: &'a Path,
+}
+
+impl<'a> PartialEq for PipeDescriptor<'a> {
+    fn eq(&self, other: &Self) -> bool {
+        self.id == other.id
+    }
+}
+
+impl<'a> PipeDescriptor<'a> {
+    pub fn builder() -> PipeDescriptorBuilder<'a> {
+        PipeDescriptorBuilder::default()
+    }
+
+    // validate descriptor before any file created
+    pub fn validate(&self) -> Result<()> {
+        if !is_valid_pipe_id(self.id.as_str()) {
+            return Err(pipe_error(
+                PipeOperation::Validate,
+                format!(
+                    "invalid pipe id '{}', expect alphanumeric first character followed by alphanumeric, ':', '-', '_' or '.'",
+                    self.id
+                ),
+            ));
+        }
+        for env in self.envs.iter() {
+            if !env.is_valid_key() {
+                return Err(pipe_error(
+                    PipeOperation::Validate,
+                    format!("invalid environment variable name '{}'", env.key),
+                ));
+            }
+        }
+        for env_file in self.env_files.iter() {
+            let path = env_file.strip_prefix('-').unwrap_or(env_file);
+            if !Path::new(path).is_absolute() {
+                return Err(pipe_error(
+                    PipeOperation::Validate,
+                    format!("environment file '{}' is not an absolute path", env_file),
+                ));
+            }
+        }
+        let mut dependency_ids: Vec<&str> = vec![];
+        for dependency in self.depends_on.iter() {
+            let dependency_id = dependency.id.as_str();
+            if !is_valid_pipe_id(dependency_id) {
+                return Err(pipe_error(
+                    PipeOperation::Validate,
+                    format!("invalid dependency pipe id '{}'", dependency_id),
+                ));
+            }
+            if dependency_id == self.id {
+                return Err(pipe_error(
+                    PipeOperation::Validate,
+                    format!("pipe '{}' depends on itself", self.id),
+                ));
+            }
+            if dependency_ids.contains(&dependency_id) {
+                return Err(pipe_error(
+                    PipeOperation::Validate,
+                    format!("duplicate dependency pipe id '{}'", dependency_id),
+                ));
+            }
+            dependency_ids.push(dependency_id);
+        }
+        if let Some(schedule) = self.schedule.as_ref() {
+            schedule.validate()?;
+        }
+        let oneshot = matches!(self.service_type, ServiceType::Oneshot);
+        if !oneshot && self.remain_after_exit.is_some() {
+            return Err(pipe_error(
+                PipeOperation::Validate,
+                String::from("remain after exit requires oneshot service type"),
+            ));
+        }
+        if oneshot && matches!(self.restart.policy, RestartPolicy::Always) {
+            return Err(pipe_error(
+                PipeOperation::Validate,
+                String::from("restart policy 'always' not allowed for oneshot service type"),
+            ));
+        }
+        if let Some(liveness) = self.liveness.as_ref() {
+            liveness.validate("liveness")?;
+        }
+        if let Some(readiness) = self.readiness.as_ref() {
+            readiness.validate("readiness")?;
+        }
+        self.restart.validate()?;
+        self.stop.validate()?;
+        self.limits.validate()?;
+        self.security.validate()
+    }
+}
+
+pub struct PipeDescriptorBuilder<'a> {
+    pub id: Option<String>,
+    pub description: String,
+    pub user: String,
+    pub group: String,
+    pub envs: Vec<EnvironmentVariable>,
+    pub env_files: Vec<String>,
+    pub restart: RestartDescriptor,
+    pub stop: StopDescriptor,
+    pub limits: LimitDescriptor,
+    pub security: SecurityDescriptor,
+    pub autostart: bool,
+    pub depends_on: Vec<PipeDependency>,
+    pub schedule: Option<ScheduleDescriptor>,
+    pub service_type: ServiceType,
+    pub remain_after_exit: Option<bool>,
+    pub liveness: Option<ProbeDescriptor>,
+    pub readiness: Option<ProbeDescriptor>,
+    pub app_path: Option<&'a Path>,
+    pub catalogs_path: Option<&'a Path>,
+}
+
+impl<'a> Default for PipeDescriptorBuilder<'a> {
+    fn default() -> Self {
+        PipeDescriptorBuilder {
+            id: None,
+            description: String::from(SYSTEMD_DEFAULT_DESCRIPTION),
+            user: String::from(SYSTEMD_DEFAULT_USER),
+            group: String::from(SYSTEMD_DEFAULT_GROUP),
+            envs: vec![],
+            env_files: vec![],
+            restart: RestartDescriptor::default(),
+            stop: StopDescriptor::default(),
+            limits: LimitDescriptor::default(),
+            security: SecurityDescriptor::default(),
+            autostart: false,
+            depends_on: vec![],
+            schedule: None,
+            service_type: ServiceType::default(),
+            remain_after_exit: None,
+            liveness: None,
+            readiness: None,
+            app_path: None,
+            catalogs_path: None,
+        }
+    }
+}
+
+impl<'a> PipeDescriptorBuilder<'a> {
+    pub fn id(mut self, id: String) -> Self {
+        self.id = Some(id);
+        self
+    }
+
+    pub fn description(mut self, description: String) -> Self {
+        self.description = description;
+        self
+    }
+
+    pub fn user(mut self, user: String) -> Self {
+        self.user = user;
+        self
+    }
+
+    pub fn group(mut self, group: String) -> Self {
+        self.group = group;
+        self
+    }
+
+    pub fn env(mut self, env: EnvironmentVariable) -> Self {
+        self.envs.push(env);
+        self
+    }
+
+    pub fn envs(mut self, envs: Vec<EnvironmentVariable>) -> Self {
+        self.envs.extend(envs);
+        self
+    }
+
+    pub fn env_file(mut self, env_file: String) -> Self {
+        self.env_files.push(env_file);
+        self
+    }
+
+    pub fn restart(mut self, restart: RestartDescriptor) -> Self {
+        self.restart = restart;
+        self
+    }
+
+    pub fn stop(mut self, stop: StopDescriptor) -> Self {
+        self.stop = stop;
+        self
+    }
+
+    pub fn limits(mut self, limits: LimitDescriptor) -> Self {
+        self.limits = limits;
+        self
+    }
+
+    pub fn security(mut self, security: SecurityDescriptor) -> Self {
+        self.security = security;
+        self
+    }
+
+    pub fn autostart(mut self, autostart: bool) -> Self {
+        self.autostart = autostart;
+        self
+    }
+
+    pub fn depends_on(mut self, dependency: PipeDependency) -> Self {
+        self.depends_on.push(dependency);
+        self
+    }
+
+    pub fn schedule(mut self, schedule: ScheduleDescriptor) -> Self {
+        self.schedule = Some(schedule);
+        self
+    }
+
+    pub fn service_type(mut self, service_type: ServiceType) -> Self {
+        self.service_type = service_type;
+        self
+    }
+
+    pub fn remain_after_exit(mut self, remain_after_exit: bool) -> Self {
+        self.remain_after_exit = Some(remain_after_exit);
+        self
+    }
+
+    pub fn liveness(mut self, liveness: ProbeDescriptor) -> Self {
+        self.liveness = Some(liveness);
+        self
+    }
+
+    pub fn readiness(mut self, readiness: ProbeDescriptor) -> Self {
+        self.readiness = Some(readiness);
+        self
+    }
+
+    // options persisted in pipe record, defaults kept if undefined
+    pub fn options(mut self, options: PipeOptions) -> Self {
+        if let Some(description) = options.description {
+            self.description = description;
+        }
+        if let Some(user) = options.user {
+            self.user = user;
+        }
+        if let Some(group) = options.group {
+            self.group = group;
+        }
+        self.envs.extend(options.envs);
+        self.env_files.extend(options.env_files);
+        if let Some(restart) = options.restart {
+            self.restart = restart;
+        }
+        if let Some(stop) = options.stop {
+            self.stop = stop;
+        }
+        if let Some(limits) = options.limits {
+            self.limits = limits;
+        }
+        if let Some(security) = options.security {
+            self.security = security;
+        }
+        self.autostart = options.autostart;
+        self.depends_on.extend(options.depends_on);
+        if let Some(schedule) = options.schedule {
+            self.schedule = Some(schedule);
+        }
+        self.service_type = options.service_type;
+        if let Some(remain_after_exit) = options.remain_after_exit {
+            self.remain_after_exit = Some(remain_after_exit);
+        }
+        if let Some(liveness) = options.liveness {
+            self.liveness = Some(liveness);
+        }
+        if let Some(readiness) = options.readiness {
+            self.readiness = Some(readiness);
+        }
+        self
+    }
+
+    pub fn app_path(mut self, app_path: &'a Path) -> Self {
+        self.app_path = Some(app_path);
+        self
+    }
+
+    pub fn catalogs_path(mut self, catalogs_path: &'a Path) -> Self {
+        self.catalogs_path = Some(catalogs_path);
+        self
+    }
+
+    pub fn build(self) -> PipeDescriptor<'a> {
+        let id = self.id.expect("id undefined");
+        let description = self.description;
+        let user = self.user;
+        let group = self.group;
+        let envs = self.envs;
+        let env_files = self.env_files;
+        let restart = self.restart;
+        let stop = self.stop;
+        let limits = self.limits;
+        let security = self.security;
+        let autostart = self.autostart;
+        let depends_on = self.depends_on;
+        let schedule = self.schedule;
+        let service_type = self.service_type;
+        let remain_after_exit = self.remain_after_exit;
+        let liveness = self.liveness;
+        let readiness = self.readiness;
+        let app_path = self.app_path.expect("app path undefined");
+        let catalogs_path = self.catalogs_path.expect("catalogs path undefined");
+        PipeDescriptor {
+            id,
+            description,
+            user,
+            group,
+            envs,
+            env_files,
+            restart,
+            stop,
+            limits,
+            security,
+            autostart,
+            depends_on,
+            schedule,
+            service_type,
+            remain_after_exit,
+            liveness,
+            readiness,
+            app_path,
+            catalogs_path,
+        }
+    }
+}
+
+// pipe options given at creation
+#[derive(Clone, Default, Deserialize, Serialize)]
+pub struct PipeOptions {
+    pub description: Option<String>,
+    pub user: Option<String>,
+    pub group: Option<String>,
+    #[serde(default)]
+    pub envs: Vec<EnvironmentVariable>,
+    #[serde(default)]
+    pub env_files: Vec<String>,
+    pub restart: Option<RestartDescriptor>,
+    pub stop: Option<StopDescriptor>,
+    pub limits: Option<LimitDescriptor>,
+    pub security: Option<SecurityDescriptor>,
+    #[serde(default)]
+    pub autostart: bool,
+    #[serde(default)]
+    pub labels: HashMap<String, String>,
+    #[serde(default)]
+    pub depends_on: Vec<PipeDependency>,
+    pub schedule: Option<ScheduleDescriptor>,
+    #[serde(default)]
+    pub service_type: ServiceType,
+    pub remain_after_exit: Option<bool>,
+    pub liveness: Option<ProbeDescriptor>,
+    pub readiness: Option<ProbeDescriptor>,
+}
+
+// pipe record persisted in pipe register
+#[derive(Clone, Deserialize, Serialize)]
+pub struct PipeRecord {
+    pub id: String,
+    // undefined if migrated from id only register and unresolved
+    pub app: Option<AppDescriptor>,
+    pub catalogs: Option<CatalogsDescriptor>,
+    #[serde(default)]
+    pub options: PipeOptions,
+    // seconds since epoch
+    pub created_at: u64,
+    // increased on every update
+    pub revision: u64,
+    // systemd unit name, legacy '<id>.service' if undefined
+    #[serde(default)]
+    pub unit: Option<String>,
+}
+
+impl PipeRecord {
+    pub fn new(
+        id: String,
+        app: AppDescriptor,
+        catalogs: CatalogsDescriptor,
+        options: PipeOptions,
+    ) -> Self {
+        PipeRecord {
+            id,
+            app: Some(app),
+            catalogs: Some(catalogs),
+            options,
+            created_at: unix_timestamp_secs(),
+            revision: 1,
+            unit: None,
+        }
+    }
+
+    // record migrated from id only register
+    fn migrate(id: String) -> Self {
+        PipeRecord {
+            id,
+            app: None,
+            catalogs: None,
+            options: PipeOptions::default(),
+            created_at: unix_timestamp_secs(),
+            revision: 1,
+            unit: None,
+        }
+    }
+}
+
+#[derive(Deserialize, Serialize)]
+struct PipeRegister {
+    version: u32,
+    pipes: Vec<PipeRecord>,
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum VersionedPipeRegister {
+    // id only register
+    V0(Vec<String>),
+    V1(PipeRegister),
+}
+
+impl From<VersionedPipeRegister> for Vec<PipeRecord> {
+    fn from(origin: VersionedPipeRegister) -> Self {
+        match origin {
+            VersionedPipeRegister::V0(ids) => ids.into_iter().map(PipeRecord::migrate).collect(),
+            VersionedPipeRegister::V1(register) => register.pipes,
+        }
+    }
+}
+
+// snapshot of pipe unit configuration and catalogs link, used for rollback
+#[derive(PartialEq)]
+struct PipeRevision {
+    unit: Vec<u8>,
+    // timer unit of scheduled pipe
+    timer: Option<Vec<u8>>,
+    catalogs_path: PathBuf,
+}
+
+#[derive(Default)]
+pub struct PipeUnitNameBuilder<'a> {
+    prefix: Option<&'a str>,
+    id: Option<&'a str>,
+}
+
+impl<'a> PipeUnitNameBuilder<'a> {
+    pub fn prefix(mut self, prefix: &'a str) -> Self {
+        self.prefix = Some(prefix);
+        self
+    }
+
+    pub fn id(mut self, id: &'a str) -> Self {
+        self.id = Some(id);
+        self
+    }
+
+    pub fn build(self) -> String {
+        let prefix = self.prefix.unwrap_or_default();
+        let id = self.id.expect("pipe id undefined");
+        format!("{}{}{}", prefix, id, SYSTEMD_SERVICE_UNIT_SUFFIX)
+    }
+}
+
+// companion timer of pipe service unit, i.e 'pipebase-foo.service' -> 'pipebase-foo.timer'
+fn timer_unit_name(unit_name: &str) -> String {
+    let name = unit_name
+        .strip_suffix(SYSTEMD_SERVICE_UNIT_SUFFIX)
+        .unwrap_or(unit_name);
+    format!("{}{}", name, SYSTEMD_TIMER_UNIT_SUFFIX)
+}
+
+fn is_valid_unit_name_char(c: char) -> bool {
+    c.is_ascii_alphanumeric() || matches!(c, ':' | '-' | '_' | '.')
+}
+
+// pipe id is part of unit name and working directory, '@' and '\\' are reserved by systemd
+pub fn is_valid_pipe_id(id: &str) -> bool {
+    match id.chars().next() {
+        Some(first) if first.is_ascii_alphanumeric() => id.chars().all(is_valid_unit_name_char),
+        _ => false,
+    }
+}
+
+// order pipe ids so that dependencies come first, keep given order otherwise,
+// dependencies outside of given pipes ignored
+pub(crate) fn order_by_dependencies(pipes: &[(String, Vec<String>)]) -> Result<Vec<String>> {
+    let ids: Vec<&str> = pipes.iter().map(|(id, _)| id.as_str()).collect();
+    let mut ordered: Vec<String> = Vec::with_capacity(pipes.len());
+    let mut pending: Vec<&(String, Vec<String>)> = pipes.iter().collect();
+    while !pending.is_empty() {
+        let ready = pending.iter().position(|(_, dependencies)| {
+            dependencies.iter().all(|dependency| {
+                !ids.contains(&dependency.as_str()) || ordered.contains(dependency)
+            })
+        });
+        match ready {
+            Some(i) => ordered.push(pending.remove(i).0.to_owned()),
+            None => {
+                let cycle: Vec<&str> = pending.iter().map(|(id, _)| id.as_str()).collect();
+                return Err(pipe_error(
+                    PipeOperation::Validate,
+                    format!("dependency cycle among pipes '{}'", cycle.join("', '")),
+                ));
+            }
+        }
+    }
+    Ok(ordered)
+}
+
+#[derive(Deserialize)]
+pub struct PipeManagerConfig {
+    pub workspace: String,
+    pub unit_prefix: Option<String>,
+    pub slice: Option<String>,
+    // default job modes, overridden per request
+    pub start_mode: Option<JobMode>,
+    pub stop_mode: Option<JobMode>,
+}
+
+#[derive(Clone)]
+pub struct PipeManager {
+    pub workspace: PathBuf,
+    pub unit_prefix: String,
+    pub slice: String,
+    pub start_mode: JobMode,
+    pub stop_mode: JobMode,
+}
+
+pub struct PipeManagerBuilder {
+    workspace: Option<PathBuf>,
+    unit_prefix: String,
+    slice: String,
+    start_mode: JobMode,
+    stop_mode: JobMode,
+}
+
+impl Default for PipeManagerBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl PipeManagerBuilder {
+    pub fn new() -> Self {
+        PipeManagerBuilder {
+            workspace: None,
+            unit_prefix: String::from(PIPE_DEFAULT_UNIT_PREFIX),
+            slice: String::from(PIPE_DEFAULT_SLICE),
+            start_mode: JobMode::default(),
+            stop_mode: JobMode::default(),
+        }
+    }
+
+    pub fn workspace(mut self, workspace: String) -> Self {
+        self.workspace = Some(PathBuf::from(workspace));
+        self
+    }
+
+    pub fn unit_prefix(mut self, unit_prefix: String) -> Self {
+        self.unit_prefix = unit_prefix;
+        self
+    }
+
+    pub fn slice(mut self, slice: String) -> Self {
+        self.slice = slice;
+        self
+    }
+
+    pub fn start_mode(mut self, start_mode: JobMode) -> Self {
+        self.start_mode = start_mode;
+        self
+    }
+
+    pub fn stop_mode(mut self, stop_mode: JobMode) -> Self {
+        self.stop_mode = stop_mode;
+        self
+    }
+
+    pub fn build(self) -> PipeManager {
+        let workspace = self.workspace.expect("workspace undefined");
+        let workspace = canonicalize(workspace).expect("canonicalize workspace failed");
+        let unit_prefix = self.unit_prefix;
+        assert!(
+            unit_prefix.chars().all(is_valid_unit_name_char),
+            "invalid unit prefix '{}'",
+            unit_prefix
+        );
+        let slice = self.slice;
+        assert!(
+            slice
+                .strip_suffix(SYSTEMD_SLICE_UNIT_SUFFIX)
+                .is_some_and(is_valid_pipe_id),
+            "invalid slice '{}'",
+            slice
+        );
+        let start_mode = self.start_mode;
+        let stop_mode = self.stop_mode;
+        assert!(
+            !stop_mode.is_isolate(),
+            "invalid stop mode '{}', isolate only allowed for start",
+            stop_mode
+        );
+        PipeManager {
+            workspace,
+            unit_prefix,
+            slice,
+            start_mode,
+            stop_mode,
+        }
+    }
+}
+
+impl PipeManager {
+    pub fn builder() -> PipeManagerBuilder {
+        PipeManagerBuilder::default()
+    }
+
+    // init service configuration file and add pipe id into register
+    pub(crate) fn init(&self, desc: &PipeDescriptor<'_>, record: PipeRecord) -> Result<()> {
+        let mut lock_file = self.open_pipe_lock()?;
+        lock_file.lock()?;
+        let id = desc.id.as_str();
+        let registered = self.do_check_pipe_registered(id)?;
+        if registered {
+            return Err(pipe_error(
+                PipeOperation::Init,
+                format!("conflict pipe id '{}'", id),
+            ));
+        }
+        let unit_name = self.do_build_unit_name(id);
+        if unit_name.len() > SYSTEMD_UNIT_NAME_MAX_LENGTH {
+            return Err(pipe_error(
+                PipeOperation::Init,
+                format!(
+                    "pipe id '{}' too long, unit name '{}' exceeds {} characters",
+                    id, unit_name, SYSTEMD_UNIT_NAME_MAX_LENGTH
+                ),
+            ));
+        }
+        if Self::do_check_unit_exists(unit_name.as_str()) {
+            return Err(pipe_error(
+                PipeOperation::Init,
+                format!("unit '{}' of pipe '{}' already exists", unit_name, id),
+            ));
+        }
+        self.do_check_dependencies_registered(desc, PipeOperation::Init)?;
+        // working directory kept at removal, deleted manually after inspection
+        if self.do_get_working_directory(id).exists() {
+            return Err(pipe_error(
+                PipeOperation::Init,
+                format!("working directory of pipe '{}' already exists", id),
+            ));
+        }
+        // init working directory
+        let working_directory = self.do_create_working_directory(id)?;
+        // link catalogs
+        Self::do_link_catalogs(working_directory.as_path(), desc.catalogs_path)?;
+        Self::do_create_ownership(
+            desc.user.as_str(),
+            desc.group.as_str(),
+            working_directory.as_path(),
+        )?;
+        // create service configuration file
+        self.do_create_pipe_configuration_file(
+            desc,
+            unit_name.as_str(),
+            working_directory.as_path(),
+        )?;
+        if desc.autostart {
+            Self::do_enable_unit(
+                Self::do_get_desc_trigger_unit_name(desc, unit_name.as_str()).as_str(),
+            )?;
+        }
+        let mut record = record;
+        record.unit = Some(unit_name);
+        self.do_register_pipe(record)
+    }
+
+    // enable pipe unit to start at boot
+    pub(crate) fn enable(&self, id: &str) -> Result<()> {
+        let mut lock_file = self.open_pipe_lock()?;
+        lock_file.lock()?;
+        let registered = self.do_check_pipe_registered(id)?;
+        if !registered {
+            return Err(pipe_error(
+                PipeOperation::Enable,
+                format!("pipe '{}' not registered", id),
+            ));
+        }
+        let unit_name = self.do_get_trigger_unit_name(id)?;
+        Self::do_enable_unit(unit_name.as_str())?;
+        self.do_update_pipe_record(id, |record| record.options.autostart = true)
+    }
+
+    pub(crate) fn disable(&self, id: &str) -> Result<()> {
+        let mut lock_file = self.open_pipe_lock()?;
+        lock_file.lock()?;
+        let registered = self.do_check_pipe_registered(id)?;
+        if !registered {
+            return Err(pipe_error(
+                PipeOperation::Disable,
+                format!("pipe '{}' not registered", id),
+            ));
+        }
+        let unit_name = self.do_get_trigger_unit_name(id)?;
+        Self::do_disable_unit(unit_name.as_str())?;
+        self.do_update_pipe_record(id, |record| record.options.autostart = false)
+    }
+
+    // returns start job, enqueued with default start mode if mode undefined
+    pub(crate) fn start(
+        &self,
+        id: &str,
+        mode: Option<&JobMode>,
+    ) -> Result<zvariant::OwnedObjectPath> {
+        let mut lock_file = self.open_pipe_lock()?;
+        lock_file.lock()?;
+        let registered = self.do_check_pipe_registered(id)?;
+        if !registered {
+            return Err(pipe_error(
+                PipeOperation::Start,
+                format!("pipe '{}' not registered", id),
+            ));
+        }
+        // scheduled pipe runs once timer triggered
+        let unit_name = self.do_get_trigger_unit_name(id)?;
+        Self::do_start_unit(unit_name.as_str(), mode.unwrap_or(&self.start_mode))
+    }
+
+    // returns stop job of pipe unit, enqueued with default stop mode if mode undefined
+    pub(crate) fn stop(
+        &self,
+        id: &str,
+        mode: Option<&JobMode>,
+    ) -> Result<zvariant::OwnedObjectPath> {
+        let mut lock_file = self.open_pipe_lock()?;
+        lock_file.lock()?;
+        let registered = self.do_check_pipe_registered(id)?;
+        if !registered {
+            return Err(pipe_error(
+                PipeOperation::Stop,
+                format!("pipe '{}' not registered", id),
+            ));
+        }
+        let mode = mode.unwrap_or(&self.stop_mode);
+        if mode.is_isolate() {
+            return Err(pipe_error(
+                PipeOperation::Stop,
+                String::from("job mode 'isolate' only allowed for start"),
+            ));
+        }
+        let unit_name = self.do_get_unit_name(id)?;
+        // stop timer of scheduled pipe first so that no further run triggered
+        if self.do_is_scheduled(id)? {
+            Self::do_stop_unit(timer_unit_name(unit_name.as_str()).as_str(), mode)?;
+        }
+        Self::do_stop_unit(unit_name.as_str(), mode)
+    }
+
+    // exit of main process of stopped pipe
+    pub(crate) fn exit(&self, id: &str) -> Result<PipeExit> {
+        let mut lock_file = self.open_pipe_lock()?;
+        lock_file.lock()?;
+        let registered = self.do_check_pipe_registered(id)?;
+        if !registered {
+            return Err(pipe_error(
+                PipeOperation::Stop,
+                format!("pipe '{}' not registered", id),
+            ));
+        }
+        let unit_name = self.do_get_unit_name(id)?;
+        let unit_path = Self::do_load_unit(unit_name.as_str())?;
+        let service = build_blocking_service_proxy(unit_path)?;
+        Ok(PipeExit {
+            exit_code: service.exec_main_status()?,
+            exit_reason: exit_reason(service.exec_main_code()?),
+            result: service.result()?,
+        })
+    }
+
+    pub(crate) fn restart(&self, id: &str) -> Result<()> {
+        let mut lock_file = self.open_pipe_lock()?;
+        lock_file.lock()?;
+        let registered = self.do_check_pipe_registered(id)?;
+        if !registered {
+            return Err(pipe_error(
+                PipeOperation::Restart,
+                format!("pipe '{}' not registered", id),
+            ));
+        }
+        let unit_name = self.do_get_unit_name(id)?;
+        if !self.do_is_legacy_unit(id)? {
+            return Self::do_restart_unit(unit_name.as_str()).map(|_| ());
+        }
+        // pipe goes down anyway, move legacy unit to namespaced one
+        Self::do_stop_unit(unit_name.as_str(), &self.stop_mode)?;
+        let timeout = Duration::from_secs(PIPE_DEFAULT_UPDATE_TIMEOUT_SECS);
+        self.do_wait_inactive(id, PipeOperation::Migrate, timeout)?;
+        let unit_name = match self.do_migrate_unit(id) {
+            Ok(unit_name) => unit_name,
+            Err(err) => {
+                // keep legacy unit running
+                warn!(pipe_id = id, "migrate pipe unit failed, error: {:#?}", err);
+                unit_name
+            }
+        };
+        Self::do_start_unit(unit_name.as_str(), &self.start_mode)?;
+        Ok(())
+    }
+
+    // reload pipe if supported, restart otherwise
+    pub(crate) fn reload(&self, id: &str) -> Result<()> {
+        let mut lock_file = self.open_pipe_lock()?;
+        lock_file.lock()?;
+        let registered = self.do_check_pipe_registered(id)?;
+        if !registered {
+            return Err(pipe_error(
+                PipeOperation::Reload,
+                format!("pipe '{}' not registered", id),
+            ));
+        }
+        let unit_name = self.do_get_unit_name(id)?;
+        Self::do_reload_or_restart_unit(unit_name.as_str())
+    }
+
+    pub(crate) fn kill(&self, id: &str, whom: &KillWhom, signal: i32) -> Result<()> {
+        let mut lock_file = self.open_pipe_lock()?;
+        lock_file.lock()?;
+        let registered = self.do_check_pipe_registered(id)?;
+        if !registered {
+            return Err(pipe_error(
+                PipeOperation::Kill,
+                format!("pipe '{}' not registered", id),
+            ));
+        }
+        let unit_name = self.do_get_unit_name(id)?;
+        Self::do_kill_unit(unit_name.as_str(), whom, signal)
+    }
+
+    pub(crate) fn inspect(&self, id: &str) -> Result<PipeInspection> {
+        let mut lock_file = self.open_pipe_lock()?;
+        lock_file.lock()?;
+        let registered = self.do_check_pipe_registered(id)?;
+        if !registered {
+            return Err(pipe_error(
+                PipeOperation::Inspect,
+                format!("pipe '{}' not registered", id),
+            ));
+        }
+        let record = self.do_get_pipe_record(id)?;
+        let mut inspection = self.do_inspect(id)?;
+        if let Some(record) = record {
+            inspection.app = record.app;
+            inspection.catalogs = record.catalogs;
+            inspection.state.labels = record.options.labels;
+        }
+        Ok(inspection)
+    }
+
+    pub(crate) fn stats(&self, id: &str) -> Result<PipeStats> {
+        let mut lock_file = self.open_pipe_lock()?;
+        lock_file.lock()?;
+        let registered = self.do_check_pipe_registered(id)?;
+        if !registered {
+            return Err(pipe_error(
+                PipeOperation::Stats,
+                format!("pipe '{}' not registered", id),
+            ));
+        }
+        let unit_name = self.do_get_unit_name(id)?;
+        let unit_path = Self::do_load_unit(unit_name.as_str())?;
+        let service = build_blocking_service_proxy(unit_path)?;
+        PipeStats::sample(id, &service)
+    }
+
+    pub(crate) fn logs(&self, id: &str, query: &PipeLogQuery) -> Result<PipeLogReceiver> {
+        let mut lock_file = self.open_pipe_lock()?;
+        lock_file.lock()?;
+        let registered = self.do_check_pipe_registered(id)?;
+        if !registered {
+            return Err(pipe_error(
+                PipeOperation::Logs,
+                format!("pipe '{}' not registered", id),
+            ));
+        }
+        let unit_name = self.do_get_unit_name(id)?;
+        stream_journal(unit_name.as_str(), query)
+    }
+
+    pub(crate) fn status(&self, id: &str) -> Result<PipeState> {
+        let mut lock_file = self.open_pipe_lock()?;
+        lock_file.lock()?;
+        let registered = self.do_check_pipe_registered(id)?;
+        if !registered {
+            return Err(pipe_error(
+                PipeOperation::Status,
+                format!("pipe '{}' not registered", id),
+            ));
+        }
+        let mut state = self.do_status(id)?;
+        if let Some(record) = self.do_get_pipe_record(id)? {
+            state.labels = record.options.labels;
+        }
+        Ok(state)
+    }
+
+    // swap app and / or catalogs in pipe record and regenerate unit from it, then restart,
+    // rollback to previous revision if pipe is not running within timeout,
+    // scheduled pipe is restarted only if active, otherwise next run is left to its timer
+    pub(crate) async fn update(
+        &self,
+        id: &str,
+        app: Option<(&AppDescriptor, &Path)>,
+        catalogs: Option<(&CatalogsDescriptor, &Path)>,
+        timeout: Duration,
+    ) -> Result<()> {
+        let mut lock_file = self.open_pipe_lock()?;
+        lock_file.lock()?;
+        let mut record = match self.do_get_pipe_record(id)? {
+            Some(record) => record,
+            None => {
+                return Err(pipe_error(
+                    PipeOperation::Update,
+                    format!("pipe '{}' not registered", id),
+                ))
+            }
+        };
+        let app_path = match app {
+            Some((app, app_path)) => {
+                record.app = Some(app.to_owned());
+                app_path.to_owned()
+            }
+            None => match self.do_get_app_path(id)? {
+                Some(app_path) => app_path,
+                None => {
+                    return Err(pipe_error(
+                        PipeOperation::Update,
+                        format!("app of pipe '{}' unresolved", id),
+                    ))
+                }
+            },
+        };
+        let catalogs_path = match catalogs {
+            Some((catalogs, catalogs_path)) => {
+                record.catalogs = Some(catalogs.to_owned());
+                catalogs_path.to_owned()
+            }
+            None => {
+                let working_directory = self.do_get_working_directory(id);
+                read_link(Self::do_get_catalogs_link_path(working_directory.as_path()).as_path())?
+            }
+        };
+        let scheduled = record.options.schedule.is_some();
+        let desc =
+            Self::do_build_record_descriptor(&record, app_path.as_path(), catalogs_path.as_path());
+        self.do_reconfigure(&mut lock_file, &desc, record, !scheduled, timeout)
+            .await?;
+        info!(pipe_id = id, "pipe updated");
+        Ok(())
+    }
+
+    // rewrite unit configuration with new descriptor and replace pipe record, restart if active
+    // and rollback if pipe not running within timeout
+    pub(crate) async fn reconfigure(
+        &self,
+        desc: &PipeDescriptor<'_>,
+        record: PipeRecord,
+        timeout: Duration,
+    ) -> Result<()> {
+        let mut lock_file = self.open_pipe_lock()?;
+        lock_file.lock()?;
+        let id = desc.id.as_str();
+        let registered = self.do_check_pipe_registered(id)?;
+        if !registered {
+            return Err(pipe_error(
+                PipeOperation::Update,
+                format!("pipe '{}' not registered", id),
+            ));
+        }
+        self.do_check_dependencies_registered(desc, PipeOperation::Update)?;
+        self.do_reconfigure(&mut lock_file, desc, record, false, timeout)
+            .await?;
+        info!(pipe_id = id, "pipe reconfigured");
+        Ok(())
+    }
+
+    // apply revision of descriptor and replace app, catalogs and options of pipe record,
+    // restart if forced or active and revision changed, rollback to previous revision on failure
+    async fn do_reconfigure(
+        &self,
+        lock_file: &mut LockFile,
+        desc: &PipeDescriptor<'_>,
+        record: PipeRecord,
+        force_restart: bool,
+        timeout: Duration,
+    ) -> Result<()> {
+        let id = desc.id.as_str();
+        let state = self.do_status(id)?;
+        let was_active = state.is_active();
+        let working_directory = self.do_get_working_directory(id);
+        let revision = self.do_snapshot_revision(id, working_directory.as_path())?;
+        let unit_name = self.do_get_unit_name(id)?;
+        // schedule added or removed, move enablement to new trigger unit
+        let previous_trigger_unit_name = self.do_get_trigger_unit_name(id)?;
+        let trigger_unit_name = Self::do_get_desc_trigger_unit_name(desc, unit_name.as_str());
+        let trigger_changed = previous_trigger_unit_name != trigger_unit_name;
+        // timer of scheduled pipe stopped at trigger change, started again at rollback
+        let previous_timer_active = trigger_changed
+            && previous_trigger_unit_name != unit_name
+            && !matches!(
+                Self::do_get_active_state(previous_trigger_unit_name.as_str())?,
+                PipeActiveStateType::Inactive
+            );
+        let reconfigured = self
+            .do_apply_reconfigure(lock_file, desc, &state, &revision, force_restart, timeout)
+            .await;
+        if let Err(err) = reconfigured {
+            warn!(pipe_id = id, "reconfigure pipe failed, rollback ...");
+            self.do_rollback_revision_logged(id, working_directory.as_path(), &revision);
+            let mut restored = vec![];
+            match (was_active, force_restart) {
+                (true, _) => restored.push(Self::do_restart_unit(unit_name.as_str()).map(|_| ())),
+                (false, true) => restored
+                    .push(Self::do_stop_unit(unit_name.as_str(), &self.stop_mode).map(|_| ())),
+                (false, false) => (),
+            };
+            if trigger_changed && state.enabled {
+                restored.push(Self::do_enable_unit(previous_trigger_unit_name.as_str()));
+            }
+            if previous_timer_active {
+                restored.push(
+                    Self::do_start_unit(previous_trigger_unit_name.as_str(), &self.start_mode)
+                        .map(|_| ()),
+                );
+            }
+            for restore_err in restored.into_iter().filter_map(|restored| restored.err()) {
+                warn!(
+                    pipe_id = id,
+                    "restore pipe after rollback failed, error: {:#?}", restore_err
+                );
+            }
+            return Err(err);
+        }
+        self.do_update_pipe_record(id, |current| {
+            current.app = record.app.to_owned();
+            current.catalogs = record.catalogs.to_owned();
+            current.options = record.options.to_owned();
+            current.revision += 1;
+        })
+    }
+
+    // move enablement to trigger unit of descriptor and rewrite unit configuration,
+    // restart if required and error if pipe not running within timeout
+    async fn do_apply_reconfigure(
+        &self,
+        lock_file: &mut LockFile,
+        desc: &PipeDescriptor<'_>,
+        state: &PipeState,
+        revision: &PipeRevision,
+        force_restart: bool,
+        timeout: Duration,
+    ) -> Result<()> {
+        let id = desc.id.as_str();
+        let working_directory = self.do_get_working_directory(id);
+        let unit_name = self.do_get_unit_name(id)?;
+        let previous_trigger_unit_name = self.do_get_trigger_unit_name(id)?;
+        let trigger_unit_name = Self::do_get_desc_trigger_unit_name(desc, unit_name.as_str());
+        let mut enabled = state.enabled;
+        if previous_trigger_unit_name != trigger_unit_name {
+            if enabled {
+                Self::do_disable_unit(previous_trigger_unit_name.as_str())?;
+                enabled = false;
+            }
+            if previous_trigger_unit_name != unit_name {
+                Self::do_stop_unit(previous_trigger_unit_name.as_str(), &self.stop_mode)?;
+            }
+        }
+        self.do_reconfigure_revision(desc, unit_name.as_str(), working_directory.as_path())?;
+        // labels, autostart and probes are not part of revision, no restart if only those changed
+        let changed = self.do_snapshot_revision(id, working_directory.as_path())? != *revision;
+        let restart = force_restart || (changed && state.is_active());
+        match desc.service_type {
+            _ if !restart => (),
+            // oneshot pipe without remain after exit never stays running, judged by restart job
+            ServiceType::Oneshot => {
+                self.do_restart_wait_done(lock_file, id, unit_name.as_str(), timeout)
+                    .await?
+            }
+            _ => {
+                Self::do_restart_unit(unit_name.as_str())?;
+                if !self.do_wait_running(lock_file, id, timeout).await? {
+                    return Err(pipe_error(
+                        PipeOperation::Update,
+                        format!(
+                            "pipe '{}' not running within {:?}, rollback to previous revision",
+                            id, timeout
+                        ),
+                    ));
+                }
+            }
+        };
+        match (enabled, desc.autostart) {
+            (false, true) => Self::do_enable_unit(trigger_unit_name.as_str())?,
+            (true, false) => Self::do_disable_unit(trigger_unit_name.as_str())?,
+            _ => (),
+        };
+        Ok(())
+    }
+
+    // delete service configuration file and remove pipe id from register,
+    // refuse if other pipes depend on it unless forced
+    // reset failed state first if forced or purge failed, keep working directory if keep data
+    pub(crate) fn remove(
+        &self,
+        id: &str,
+        force: bool,
+        keep_data: bool,
+        purge_failed: bool,
+    ) -> Result<()> {
+        let mut lock_file = self.open_pipe_lock()?;
+        lock_file.lock()?;
+        let registered = self.do_check_pipe_registered(id)?;
+        if !registered {
+            warn!("pipe '{}' not registered", id);
+            return Ok(());
+        }
+        let dependents = self.do_list_dependents(id)?;
+        if !dependents.is_empty() {
+            if !force {
+                return Err(pipe_error(
+                    PipeOperation::Remove,
+                    format!(
+                        "pipe '{}' is depended on by '{}'",
+                        id,
+                        dependents.join("', '")
+                    ),
+                ));
+            }
+            warn!(
+                pipe_id = id,
+                "force remove pipe depended on by '{}'",
+                dependents.join("', '")
+            );
+        }
+        let scheduled = self.do_is_scheduled(id)?;
+        if force || purge_failed {
+            let unit_name = self.do_get_unit_name(id)?;
+            Self::do_reset_failed_unit(unit_name.as_str())?;
+            if scheduled {
+                Self::do_reset_failed_unit(timer_unit_name(unit_name.as_str()).as_str())?;
+            }
+        }
+        let state = self.do_status(id)?;
+        // before pipe deletion, the process should be stopped first
+        if !state.is_inactive() {
+            return Err(pipe_error(
+                PipeOperation::Remove,
+                format!("pipe '{}' is not inactive", id),
+            ));
+        }
+        if !state.is_dead() {
+            return Err(pipe_error(
+                PipeOperation::Remove,
+                format!("pipe '{}' is not dead", id),
+            ));
+        }
+        let trigger_unit_name = self.do_get_trigger_unit_name(id)?;
+        if scheduled
+            && !matches!(
+                Self::do_get_active_state(trigger_unit_name.as_str())?,
+                PipeActiveStateType::Inactive
+            )
+        {
+            return Err(pipe_error(
+                PipeOperation::Remove,
+                format!("timer of pipe '{}' is not inactive", id),
+            ));
+        }
+        // remove install symlinks before unit file deleted
+        if state.enabled {
+            Self::do_disable_unit(trigger_unit_name.as_str())?;
+        }
+        self.do_delete_pipe_configuration_file(id)?;
+        match keep_data {
+            true => {
+                self.do_mark_working_directory_kept(id)?;
+                info!(pipe_id = id, "keep working directory of removed pipe");
+            }
+            false => self.do_delete_working_directory(id)?,
+        };
+        self.do_deregister_pipe(id)?;
+        Ok(())
+    }
+
+    pub(crate) fn list_pipe_register(&self) -> Result<Vec<String>> {
+        let mut lock_file = self.open_pipe_lock()?;
+        lock_file.lock()?;
+        let records = self.do_read_pipe_register()?;
+        Ok(records.into_iter().map(|record| record.id).collect())
+    }
+
+    pub(crate) fn list_pipe_records(&self) -> Result<Vec<PipeRecord>> {
+        let mut lock_file = self.open_pipe_lock()?;
+        lock_file.lock()?;
         self.do_read_pipe_register()
     }
 
-    fn do_create_working_directory(&self, id: &str) -> Result<PathBuf> {
-        let working_directory = PathBuilder::default()
+    pub(crate) fn get_pipe_record(&self, id: &str) -> Result<PipeRecord> {
+        let mut lock_file = self.open_pipe_lock()?;
+        lock_file.lock()?;
+        match self.do_get_pipe_record(id)? {
+            Some(record) => Ok(record),
+            None => Err(pipe_error(
+                PipeOperation::Load,
+                format!("pipe '{}' not registered", id),
+            )),
+        }
+    }
+
+    // rewrite register in latest version, resolve app and catalogs of records migrated
+    // from id only register with current unit configuration
+    pub(crate) fn migrate<A, C>(&self, resolve_app: A, resolve_catalogs: C) -> Result<()>
+    where
+        A: Fn(&Path) -> Option<AppDescriptor>,
+        C: Fn(&Path) -> Option<CatalogsDescriptor>,
+    {
+        let mut lock_file = self.open_pipe_lock()?;
+        lock_file.lock()?;
+        let mut records = self.do_read_pipe_register()?;
+        for record in records.iter_mut() {
+            if record.app.is_some() && record.catalogs.is_some() {
+                continue;
+            }
+            let id = record.id.as_str();
+            let working_directory = self.do_get_working_directory(id);
+            if record.app.is_none() {
+                match self.do_get_app_path(id) {
+                    Ok(app_path) => {
+                        record.app = app_path.as_deref().and_then(&resolve_app);
+                    }
+                    Err(err) => {
+                        warn!(pipe_id = id, "resolve app failed, error: {:#?}", err);
+                    }
+                }
+            }
+            if record.catalogs.is_none() {
+                let catalogs_link_path =
+                    Self::do_get_catalogs_link_path(working_directory.as_path());
+                record.catalogs = read_link(catalogs_link_path.as_path())
+                    .ok()
+                    .as_deref()
+                    .and_then(&resolve_catalogs);
+            }
+        }
+        self.do_write_pipe_register(records)?;
+        self.do_migrate_legacy_units()
+    }
+
+    // append completed run of oneshot pipe to run history
+    pub(crate) fn record_run(&self, id: &str) -> Result<()> {
+        let mut lock_file = self.open_pipe_lock()?;
+        lock_file.lock()?;
+        let oneshot = self
+            .do_get_pipe_record(id)?
+            .is_some_and(|record| matches!(record.options.service_type, ServiceType::Oneshot));
+        if !oneshot {
+            return Ok(());
+        }
+        let unit_name = self.do_get_unit_name(id)?;
+        let unit_path = Self::do_load_unit(unit_name.as_str())?;
+        let service = build_blocking_service_proxy(unit_path)?;
+        let state = self.do_status(id)?;
+        let run = PipeRun {
+            started_at: service.exec_main_start_timestamp()?,
+            ended_at: service.exec_main_exit_timestamp()?,
+            exit_code: service.exec_main_status()?,
+            exit_reason: exit_reason(service.exec_main_code()?),
+            active_state: state.active_state.to_string(),
+            sub_state: state.sub_state.to_string(),
+        };
+        let path = self.do_get_run_history_path(id);
+        let mut history = Self::do_read_run_history(path.as_path())?;
+        history.push(run, PIPE_RUN_HISTORY_CAPACITY);
+        write_yml(path, &history)
+    }
+
+    // runs of oneshot pipe, oldest first
+    pub(crate) fn list_runs(&self, id: &str) -> Result<Vec<PipeRun>> {
+        let mut lock_file = self.open_pipe_lock()?;
+        lock_file.lock()?;
+        let registered = self.do_check_pipe_registered(id)?;
+        if !registered {
+            return Err(pipe_error(
+                PipeOperation::Load,
+                format!("pipe '{}' not registered", id),
+            ));
+        }
+        let path = self.do_get_run_history_path(id);
+        Ok(Self::do_read_run_history(path.as_path())?.runs)
+    }
+
+    // (pipe id, unit name) of registered pipes
+    pub(crate) fn list_pipe_units(&self) -> Result<Vec<(String, String)>> {
+        let mut lock_file = self.open_pipe_lock()?;
+        lock_file.lock()?;
+        let ids: Vec<String> = self
+            .do_read_pipe_register()?
+            .into_iter()
+            .map(|record| record.id)
+            .collect();
+        let mut units: Vec<(String, String)> = vec![];
+        for id in ids {
+            let unit_name = self.do_get_unit_name(id.as_str())?;
+            units.push((id, unit_name));
+        }
+        Ok(units)
+    }
+
+    // modified time of pipe register, undefined if not created yet
+    pub(crate) fn pipe_register_modified(&self) -> Result<Option<SystemTime>> {
+        let register_file_path = PathBuilder::default()
+            .push(self.workspace.as_path())
+            .push(PATH_PIPE_REGISTER)
+            .build();
+        if !register_file_path.as_path().exists() {
+            return Ok(None);
+        }
+        Ok(Some(register_file_path.metadata()?.modified()?))
+    }
+
+    // drifts between pipe register, working directories, catalogs links and unit files,
+    // repair by recreating missing files from records, orphans removed only if purge
+    pub(crate) fn diagnose<A, C>(
+        &self,
+        repair: bool,
+        purge: bool,
+        resolve_app: A,
+        resolve_catalogs: C,
+    ) -> Result<Vec<Drift>>
+    where
+        A: Fn(&AppDescriptor) -> Option<PathBuf>,
+        C: Fn(&CatalogsDescriptor) -> Option<PathBuf>,
+    {
+        let mut lock_file = self.open_pipe_lock()?;
+        lock_file.lock()?;
+        let records = self.do_read_pipe_register()?;
+        let mut drifts: Vec<Drift> = vec![];
+        let mut unit_names: Vec<String> = vec![];
+        for record in records.iter() {
+            let id = record.id.as_str();
+            let catalogs_path = record.catalogs.as_ref().and_then(&resolve_catalogs);
+            let working_directory = self.do_get_working_directory(id);
+            let catalogs_link_path = Self::do_get_catalogs_link_path(working_directory.as_path());
+            if !working_directory.as_path().is_dir() {
+                let mut drift = Drift::new(
+                    DriftType::MissingWorkingDirectory,
+                    id.to_owned(),
+                    format!(
+                        "working directory '{}' not found",
+                        working_directory.display()
+                    ),
+                );
+                if repair {
+                    drift.repair(|| {
+                        self.do_recreate_working_directory(record, catalogs_path.as_deref())
+                    });
+                }
+                drifts.push(drift);
+            } else if !catalogs_link_path.as_path().exists() {
+                let mut drift = Drift::new(
+                    DriftType::DanglingCatalogsLink,
+                    id.to_owned(),
+                    format!(
+                        "catalogs link '{}' missing or dangling",
+                        catalogs_link_path.display()
+                    ),
+                );
+                if repair {
+                    drift.repair(|| match catalogs_path.as_deref() {
+                        Some(catalogs_path) => {
+                            Self::do_relink_catalogs(working_directory.as_path(), catalogs_path)
+                        }
+                        None => Err(pipe_error(
+                            PipeOperation::Repair,
+                            format!("catalogs of pipe '{}' unresolved", id),
+                        )),
+                    });
+                }
+                drifts.push(drift);
+            }
+            let unit_name = self.do_get_unit_name(id)?;
+            let unit_file_path = PathBuilder::default()
+                .push(SYSTEMD_UNIT_CONFIGURATION_DIRECTORY)
+                .push(unit_name.as_str())
+                .build();
+            if !unit_file_path.as_path().exists() {
+                let mut drift = Drift::new(
+                    DriftType::MissingUnitFile,
+                    id.to_owned(),
+                    format!("unit file '{}' not found", unit_file_path.display()),
+                );
+                if repair {
+                    let app_path = record.app.as_ref().and_then(&resolve_app);
+                    drift.repair(|| {
+                        self.do_recreate_pipe_configuration_file(
+                            record,
+                            unit_name.as_str(),
+                            app_path.as_deref(),
+                            catalogs_path.as_deref(),
+                        )
+                    });
+                }
+                drifts.push(drift);
+            }
+            if record.options.schedule.is_some() {
+                unit_names.push(timer_unit_name(unit_name.as_str()));
+            }
+            unit_names.push(unit_name);
+        }
+        for entry in read_dir(self.workspace.as_path())? {
+            let path = entry?.path();
+            if !path.as_path().is_dir() {
+                continue;
+            }
+            let id = match path.file_name().and_then(|name| name.to_str()) {
+                Some(id) => id,
+                None => continue,
+            };
+            if records.iter().any(|record| record.id == id) {
+                continue;
+            }
+            // kept at removal for inspection
+            let kept_path = PathBuilder::default()
+                .push(path.as_path())
+                .push(PATH_PIPE_KEPT)
+                .build();
+            if kept_path.exists() {
+                continue;
+            }
+            let mut drift = Drift::new(
+                DriftType::OrphanWorkingDirectory,
+                id.to_owned(),
+                format!("working directory '{}' not registered", path.display()),
+            );
+            // pipe data deleted only if purge explicitly
+            if repair && purge {
+                drift.repair(|| remove_directory(path.as_path()));
+            }
+            drifts.push(drift);
+        }
+        // units without prefix are not owned by pipe manager
+        if self.unit_prefix.is_empty() {
+            return Ok(drifts);
+        }
+        for entry in read_dir(SYSTEMD_UNIT_CONFIGURATION_DIRECTORY)? {
+            let unit_name = entry?.file_name().to_string_lossy().into_owned();
+            // service and timer units of pipes
+            let is_pipe_unit = unit_name.ends_with(SYSTEMD_SERVICE_UNIT_SUFFIX)
+                || unit_name.ends_with(SYSTEMD_TIMER_UNIT_SUFFIX);
+            if !unit_name.starts_with(self.unit_prefix.as_str())
+                || !is_pipe_unit
+                || unit_names.contains(&unit_name)
+            {
+                continue;
+            }
+            // unit files without pipe id are not generated by pipe manager
+            let pipe_id = match Self::do_read_unit_pipe_id(unit_name.as_str())? {
+                Some(pipe_id) => pipe_id,
+                None => continue,
+            };
+            let mut drift = Drift::new(
+                DriftType::OrphanUnitFile,
+                unit_name.to_owned(),
+                format!(
+                    "unit file '{}' of pipe '{}' not registered",
+                    unit_name, pipe_id
+                ),
+            );
+            // unit files deleted only if purge explicitly
+            if repair && purge {
+                drift.repair(|| Self::do_delete_orphan_unit(unit_name.as_str()));
+            }
+            drifts.push(drift);
+        }
+        Ok(drifts)
+    }
+
+    // migrate inactive pipes to namespaced units, active pipes are migrated on next restart
+    fn do_migrate_legacy_units(&self) -> Result<()> {
+        let records = self.do_read_pipe_register()?;
+        for record in records.iter().filter(|record| record.unit.is_none()) {
+            let id = record.id.as_str();
+            let state = match self.do_status(id) {
+                Ok(state) => state,
+                Err(err) => {
+                    warn!(pipe_id = id, "get pipe status failed, error: {:#?}", err);
+                    continue;
+                }
+            };
+            if !state.is_inactive() && !state.is_failed() {
+                warn!(
+                    pipe_id = id,
+                    "pipe runs in legacy unit, migrate on next restart"
+                );
+                continue;
+            }
+            if let Err(err) = self.do_migrate_unit(id) {
+                warn!(pipe_id = id, "migrate pipe unit failed, error: {:#?}", err);
+            }
+        }
+        Ok(())
+    }
+
+    fn do_check_dependencies_registered(
+        &self,
+        desc: &PipeDescriptor<'_>,
+        op: PipeOperation,
+    ) -> Result<()> {
+        for dependency in desc.depends_on.iter() {
+            if !self.do_check_pipe_registered(dependency.id.as_str())? {
+                return Err(pipe_error(
+                    op,
+                    format!(
+                        "dependency '{}' of pipe '{}' not registered",
+                        dependency.id, desc.id
+                    ),
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    // ids of registered pipes depending on pipe
+    fn do_list_dependents(&self, id: &str) -> Result<Vec<String>> {
+        let dependents = self
+            .do_read_pipe_register()?
+            .into_iter()
+            .filter(|record| {
+                record
+                    .options
+                    .depends_on
+                    .iter()
+                    .any(|dependency| dependency.id == id)
+            })
+            .map(|record| record.id)
+            .collect();
+        Ok(dependents)
+    }
+
+    fn do_get_working_directory(&self, id: &str) -> PathBuf {
+        PathBuilder::default()
             .push(self.workspace.as_path())
             .push(id)
-            .build();
+            .build()
+    }
+
+    fn do_get_run_history_path(&self, id: &str) -> PathBuf {
+        PathBuilder::default()
+            .push(self.do_get_working_directory(id))
+            .push(PATH_PIPE_RUNS)
+            .build()
+    }
+
+    // empty history if no run recorded
+    fn do_read_run_history(path: &Path) -> Result<PipeRunHistory> {
+        if !path.exists() {
+            return Ok(PipeRunHistory::default());
+        }
+        read_yml(path)
+    }
+
+    fn do_create_working_directory(&self, id: &str) -> Result<PathBuf> {
+        let working_directory = self.do_get_working_directory(id);
         create_directory(working_directory.as_path())?;
         Ok(working_directory)
     }
 
     fn do_delete_working_directory(&self, id: &str) -> Result<()> {
-        let working_directory = PathBuilder::default()
-            .push(self.workspace.as_path())
-            .push(id)
-            .build();
+        let working_directory = self.do_get_working_directory(id);
         remove_directory(working_directory.as_path())
     }
 
-    fn do_link_catalogs(working_directory: &Path, catalogs_path: &Path) -> Result<()> {
-        let catalogs_link_path = PathBuilder::default()
+    // keep record of removed pipe so that kept working directory is not an orphan
+    fn do_mark_working_directory_kept(&self, id: &str) -> Result<()> {
+        let record = self.do_get_pipe_record(id)?;
+        let kept_path = PathBuilder::default()
+            .push(self.do_get_working_directory(id))
+            .push(PATH_PIPE_KEPT)
+            .build();
+        write_yml(kept_path.as_path(), &record)
+    }
+
+    fn do_get_catalogs_link_path(working_directory: &Path) -> PathBuf {
+        PathBuilder::default()
             .push(working_directory)
             .push(PATH_CATALOGS)
-            .build();
+            .build()
+    }
+
+    // working directory with catalogs link and ownership as created at init
+    fn do_recreate_working_directory(
+        &self,
+        record: &PipeRecord,
+        catalogs_path: Option<&Path>,
+    ) -> Result<()> {
+        let id = record.id.as_str();
+        let catalogs_path = match catalogs_path {
+            Some(catalogs_path) => catalogs_path,
+            None => {
+                return Err(pipe_error(
+                    PipeOperation::Repair,
+                    format!("catalogs of pipe '{}' unresolved", id),
+                ))
+            }
+        };
+        let working_directory = self.do_create_working_directory(id)?;
+        Self::do_link_catalogs(working_directory.as_path(), catalogs_path)?;
+        let options = &record.options;
+        Self::do_create_ownership(
+            options.user.as_deref().unwrap_or(SYSTEMD_DEFAULT_USER),
+            options.group.as_deref().unwrap_or(SYSTEMD_DEFAULT_GROUP),
+            working_directory.as_path(),
+        )
+    }
+
+    fn do_link_catalogs(working_directory: &Path, catalogs_path: &Path) -> Result<()> {
+        let catalogs_link_path = Self::do_get_catalogs_link_path(working_directory);
+        link(catalogs_path, catalogs_link_path.as_path(), true)
+    }
+
+    fn do_relink_catalogs(working_directory: &Path, catalogs_path: &Path) -> Result<()> {
+        let catalogs_link_path = Self::do_get_catalogs_link_path(working_directory);
+        if catalogs_link_path.as_path().symlink_metadata().is_ok() {
+            remove_file(catalogs_link_path.as_path())?;
+        }
         link(catalogs_path, catalogs_link_path.as_path(), true)
     }
 
+    fn do_snapshot_revision(&self, id: &str, working_directory: &Path) -> Result<PipeRevision> {
+        let unit = self.do_read_pipe_configuration_file(id)?;
+        let unit_name = self.do_get_unit_name(id)?;
+        let timer =
+            Self::do_read_timer_configuration_file(timer_unit_name(unit_name.as_str()).as_str())?;
+        let catalogs_link_path = Self::do_get_catalogs_link_path(working_directory);
+        let catalogs_path = read_link(catalogs_link_path.as_path())?;
+        Ok(PipeRevision {
+            unit,
+            timer,
+            catalogs_path,
+        })
+    }
+
+    fn do_reconfigure_revision(
+        &self,
+        desc: &PipeDescriptor<'_>,
+        unit_name: &str,
+        working_directory: &Path,
+    ) -> Result<()> {
+        self.do_create_pipe_configuration_file(desc, unit_name, working_directory)?;
+        Self::do_relink_catalogs(working_directory, desc.catalogs_path)?;
+        Self::do_create_ownership(desc.user.as_str(), desc.group.as_str(), working_directory)?;
+        Self::do_reload_units()
+    }
+
+    fn do_rollback_revision(
+        &self,
+        id: &str,
+        working_directory: &Path,
+        revision: &PipeRevision,
+    ) -> Result<()> {
+        let unit_name = self.do_get_unit_name(id)?;
+        create_unit_configuration_file(unit_name.as_str(), revision.unit.as_slice())?;
+        let timer_unit_name = timer_unit_name(unit_name.as_str());
+        match revision.timer.as_ref() {
+            Some(timer) => {
+                create_unit_configuration_file(timer_unit_name.as_str(), timer.as_slice())?
+            }
+            None => Self::do_delete_timer_configuration_file(timer_unit_name.as_str())?,
+        };
+        Self::do_relink_catalogs(working_directory, revision.catalogs_path.as_path())?;
+        Self::do_reload_units()
+    }
+
+    // rollback error logged only so that error of failed change is reported
+    fn do_rollback_revision_logged(
+        &self,
+        id: &str,
+        working_directory: &Path,
+        revision: &PipeRevision,
+    ) {
+        if let Err(err) = self.do_rollback_revision(id, working_directory, revision) {
+            warn!(
+                pipe_id = id,
+                "rollback pipe revision failed, error: {:#?}", err
+            );
+        }
+    }
+
     fn do_create_ownership(user: &str, group: &str, working_directory: &Path) -> Result<()> {
         // assume user and group created
         // grant ownership
@@ -541,10 +2839,33 @@ impl PipeManager {
 
     // systemd service configuration file
     fn do_create_pipe_configuration_file(
+        &self,
         desc: &PipeDescriptor<'_>,
+        unit_name: &str,
         working_directory: &Path,
     ) -> Result<()> {
-        let unit = UnitConfiguration::builder().description(desc.description.as_str());
+        let restart = &desc.restart;
+        let mut dependencies: Vec<(String, &DependencyType)> = vec![];
+        for dependency in desc.depends_on.iter() {
+            let dependency_unit_name = self.do_get_unit_name(dependency.id.as_str())?;
+            dependencies.push((dependency_unit_name, &dependency.dependency_type));
+        }
+        let mut unit = UnitConfiguration::builder()
+            .description(desc.description.as_str())
+            .pipe_id(desc.id.as_str());
+        for (dependency_unit_name, dependency_type) in dependencies.iter() {
+            unit = unit.after(dependency_unit_name.as_str());
+            unit = match dependency_type {
+                DependencyType::Requires => unit.requires(dependency_unit_name.as_str()),
+                DependencyType::Wants => unit.wants(dependency_unit_name.as_str()),
+            };
+        }
+        if let Some(start_limit_burst) = restart.start_limit_burst {
+            unit = unit.start_limit_burst(start_limit_burst);
+        }
+        if let Some(start_limit_interval) = restart.start_limit_interval.as_ref() {
+            unit = unit.start_limit_interval_sec(start_limit_interval.as_str());
+        }
         let app_path = match desc.app_path.to_str() {
             Some(app_path) => app_path,
             None => {
@@ -563,24 +2884,206 @@ impl PipeManager {
                 ))
             }
         };
-        let service = ServiceConfiguration::builder()
+        let mut service = ServiceConfiguration::builder()
             .exec_start(vec![app_path])
             .working_directory(working_directory)
             .user(desc.user.as_str())
-            .group(desc.group.as_str());
+            .group(desc.group.as_str())
+            .slice(self.slice.as_str())
+            .service_type(desc.service_type.clone())
+            .accounting(true)
+            .restart_policy(restart.policy.clone());
+        if let Some(remain_after_exit) = desc.remain_after_exit {
+            service = service.remain_after_exit(remain_after_exit);
+        }
+        if let Some(delay) = restart.delay.as_ref() {
+            service = service.restart_sec(delay.as_str());
+        }
+        let stop = &desc.stop;
+        if let Some(timeout) = stop.timeout.as_ref() {
+            service = service.timeout_stop_sec(timeout.as_str());
+        }
+        if let Some(kill_signal) = stop.kill_signal.as_ref() {
+            service = service.kill_signal(parse_signal(kill_signal)?);
+        }
+        if let Some(kill_mode) = stop.kill_mode.as_ref() {
+            service = service.kill_mode(kill_mode.clone());
+        }
+        if let Some(final_kill_signal) = stop.final_kill_signal.as_ref() {
+            service = service.final_kill_signal(parse_signal(final_kill_signal)?);
+        }
+        if let Some(send_sigkill) = stop.send_sigkill {
+            service = service.send_sigkill(send_sigkill);
+        }
+        for env in desc.envs.iter() {
+            service = service.env(env);
+        }
+        for env_file in desc.env_files.iter() {
+            service = service.env_file(env_file.as_str());
+        }
+        let limits = &desc.limits;
+        if let Some(memory_max) = limits.memory_max.as_ref() {
+            service = service.memory_max(memory_max.as_str());
+        }
+        if let Some(memory_high) = limits.memory_high.as_ref() {
+            service = service.memory_high(memory_high.as_str());
+        }
+        if let Some(cpu_quota) = limits.cpu_quota.as_ref() {
+            service = service.cpu_quota(cpu_quota.as_str());
+        }
+        if let Some(cpu_weight) = limits.cpu_weight {
+            service = service.cpu_weight(cpu_weight);
+        }
+        if let Some(tasks_max) = limits.tasks_max.as_ref() {
+            service = service.tasks_max(tasks_max.as_str());
+        }
+        if let Some(io_weight) = limits.io_weight {
+            service = service.io_weight(io_weight);
+        }
+        let security = &desc.security;
+        if let Some(protect_system) = security.protect_system() {
+            service = service.protect_system(protect_system);
+        }
+        if let Some(protect_home) = security.protect_home() {
+            service = service.protect_home(protect_home);
+        }
+        if let Some(private_tmp) = security.private_tmp() {
+            service = service.private_tmp(private_tmp);
+        }
+        if let Some(no_new_privileges) = security.no_new_privileges() {
+            service = service.no_new_privileges(no_new_privileges);
+        }
+        if let Some(capabilities) = security.capability_bounding_set() {
+            service = service.capability_bounding_set(capabilities);
+        }
+        if security.is_file_system_protected() {
+            service = service.read_write_path(working_directory);
+        }
+        for read_write_path in security.read_write_paths.iter() {
+            service = service.read_write_path(read_write_path.as_str());
+        }
+        if let Some(families) = security.restrict_address_families() {
+            service = service.restrict_address_families(families);
+        }
+        // install section required to enable unit
         let service_unit = ServiceUnitConfiguration::builder()
             .unit(unit)
             .service(service)
+            .install(InstallConfiguration::builder())
             .build();
-        let unit_name = PipeUnitNameBuilder::default().id(desc.id.as_str()).build();
         let buffer = format!("{}", service_unit);
-        create_unit_configuration_file(unit_name.as_str(), buffer.as_bytes())?;
-        Ok(())
+        create_unit_configuration_file(unit_name, buffer.as_bytes())?;
+        let timer_unit_name = timer_unit_name(unit_name);
+        match desc.schedule.as_ref() {
+            Some(schedule) => {
+                Self::do_create_timer_configuration_file(desc, schedule, timer_unit_name.as_str())
+            }
+            None => Self::do_delete_timer_configuration_file(timer_unit_name.as_str()),
+        }
+    }
+
+    // descriptor of pipe record with resolved app and catalogs
+    fn do_build_record_descriptor<'a>(
+        record: &PipeRecord,
+        app_path: &'a Path,
+        catalogs_path: &'a Path,
+    ) -> PipeDescriptor<'a> {
+        PipeDescriptor::builder()
+            .id(record.id.to_owned())
+            .app_path(app_path)
+            .catalogs_path(catalogs_path)
+            .options(record.options.to_owned())
+            .build()
+    }
+
+    // unit configuration file rebuilt from pipe record
+    fn do_recreate_pipe_configuration_file(
+        &self,
+        record: &PipeRecord,
+        unit_name: &str,
+        app_path: Option<&Path>,
+        catalogs_path: Option<&Path>,
+    ) -> Result<()> {
+        let id = record.id.as_str();
+        let (app_path, catalogs_path) = match app_path.zip(catalogs_path) {
+            Some(paths) => paths,
+            None => {
+                return Err(pipe_error(
+                    PipeOperation::Repair,
+                    format!("app or catalogs of pipe '{}' unresolved", id),
+                ))
+            }
+        };
+        let desc = Self::do_build_record_descriptor(record, app_path, catalogs_path);
+        let working_directory = self.do_get_working_directory(id);
+        self.do_create_pipe_configuration_file(&desc, unit_name, working_directory.as_path())?;
+        if desc.autostart {
+            Self::do_enable_unit(Self::do_get_desc_trigger_unit_name(&desc, unit_name).as_str())?;
+        }
+        Self::do_reload_units()
     }
 
-    fn do_delete_pipe_configuration_file(id: &str) -> Result<()> {
-        let unit_name = PipeUnitNameBuilder::default().id(id).build();
+    fn do_read_pipe_configuration_file(&self, id: &str) -> Result<Vec<u8>> {
+        let unit_name = self.do_get_unit_name(id)?;
+        let path = Self::do_get_unit_configuration_file_path(unit_name.as_str());
+        read_file(path.as_path())
+    }
+
+    fn do_delete_pipe_configuration_file(&self, id: &str) -> Result<()> {
+        let unit_name = self.do_get_unit_name(id)?;
         delete_unit_configuration_file(unit_name.as_str())?;
+        Self::do_delete_timer_configuration_file(timer_unit_name(unit_name.as_str()).as_str())
+    }
+
+    // systemd timer configuration file of scheduled pipe
+    fn do_create_timer_configuration_file(
+        desc: &PipeDescriptor<'_>,
+        schedule: &ScheduleDescriptor,
+        timer_unit_name: &str,
+    ) -> Result<()> {
+        let unit = UnitConfiguration::builder()
+            .description(desc.description.as_str())
+            .pipe_id(desc.id.as_str());
+        let mut timer = TimerConfiguration::builder();
+        if let Some(on_calendar) = schedule.on_calendar.as_ref() {
+            timer = timer.on_calendar(on_calendar.as_str());
+        }
+        if let Some(on_boot_sec) = schedule.on_boot_sec.as_ref() {
+            timer = timer.on_boot_sec(on_boot_sec.as_str());
+        }
+        if let Some(on_unit_active_sec) = schedule.on_unit_active_sec.as_ref() {
+            timer = timer.on_unit_active_sec(on_unit_active_sec.as_str());
+        }
+        let timer_unit = TimerUnitConfiguration::builder()
+            .unit(unit)
+            .timer(timer)
+            .build();
+        let buffer = format!("{}", timer_unit);
+        create_unit_configuration_file(timer_unit_name, buffer.as_bytes())?;
+        Ok(())
+    }
+
+    fn do_get_unit_configuration_file_path(unit_name: &str) -> PathBuf {
+        PathBuilder::default()
+            .push(SYSTEMD_UNIT_CONFIGURATION_DIRECTORY)
+            .push(unit_name)
+            .build()
+    }
+
+    fn do_read_timer_configuration_file(timer_unit_name: &str) -> Result<Option<Vec<u8>>> {
+        let path = Self::do_get_unit_configuration_file_path(timer_unit_name);
+        if !path.as_path().exists() {
+            return Ok(None);
+        }
+        Ok(Some(read_file(path.as_path())?))
+    }
+
+    fn do_delete_timer_configuration_file(timer_unit_name: &str) -> Result<()> {
+        let path = Self::do_get_unit_configuration_file_path(timer_unit_name);
+        if !path.as_path().exists() {
+            return Ok(());
+        }
+        delete_unit_configuration_file(timer_unit_name)?;
         Ok(())
     }
 
@@ -590,99 +3093,503 @@ impl PipeManager {
         Ok(unit_path)
     }
 
+    // unit loaded or unit file installed
+    fn do_check_unit_exists(unit_name: &str) -> bool {
+        if Self::do_get_unit(unit_name).is_ok() {
+            return true;
+        }
+        match build_blocking_manager_proxy() {
+            Ok(proxy) => proxy.get_unit_file_state(unit_name).is_ok(),
+            Err(_) => false,
+        }
+    }
+
+    fn do_build_unit_name(&self, id: &str) -> String {
+        PipeUnitNameBuilder::default()
+            .prefix(self.unit_prefix.as_str())
+            .id(id)
+            .build()
+    }
+
+    // unit name persisted in record, legacy '<id>.service' if created before unit prefix
+    fn do_get_unit_name(&self, id: &str) -> Result<String> {
+        let unit_name = self
+            .do_get_pipe_record(id)?
+            .and_then(|record| record.unit)
+            .unwrap_or_else(|| PipeUnitNameBuilder::default().id(id).build());
+        Ok(unit_name)
+    }
+
+    fn do_is_scheduled(&self, id: &str) -> Result<bool> {
+        let record = self.do_get_pipe_record(id)?;
+        Ok(record.is_some_and(|record| record.options.schedule.is_some()))
+    }
+
+    // unit started and enabled on behalf of pipe, timer if scheduled
+    fn do_get_trigger_unit_name(&self, id: &str) -> Result<String> {
+        let unit_name = self.do_get_unit_name(id)?;
+        match self.do_is_scheduled(id)? {
+            true => Ok(timer_unit_name(unit_name.as_str())),
+            false => Ok(unit_name),
+        }
+    }
+
+    fn do_get_desc_trigger_unit_name(desc: &PipeDescriptor<'_>, unit_name: &str) -> String {
+        match desc.schedule {
+            Some(_) => timer_unit_name(unit_name),
+            None => unit_name.to_owned(),
+        }
+    }
+
+    fn do_is_legacy_unit(&self, id: &str) -> Result<bool> {
+        let record = self.do_get_pipe_record(id)?;
+        Ok(record.is_some_and(|record| record.unit.is_none()))
+    }
+
+    // poll pipe status until stopped, blocks current thread so never call on async runtime
+    fn do_wait_inactive(&self, id: &str, op: PipeOperation, timeout: Duration) -> Result<()> {
+        let deadline = Instant::now() + timeout;
+        loop {
+            let state = self.do_status(id)?;
+            if state.is_inactive() || state.is_failed() {
+                return Ok(());
+            }
+            if Instant::now() >= deadline {
+                return Err(pipe_error(
+                    op,
+                    format!("pipe '{}' not inactive after stop", id),
+                ));
+            }
+            std::thread::sleep(Duration::from_millis(PIPE_STATUS_POLL_INTERVAL_MILLIS));
+        }
+    }
+
+    // move inactive pipe from legacy unit to namespaced unit in slice, returns new unit name
+    fn do_migrate_unit(&self, id: &str) -> Result<String> {
+        let legacy_unit_name = self.do_get_unit_name(id)?;
+        let unit_name = self.do_build_unit_name(id);
+        if Self::do_check_unit_exists(unit_name.as_str()) {
+            return Err(pipe_error(
+                PipeOperation::Migrate,
+                format!("unit '{}' of pipe '{}' already exists", unit_name, id),
+            ));
+        }
+        let unit = String::from_utf8(self.do_read_pipe_configuration_file(id)?)?;
+        let pipe_id_prefix = format!("{}=", SYSTEMD_PIPE_ID_KEY);
+        let mut lines: Vec<String> = vec![];
+        for line in unit
+            .lines()
+            .filter(|line| !line.starts_with("Slice=") && !line.starts_with(&pipe_id_prefix))
+        {
+            lines.push(line.to_owned());
+            if line == "[Unit]" {
+                lines.push(format!("{}{}", pipe_id_prefix, id));
+            }
+            if line == "[Service]" {
+                lines.push(format!("Slice={}", self.slice));
+            }
+        }
+        let buffer = format!("{}\n", lines.join("\n"));
+        create_unit_configuration_file(unit_name.as_str(), buffer.as_bytes())?;
+        let enabled = self.do_status(id)?.enabled;
+        if enabled {
+            Self::do_disable_unit(legacy_unit_name.as_str())?;
+        }
+        delete_unit_configuration_file(legacy_unit_name.as_str())?;
+        self.do_update_pipe_record(id, |record| record.unit = Some(unit_name.to_owned()))?;
+        if enabled {
+            Self::do_enable_unit(unit_name.as_str())?;
+        }
+        for dependent in self.do_list_dependents(id)? {
+            self.do_rename_dependency_unit(
+                dependent.as_str(),
+                legacy_unit_name.as_str(),
+                unit_name.as_str(),
+            )?;
+        }
+        Self::do_reload_units()?;
+        info!(
+            pipe_id = id,
+            "pipe unit migrated from '{}' to '{}'", legacy_unit_name, unit_name
+        );
+        Ok(unit_name)
+    }
+
+    // rewrite dependency unit name in After=, Requires= and Wants= of dependent unit
+    fn do_rename_dependency_unit(
+        &self,
+        id: &str,
+        dependency_unit_name: &str,
+        new_dependency_unit_name: &str,
+    ) -> Result<()> {
+        let unit_name = self.do_get_unit_name(id)?;
+        let unit = String::from_utf8(self.do_read_pipe_configuration_file(id)?)?;
+        let mut lines: Vec<String> = vec![];
+        for line in unit.lines() {
+            let line = match line.split_once('=') {
+                Some((key, units)) if ["After", "Requires", "Wants"].contains(&key) => {
+                    let units: Vec<&str> = units
+                        .split_whitespace()
+                        .map(|unit| match unit == dependency_unit_name {
+                            true => new_dependency_unit_name,
+                            false => unit,
+                        })
+                        .collect();
+                    format!("{}={}", key, units.join(" "))
+                }
+                _ => line.to_owned(),
+            };
+            lines.push(line);
+        }
+        let buffer = format!("{}\n", lines.join("\n"));
+        create_unit_configuration_file(unit_name.as_str(), buffer.as_bytes())?;
+        Ok(())
+    }
+
+    // pipe id marked in unit file, undefined if unit file not generated for a pipe
+    fn do_read_unit_pipe_id(unit_name: &str) -> Result<Option<String>> {
+        let path = Self::do_get_unit_configuration_file_path(unit_name);
+        let unit = String::from_utf8(read_file(path.as_path())?)?;
+        let pipe_id = unit.lines().find_map(|line| match line.split_once('=') {
+            Some((key, pipe_id)) if key == SYSTEMD_PIPE_ID_KEY => Some(pipe_id.to_owned()),
+            _ => None,
+        });
+        Ok(pipe_id)
+    }
+
+    // delete unit file not owned by any pipe, unit must not be running
+    fn do_delete_orphan_unit(unit_name: &str) -> Result<()> {
+        if let Ok(unit_path) = Self::do_get_unit(unit_name) {
+            let unit_props = Self::do_get_unit_properties(unit_path)?;
+            let active_state: PipeActiveStateType = unit_props.active_state.into();
+            if !matches!(
+                active_state,
+                PipeActiveStateType::Inactive | PipeActiveStateType::Failed
+            ) {
+                return Err(pipe_error(
+                    PipeOperation::Repair,
+                    format!("unit '{}' is not inactive", unit_name),
+                ));
+            }
+        }
+        let proxy = build_blocking_manager_proxy()?;
+        if proxy.get_unit_file_state(unit_name)? == "enabled" {
+            Self::do_disable_unit(unit_name)?;
+        }
+        delete_unit_configuration_file(unit_name)?;
+        Self::do_reload_units()
+    }
+
+    fn do_get_active_state(unit_name: &str) -> Result<PipeActiveStateType> {
+        let unit_path = Self::do_load_unit(unit_name)?;
+        let unit_props = Self::do_get_unit_properties(unit_path)?;
+        Ok(unit_props.active_state.into())
+    }
+
     fn do_get_unit_properties(unit_path: zvariant::OwnedObjectPath) -> Result<UnitProps> {
         let client = unit::build_blocking_proxy(unit_path)?;
         let unit_props = client.get_properties()?;
         Ok(unit_props)
     }
 
-    fn do_start_unit(unit_name: &str) -> Result<()> {
+    // returns enqueued job
+    fn do_start_unit(unit_name: &str, mode: &JobMode) -> Result<zvariant::OwnedObjectPath> {
         let proxy = manager::build_blocking_proxy()?;
-        let _ = proxy.start_unit(unit_name, SYSTEMD_DEFAULT_START_UNIT_MODE)?;
-        Ok(())
+        let mode = mode.to_string();
+        proxy
+            .start_unit(unit_name, mode.as_str())
+            .map_err(|err| job_error(unit_name, mode.as_str(), err))
     }
 
-    fn do_stop_unit(unit_name: &str) -> Result<()> {
+    // returns enqueued job
+    fn do_stop_unit(unit_name: &str, mode: &JobMode) -> Result<zvariant::OwnedObjectPath> {
         let client = manager::build_blocking_proxy()?;
-        let _ = client.stop_unit(unit_name, SYSTEMD_DEFAULT_STOP_UNIT_MODE)?;
+        let mode = mode.to_string();
+        client
+            .stop_unit(unit_name, mode.as_str())
+            .map_err(|err| job_error(unit_name, mode.as_str(), err))
+    }
+
+    // returns enqueued job
+    fn do_restart_unit(unit_name: &str) -> Result<zvariant::OwnedObjectPath> {
+        let proxy = manager::build_blocking_proxy()?;
+        let job = proxy.restart_unit(unit_name, SYSTEMD_DEFAULT_RESTART_UNIT_MODE)?;
+        Ok(job)
+    }
+
+    fn do_reload_or_restart_unit(unit_name: &str) -> Result<()> {
+        let proxy = build_blocking_manager_proxy()?;
+        let _ = proxy.reload_or_restart_unit(unit_name, SYSTEMD_DEFAULT_RELOAD_UNIT_MODE)?;
+        Ok(())
+    }
+
+    fn do_kill_unit(unit_name: &str, whom: &KillWhom, signal: i32) -> Result<()> {
+        let proxy = build_blocking_manager_proxy()?;
+        proxy.kill_unit(unit_name, whom.to_string().as_str(), signal)?;
+        Ok(())
+    }
+
+    fn do_reset_failed_unit(unit_name: &str) -> Result<()> {
+        let proxy = build_blocking_manager_proxy()?;
+        proxy.reset_failed_unit(unit_name)?;
         Ok(())
     }
 
+    fn do_enable_unit(unit_name: &str) -> Result<()> {
+        let proxy = build_blocking_manager_proxy()?;
+        let _ = proxy.enable_unit_files(&[unit_name], false, false)?;
+        // reload to pick up new install symlinks
+        proxy.reload()?;
+        Ok(())
+    }
+
+    fn do_disable_unit(unit_name: &str) -> Result<()> {
+        let proxy = build_blocking_manager_proxy()?;
+        let _ = proxy.disable_unit_files(&[unit_name], false)?;
+        proxy.reload()?;
+        Ok(())
+    }
+
+    // reload unit configuration files after change
+    fn do_reload_units() -> Result<()> {
+        let proxy = build_blocking_manager_proxy()?;
+        proxy.reload()?;
+        Ok(())
+    }
+
+    // poll pipe status until active and running, pipe lock held at poll
+    // and released while sleeping so that other operations are not blocked
+    async fn do_wait_running(
+        &self,
+        lock_file: &mut LockFile,
+        id: &str,
+        timeout: Duration,
+    ) -> Result<bool> {
+        let deadline = Instant::now() + timeout;
+        loop {
+            let state = self.do_status(id)?;
+            // oneshot pipe waits for restart job instead
+            if state.is_active() && state.is_running() {
+                return Ok(true);
+            }
+            if Instant::now() >= deadline {
+                return Ok(false);
+            }
+            lock_file.unlock()?;
+            sleep(Duration::from_millis(PIPE_STATUS_POLL_INTERVAL_MILLIS)).await;
+            lock_file.lock()?;
+        }
+    }
+
+    // restart unit and wait until restart job removed, error if job not done within timeout,
+    // pipe lock released while waiting
+    async fn do_restart_wait_done(
+        &self,
+        lock_file: &mut LockFile,
+        id: &str,
+        unit_name: &str,
+        timeout: Duration,
+    ) -> Result<()> {
+        let listener = JobListener::new().await?;
+        let job = Self::do_restart_unit(unit_name)?;
+        lock_file.unlock()?;
+        let result = listener.wait(&job, timeout).await;
+        lock_file.lock()?;
+        match result? {
+            Some(result) if result.is_done() => Ok(()),
+            Some(result) => Err(pipe_error(
+                PipeOperation::Update,
+                format!(
+                    "restart job of pipe '{}' {}, rollback to previous revision",
+                    id, result
+                ),
+            )),
+            None => Err(pipe_error(
+                PipeOperation::Update,
+                format!(
+                    "restart job of pipe '{}' not completed within {:?}, rollback to previous revision",
+                    id, timeout
+                ),
+            )),
+        }
+    }
+
     fn do_load_unit(unit_name: &str) -> Result<zvariant::OwnedObjectPath> {
         let client = manager::build_blocking_proxy()?;
         let unit_path = client.load_unit(unit_name)?;
         Ok(unit_path)
     }
 
-    fn do_status(id: &str) -> Result<PipeState> {
-        let unit_name = PipeUnitNameBuilder::default().id(id).build();
+    fn do_status(&self, id: &str) -> Result<PipeState> {
+        let unit_name = self.do_get_unit_name(id)?;
         // https://unix.stackexchange.com/questions/615202/systemd-dbus-api-returns-service-not-loaded-for-disabled-services
         let unit_path = Self::do_load_unit(unit_name.as_str())?;
-        let unit_props = Self::do_get_unit_properties(unit_path)?;
-        Ok(PipeState {
+        let unit_props = Self::do_get_unit_properties(unit_path.clone())?;
+        let mut state = PipeState {
             id: id.to_owned(),
             load_state: unit_props.load_state.into(),
             active_state: unit_props.active_state.into(),
             sub_state: unit_props.sub_state.into(),
+            enabled: false,
+            labels: HashMap::new(),
+            next_trigger: None,
+            last_trigger: None,
+            liveness: None,
+            readiness: None,
+        };
+        if !self.do_is_scheduled(id)? {
+            let unit_file_state = build_blocking_unit_proxy(unit_path)?.unit_file_state()?;
+            state.enabled = unit_file_state == "enabled";
+            return Ok(state);
+        }
+        // scheduled pipe enabled and triggered by timer
+        let timer_unit_name = timer_unit_name(unit_name.as_str());
+        let timer_unit_path = Self::do_load_unit(timer_unit_name.as_str())?;
+        let unit_file_state =
+            build_blocking_unit_proxy(timer_unit_path.clone())?.unit_file_state()?;
+        state.enabled = unit_file_state == "enabled";
+        let timer = build_blocking_timer_proxy(timer_unit_path)?;
+        let next_realtime = Some(timer.next_elapse_usec_realtime()?).filter(|usec| *usec > 0);
+        let next_monotonic = Some(timer.next_elapse_usec_monotonic()?)
+            .filter(|usec| *usec > 0)
+            .and_then(monotonic_to_unix_timestamp_micros);
+        state.next_trigger = match (next_realtime, next_monotonic) {
+            (Some(realtime), Some(monotonic)) => Some(realtime.min(monotonic)),
+            (realtime, monotonic) => realtime.or(monotonic),
+        };
+        state.last_trigger = Some(timer.last_trigger_usec()?).filter(|usec| *usec > 0);
+        Ok(state)
+    }
+
+    fn do_inspect(&self, id: &str) -> Result<PipeInspection> {
+        let state = self.do_status(id)?;
+        let unit_name = self.do_get_unit_name(id)?;
+        let unit_path = Self::do_load_unit(unit_name.as_str())?;
+        let unit_props = Self::do_get_unit_properties(unit_path.clone())?;
+        let unit = build_blocking_unit_proxy(unit_path.clone())?;
+        let service = build_blocking_service_proxy(unit_path)?;
+        let envs = service
+            .environment()?
+            .iter()
+            .filter_map(|env| EnvironmentVariable::from_str(env).ok())
+            .map(|env| env.masked())
+            .collect();
+        let app_path = Self::do_get_service_app_path(&service)?;
+        let working_directory = PathBuf::from(service.working_directory()?);
+        let catalogs_link_path = Self::do_get_catalogs_link_path(working_directory.as_path());
+        let catalogs_path = read_link(catalogs_link_path.as_path()).ok();
+        let unit_file_path = PathBuf::from(unit.fragment_path()?);
+        let unit_file = read_file(unit_file_path.as_path())?;
+        Ok(PipeInspection {
+            state,
+            description: unit_props.description,
+            user: service.user()?,
+            group: service.group()?,
+            envs,
+            app: None,
+            catalogs: None,
+            app_path,
+            catalogs_path,
+            working_directory,
+            unit_path: unit_file_path,
+            unit: String::from_utf8(unit_file)?,
+            main_pid: service.main_pid()?,
+            exec_main_code: service.exec_main_code()?,
+            exec_main_status: service.exec_main_status()?,
+            n_restarts: service.n_restarts()?,
+            active_enter_timestamp: unit.active_enter_timestamp()?,
+            inactive_enter_timestamp: unit.inactive_enter_timestamp()?,
         })
     }
 
-    // read pipe register
-    fn do_read_pipe_register(&self) -> Result<Vec<String>> {
+    fn do_get_app_path(&self, id: &str) -> Result<Option<PathBuf>> {
+        let unit_name = self.do_get_unit_name(id)?;
+        let unit_path = Self::do_load_unit(unit_name.as_str())?;
+        let service = build_blocking_service_proxy(unit_path)?;
+        Self::do_get_service_app_path(&service)
+    }
+
+    // app is the executable of first ExecStart command
+    fn do_get_service_app_path(service: &ServiceProxyBlocking<'_>) -> Result<Option<PathBuf>> {
+        let app_path = service
+            .exec_start()?
+            .into_iter()
+            .next()
+            .map(|command| PathBuf::from(command.0));
+        Ok(app_path)
+    }
+
+    // read pipe register, id only register is migrated in memory
+    fn do_read_pipe_register(&self) -> Result<Vec<PipeRecord>> {
         let register_file_path = PathBuilder::default()
             .push(self.workspace.as_path())
             .push(PATH_PIPE_REGISTER)
             .build();
         match register_file_path.as_path().exists() {
-            true => read_yml::<&Path, Vec<String>>(register_file_path.as_path()),
+            true => {
+                let register =
+                    read_yml::<&Path, VersionedPipeRegister>(register_file_path.as_path())?;
+                Ok(register.into())
+            }
             false => Ok(vec![]),
         }
     }
 
     // write pipe register
-    fn do_write_pipe_register(&self, ids: Vec<String>) -> Result<()> {
+    fn do_write_pipe_register(&self, records: Vec<PipeRecord>) -> Result<()> {
         let register_file_path = PathBuilder::default()
             .push(self.workspace.as_path())
             .push(PATH_PIPE_REGISTER)
             .build();
-        write_yml(register_file_path.as_path(), &ids)
+        let register = PipeRegister {
+            version: PIPE_REGISTER_VERSION,
+            pipes: records,
+        };
+        write_yml(register_file_path.as_path(), &register)
     }
 
-    // register pipe id
-    fn do_register_pipe(&self, id: &str) -> Result<()> {
-        let mut ids = self.do_read_pipe_register()?;
-        // append new pipe id
-        ids.push(id.to_owned());
-        self.do_write_pipe_register(ids)
+    // register pipe record
+    fn do_register_pipe(&self, record: PipeRecord) -> Result<()> {
+        let mut records = self.do_read_pipe_register()?;
+        // append new pipe record
+        records.push(record);
+        self.do_write_pipe_register(records)
     }
 
     fn do_deregister_pipe(&self, id: &str) -> Result<()> {
-        let mut ids = self.do_read_pipe_register()?;
-        let mut i: usize = 0;
-        for pipe_id in ids.iter() {
-            if pipe_id.as_str() == id {
-                break;
-            }
-            i += 1;
-        }
-        let len = ids.len();
-        assert!(i < len, "deregister pipe with invalid id '{}'", id);
+        let mut records = self.do_read_pipe_register()?;
+        let i = records.iter().position(|record| record.id == id);
+        let i = i.unwrap_or_else(|| panic!("deregister pipe with invalid id '{}'", id));
         // avoid O(N) removal
-        ids.swap(i, len - 1);
-        ids.remove(len - 1);
-        self.do_write_pipe_register(ids)
+        records.swap_remove(i);
+        self.do_write_pipe_register(records)
     }
 
-    fn do_check_pipe_registered(&self, id: &str) -> Result<bool> {
-        let ids = self.do_read_pipe_register()?;
-        let mut i: usize = 0;
-        for pipe_id in ids.iter() {
-            if pipe_id.as_str() == id {
-                break;
+    fn do_get_pipe_record(&self, id: &str) -> Result<Option<PipeRecord>> {
+        let records = self.do_read_pipe_register()?;
+        Ok(records.into_iter().find(|record| record.id == id))
+    }
+
+    fn do_update_pipe_record<F>(&self, id: &str, f: F) -> Result<()>
+    where
+        F: FnOnce(&mut PipeRecord),
+    {
+        let mut records = self.do_read_pipe_register()?;
+        match records.iter_mut().find(|record| record.id == id) {
+            Some(record) => f(record),
+            None => {
+                return Err(pipe_error(
+                    PipeOperation::Update,
+                    format!("pipe '{}' not registered", id),
+                ))
             }
-            i += 1;
-        }
-        Ok(i < ids.len())
+        };
+        self.do_write_pipe_register(records)
+    }
+
+    fn do_check_pipe_registered(&self, id: &str) -> Result<bool> {
+        let records = self.do_read_pipe_register()?;
+        Ok(records.iter().any(|record| record.id == id))
     }
 
     fn open_pipe_lock(&self) -> Result<LockFile> {
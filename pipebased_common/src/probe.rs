@@ -0,0 +1,528 @@
+use crate::{
+    grpc, pipe_error, Error, PipeManager, PipeOperation, PipeRecord, Result,
+    PIPE_PROBE_DEFAULT_FAILURE_THRESHOLD, PIPE_PROBE_DEFAULT_HOST,
+    PIPE_PROBE_DEFAULT_INTERVAL_SECS, PIPE_PROBE_DEFAULT_TIMEOUT_SECS,
+    PIPE_PROBE_SYNC_INTERVAL_SECS, SYSTEMD_DEFAULT_GROUP, SYSTEMD_DEFAULT_USER,
+};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::HashMap,
+    ffi::OsString,
+    fmt::Display,
+    process::Stdio,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+use tokio::{
+    io::{AsyncBufReadExt, AsyncWriteExt, BufReader},
+    net::TcpStream,
+    process::Command,
+    task::JoinHandle,
+    time::{sleep, sleep_until, timeout, Instant},
+};
+use tracing::{info, warn};
+
+#[derive(Clone, Deserialize, Serialize)]
+pub struct TcpProbe {
+    // loopback if undefined
+    pub host: Option<String>,
+    pub port: u16,
+}
+
+impl TryFrom<grpc::daemon::TcpProbe> for TcpProbe {
+    type Error = Error;
+
+    fn try_from(origin: grpc::daemon::TcpProbe) -> Result<Self> {
+        Ok(TcpProbe {
+            host: origin.host,
+            port: probe_port(origin.port)?,
+        })
+    }
+}
+
+#[derive(Clone, Deserialize, Serialize)]
+pub struct HttpProbe {
+    // loopback if undefined
+    pub host: Option<String>,
+    pub port: u16,
+    // '/' if undefined
+    pub path: Option<String>,
+}
+
+impl TryFrom<grpc::daemon::HttpProbe> for HttpProbe {
+    type Error = Error;
+
+    fn try_from(origin: grpc::daemon::HttpProbe) -> Result<Self> {
+        Ok(HttpProbe {
+            host: origin.host,
+            port: probe_port(origin.port)?,
+            path: origin.path,
+        })
+    }
+}
+
+// command run as pipe user, succeeds if exit with zero code
+#[derive(Clone, Deserialize, Serialize)]
+pub struct ExecProbe {
+    pub command: Vec<String>,
+}
+
+impl From<grpc::daemon::ExecProbe> for ExecProbe {
+    fn from(origin: grpc::daemon::ExecProbe) -> Self {
+        ExecProbe {
+            command: origin.command,
+        }
+    }
+}
+
+fn probe_port(port: u32) -> Result<u16> {
+    u16::try_from(port).map_err(|_| {
+        pipe_error(
+            PipeOperation::Validate,
+            format!("invalid probe port '{}'", port),
+        )
+    })
+}
+
+// daemon side health check, exactly one of tcp, http or exec
+#[derive(Clone, Default, Deserialize, Serialize)]
+pub struct ProbeDescriptor {
+    pub tcp: Option<TcpProbe>,
+    pub http: Option<HttpProbe>,
+    pub exec: Option<ExecProbe>,
+    // seconds, defaults if undefined
+    pub interval: Option<u64>,
+    pub timeout: Option<u64>,
+    // consecutive failures before unhealthy
+    pub failure_threshold: Option<u32>,
+}
+
+impl ProbeDescriptor {
+    pub fn validate(&self, name: &str) -> Result<()> {
+        let probes = [self.tcp.is_some(), self.http.is_some(), self.exec.is_some()];
+        if probes.iter().filter(|defined| **defined).count() != 1 {
+            return Err(pipe_error(
+                PipeOperation::Validate,
+                format!("{} probe expects exactly one of tcp, http or exec", name),
+            ));
+        }
+        if self.tcp.as_ref().is_some_and(|tcp| tcp.port == 0)
+            || self.http.as_ref().is_some_and(|http| http.port == 0)
+        {
+            return Err(pipe_error(
+                PipeOperation::Validate,
+                format!("{} probe port undefined", name),
+            ));
+        }
+        if let Some(path) = self.http.as_ref().and_then(|http| http.path.as_ref()) {
+            if !path.starts_with('/') || path.contains(char::is_whitespace) {
+                return Err(pipe_error(
+                    PipeOperation::Validate,
+                    format!("invalid {} probe path '{}'", name, path),
+                ));
+            }
+        }
+        if self
+            .exec
+            .as_ref()
+            .is_some_and(|exec| exec.command.is_empty())
+        {
+            return Err(pipe_error(
+                PipeOperation::Validate,
+                format!("{} probe command undefined", name),
+            ));
+        }
+        if self.interval == Some(0) || self.timeout == Some(0) || self.failure_threshold == Some(0)
+        {
+            return Err(pipe_error(
+                PipeOperation::Validate,
+                format!(
+                    "{} probe interval, timeout and failure threshold must be positive",
+                    name
+                ),
+            ));
+        }
+        Ok(())
+    }
+
+    fn interval(&self) -> Duration {
+        Duration::from_secs(self.interval.unwrap_or(PIPE_PROBE_DEFAULT_INTERVAL_SECS))
+    }
+
+    fn timeout(&self) -> Duration {
+        Duration::from_secs(self.timeout.unwrap_or(PIPE_PROBE_DEFAULT_TIMEOUT_SECS))
+    }
+
+    fn failure_threshold(&self) -> u32 {
+        self.failure_threshold
+            .unwrap_or(PIPE_PROBE_DEFAULT_FAILURE_THRESHOLD)
+    }
+
+    // probe once within timeout
+    async fn probe(&self, user: &str, group: &str) -> Result<()> {
+        let probe = async {
+            if let Some(tcp) = self.tcp.as_ref() {
+                return probe_tcp(tcp).await;
+            }
+            if let Some(http) = self.http.as_ref() {
+                return probe_http(http).await;
+            }
+            match self.exec.as_ref() {
+                Some(exec) => probe_exec(exec, user, group).await,
+                None => Ok(()),
+            }
+        };
+        match timeout(self.timeout(), probe).await {
+            Ok(result) => result,
+            Err(_) => Err(pipe_error(
+                PipeOperation::Probe,
+                format!("probe timeout after {:?}", self.timeout()),
+            )),
+        }
+    }
+}
+
+impl TryFrom<grpc::daemon::ProbeDescriptor> for ProbeDescriptor {
+    type Error = Error;
+
+    fn try_from(origin: grpc::daemon::ProbeDescriptor) -> Result<Self> {
+        let tcp = match origin.tcp {
+            Some(tcp) => Some(tcp.try_into()?),
+            None => None,
+        };
+        let http = match origin.http {
+            Some(http) => Some(http.try_into()?),
+            None => None,
+        };
+        let exec = origin.exec.map(|exec| exec.into());
+        Ok(ProbeDescriptor {
+            tcp,
+            http,
+            exec,
+            interval: origin.interval,
+            timeout: origin.timeout,
+            failure_threshold: origin.failure_threshold,
+        })
+    }
+}
+
+async fn probe_tcp(tcp: &TcpProbe) -> Result<()> {
+    let host = tcp.host.as_deref().unwrap_or(PIPE_PROBE_DEFAULT_HOST);
+    TcpStream::connect((host, tcp.port)).await?;
+    Ok(())
+}
+
+// http get, succeeds if status 2xx or 3xx
+async fn probe_http(http: &HttpProbe) -> Result<()> {
+    let host = http.host.as_deref().unwrap_or(PIPE_PROBE_DEFAULT_HOST);
+    let path = http.path.as_deref().unwrap_or("/");
+    let mut stream = TcpStream::connect((host, http.port)).await?;
+    let request = format!(
+        "GET {} HTTP/1.1\r\nHost: {}:{}\r\nConnection: close\r\n\r\n",
+        path, host, http.port
+    );
+    stream.write_all(request.as_bytes()).await?;
+    let mut status_line = String::new();
+    BufReader::new(stream).read_line(&mut status_line).await?;
+    // i.e 'HTTP/1.1 200 OK'
+    let status: Option<u16> = status_line
+        .split_whitespace()
+        .nth(1)
+        .and_then(|status| status.parse().ok());
+    match status {
+        Some(status) if (200..400).contains(&status) => Ok(()),
+        _ => Err(pipe_error(
+            PipeOperation::Probe,
+            format!("http probe response '{}'", status_line.trim()),
+        )),
+    }
+}
+
+fn runuser_binary() -> OsString {
+    "runuser".to_owned().into()
+}
+
+async fn probe_exec(exec: &ExecProbe, user: &str, group: &str) -> Result<()> {
+    let mut cmd = Command::new(runuser_binary());
+    cmd.arg("-u")
+        .arg(user)
+        .arg("-g")
+        .arg(group)
+        .arg("--")
+        .args(exec.command.iter())
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .kill_on_drop(true);
+    let status = cmd.status().await?;
+    match status.success() {
+        true => Ok(()),
+        false => Err(pipe_error(
+            PipeOperation::Probe,
+            format!("exec probe exit with {}", status),
+        )),
+    }
+}
+
+#[derive(Clone, Copy, Default)]
+pub enum HealthStatus {
+    // not probed yet, or pipe not running
+    #[default]
+    Unknown,
+    Healthy,
+    Unhealthy,
+}
+
+impl Display for HealthStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let status = match self {
+            HealthStatus::Unknown => "unknown",
+            HealthStatus::Healthy => "healthy",
+            HealthStatus::Unhealthy => "unhealthy",
+        };
+        write!(f, "{}", status)
+    }
+}
+
+// health of probed pipe, undefined if probe undefined
+#[derive(Clone, Default)]
+pub struct PipeHealth {
+    pub liveness: Option<HealthStatus>,
+    pub readiness: Option<HealthStatus>,
+}
+
+pub(crate) type PipeHealthRegistry = Arc<Mutex<HashMap<String, PipeHealth>>>;
+
+#[derive(Clone, Copy)]
+enum ProbeType {
+    Liveness,
+    Readiness,
+}
+
+impl Display for ProbeType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let probe_type = match self {
+            ProbeType::Liveness => "liveness",
+            ProbeType::Readiness => "readiness",
+        };
+        write!(f, "{}", probe_type)
+    }
+}
+
+struct ProbeState {
+    probe_type: ProbeType,
+    desc: ProbeDescriptor,
+    next: Instant,
+    failures: u32,
+}
+
+// probe task of single pipe
+struct PipeProbe {
+    id: String,
+    user: String,
+    group: String,
+    probes: Vec<ProbeState>,
+    pipe_manager: PipeManager,
+    health: PipeHealthRegistry,
+}
+
+impl PipeProbe {
+    fn new(record: &PipeRecord, pipe_manager: PipeManager, health: PipeHealthRegistry) -> Self {
+        let options = &record.options;
+        let now = Instant::now();
+        let probes: Vec<ProbeState> = [
+            (ProbeType::Liveness, options.liveness.as_ref()),
+            (ProbeType::Readiness, options.readiness.as_ref()),
+        ]
+        .into_iter()
+        .filter_map(|(probe_type, desc)| {
+            desc.map(|desc| ProbeState {
+                probe_type,
+                desc: desc.to_owned(),
+                next: now + desc.interval(),
+                failures: 0,
+            })
+        })
+        .collect();
+        PipeProbe {
+            id: record.id.to_owned(),
+            user: options
+                .user
+                .to_owned()
+                .unwrap_or_else(|| String::from(SYSTEMD_DEFAULT_USER)),
+            group: options
+                .group
+                .to_owned()
+                .unwrap_or_else(|| String::from(SYSTEMD_DEFAULT_GROUP)),
+            probes,
+            pipe_manager,
+            health,
+        }
+    }
+
+    async fn run(mut self) {
+        loop {
+            let next = match self.probes.iter().map(|probe| probe.next).min() {
+                Some(next) => next,
+                None => return,
+            };
+            sleep_until(next).await;
+            let running = self.is_running().await;
+            let now = Instant::now();
+            for i in 0..self.probes.len() {
+                if self.probes[i].next > now {
+                    continue;
+                }
+                self.probes[i].next = now + self.probes[i].desc.interval();
+                // failures count only while pipe running
+                if !running {
+                    self.probes[i].failures = 0;
+                    let probe_type = self.probes[i].probe_type;
+                    Self::set_health(&self.health, &self.id, probe_type, HealthStatus::Unknown);
+                    continue;
+                }
+                self.probe(i).await;
+            }
+        }
+    }
+
+    async fn probe(&mut self, i: usize) {
+        let probe = &mut self.probes[i];
+        let probe_type = probe.probe_type;
+        match probe
+            .desc
+            .probe(self.user.as_str(), self.group.as_str())
+            .await
+        {
+            Ok(_) => {
+                probe.failures = 0;
+                Self::set_health(&self.health, &self.id, probe_type, HealthStatus::Healthy);
+                return;
+            }
+            Err(err) => {
+                probe.failures += 1;
+                warn!(
+                    pipe_id = self.id.as_str(),
+                    probe = %probe_type,
+                    failures = probe.failures,
+                    "probe failed, error: {:#?}",
+                    err
+                );
+            }
+        }
+        if probe.failures < probe.desc.failure_threshold() {
+            return;
+        }
+        probe.failures = 0;
+        Self::set_health(&self.health, &self.id, probe_type, HealthStatus::Unhealthy);
+        if !matches!(probe_type, ProbeType::Liveness) {
+            return;
+        }
+        info!(pipe_id = self.id.as_str(), "restart unhealthy pipe ...");
+        let pipe_manager = self.pipe_manager.clone();
+        let id = self.id.to_owned();
+        match tokio::task::spawn_blocking(move || pipe_manager.restart(id.as_str())).await {
+            Ok(Ok(_)) => (),
+            Ok(Err(err)) => warn!(
+                pipe_id = self.id.as_str(),
+                "restart unhealthy pipe failed, error: {:#?}", err
+            ),
+            Err(err) => warn!(
+                pipe_id = self.id.as_str(),
+                "restart unhealthy pipe failed, error: {:#?}", err
+            ),
+        }
+    }
+
+    async fn is_running(&self) -> bool {
+        let pipe_manager = self.pipe_manager.clone();
+        let id = self.id.to_owned();
+        match tokio::task::spawn_blocking(move || pipe_manager.status(id.as_str())).await {
+            Ok(Ok(state)) => state.is_active() && state.is_running(),
+            _ => false,
+        }
+    }
+
+    fn set_health(
+        health: &PipeHealthRegistry,
+        id: &str,
+        probe_type: ProbeType,
+        status: HealthStatus,
+    ) {
+        let mut health = health.lock().expect("pipe health registry poisoned");
+        let health = health.entry(id.to_owned()).or_default();
+        match probe_type {
+            ProbeType::Liveness => health.liveness = Some(status),
+            ProbeType::Readiness => health.readiness = Some(status),
+        }
+    }
+}
+
+fn has_probes(record: &PipeRecord) -> bool {
+    record.options.liveness.is_some() || record.options.readiness.is_some()
+}
+
+// keep one probe task per probed pipe in sync with pipe register
+pub(crate) async fn run_probes(pipe_manager: PipeManager, health: PipeHealthRegistry) {
+    // pipe id -> (record revision, probe task)
+    let mut tasks: HashMap<String, (u64, JoinHandle<()>)> = HashMap::new();
+    loop {
+        let records = {
+            let pipe_manager = pipe_manager.clone();
+            tokio::task::spawn_blocking(move || pipe_manager.list_pipe_records()).await
+        };
+        match records {
+            Ok(Ok(records)) => sync_probes(&records, &mut tasks, &pipe_manager, &health),
+            Ok(Err(err)) => warn!("read pipe register failed, error: {:#?}", err),
+            Err(err) => warn!("read pipe register failed, error: {:#?}", err),
+        }
+        sleep(Duration::from_secs(PIPE_PROBE_SYNC_INTERVAL_SECS)).await;
+    }
+}
+
+fn sync_probes(
+    records: &[PipeRecord],
+    tasks: &mut HashMap<String, (u64, JoinHandle<()>)>,
+    pipe_manager: &PipeManager,
+    health: &PipeHealthRegistry,
+) {
+    // abort tasks of removed, updated or no longer probed pipes
+    tasks.retain(|id, (revision, task)| {
+        let current = records
+            .iter()
+            .find(|record| &record.id == id)
+            .filter(|record| has_probes(record) && record.revision == *revision);
+        if current.is_none() {
+            task.abort();
+            health
+                .lock()
+                .expect("pipe health registry poisoned")
+                .remove(id);
+        }
+        current.is_some()
+    });
+    for record in records.iter().filter(|record| has_probes(record)) {
+        if tasks.contains_key(&record.id) {
+            continue;
+        }
+        let initial = PipeHealth {
+            liveness: record
+                .options
+                .liveness
+                .as_ref()
+                .map(|_| HealthStatus::Unknown),
+            readiness: record
+                .options
+                .readiness
+                .as_ref()
+                .map(|_| HealthStatus::Unknown),
+        };
+        health
+            .lock()
+            .expect("pipe health registry poisoned")
+            .insert(record.id.to_owned(), initial);
+        let probe = PipeProbe::new(record, pipe_manager.clone(), health.clone());
+        let task = tokio::spawn(probe.run());
+        tasks.insert(record.id.to_owned(), (record.revision, task));
+    }
+}
@@ -1,7 +1,8 @@
 use crate::{
     chmod, create_recursive_directory_with_permission, grpc, open_lock_file, read_yml,
-    remove_directory, resource_error, write_file, write_yml, PathBuilder, Result, PATH_APP,
-    PATH_APP_LOCK, PATH_APP_REGISTER, PATH_CATALOGS, PATH_CATALOGS_LOCK, PATH_CATALOGS_REGISTER,
+    remove_directory, resource_error, write_file, write_yml, Drift, DriftType, PathBuilder, Result,
+    PATH_APP, PATH_APP_LOCK, PATH_APP_REGISTER, PATH_CATALOGS, PATH_CATALOGS_LOCK,
+    PATH_CATALOGS_REGISTER,
 };
 use fslock::LockFile;
 use pipebuilder_common::api::{
@@ -11,7 +12,7 @@ use pipebuilder_common::api::{
 use serde::{Deserialize, Serialize};
 use std::{
     fmt::Display,
-    fs::canonicalize,
+    fs::{canonicalize, read_dir},
     path::{Path, PathBuf},
 };
 use tracing::warn;
@@ -407,6 +408,130 @@ impl RepositoryManager {
         self.do_check_app_registered(desc)
     }
 
+    // resolve app descriptor from path '<app directory>/<namespace>/<id>/<version>/app'
+    pub(crate) fn resolve_app_path(&self, path: &Path) -> Option<AppDescriptor> {
+        let (namespace, id, version) =
+            resolve_resource_path(self.app_directory.as_path(), path, PATH_APP)?;
+        Some(
+            AppDescriptor::builder()
+                .namespace(namespace)
+                .id(id)
+                .version(version)
+                .build(),
+        )
+    }
+
+    // resolve catalogs descriptor from path '<catalogs directory>/<namespace>/<id>/<version>/catalogs'
+    pub(crate) fn resolve_catalogs_path(&self, path: &Path) -> Option<CatalogsDescriptor> {
+        let (namespace, id, version) =
+            resolve_resource_path(self.catalogs_directory.as_path(), path, PATH_CATALOGS)?;
+        Some(
+            CatalogsDescriptor::builder()
+                .namespace(namespace)
+                .id(id)
+                .version(version)
+                .build(),
+        )
+    }
+
+    // registered apps without file and app files without register entry,
+    // repair by deregister missing apps and register found apps
+    pub(crate) fn diagnose_apps(&self, repair: bool) -> Result<Vec<Drift>> {
+        let mut lock_file = self.open_app_lock()?;
+        lock_file.lock()?;
+        let apps = self.do_read_app_register()?;
+        let mut drifts: Vec<Drift> = vec![];
+        for desc in apps.iter() {
+            let path = PathBuilder::default()
+                .push(self.app_directory.as_path())
+                .push(desc.namespace.as_str())
+                .push(desc.id.as_str())
+                .push(desc.version.to_string())
+                .push(PATH_APP)
+                .build();
+            if path.as_path().exists() {
+                continue;
+            }
+            let mut drift = Drift::new(
+                DriftType::MissingApp,
+                desc.to_string(),
+                format!("app '{}' not found", path.display()),
+            );
+            if repair {
+                drift.repair(|| self.do_deregister_app(desc));
+            }
+            drifts.push(drift);
+        }
+        for path in list_resource_paths(self.app_directory.as_path(), PATH_APP)? {
+            let desc = match self.resolve_app_path(path.as_path()) {
+                Some(desc) => desc,
+                None => continue,
+            };
+            if apps.contains(&desc) {
+                continue;
+            }
+            let mut drift = Drift::new(
+                DriftType::UnregisteredApp,
+                desc.to_string(),
+                format!("app '{}' not registered", path.display()),
+            );
+            if repair {
+                drift.repair(|| self.do_register_app(&desc));
+            }
+            drifts.push(drift);
+        }
+        Ok(drifts)
+    }
+
+    // registered catalogs without files and catalogs files without register entry,
+    // repair by deregister missing catalogs and register found catalogs
+    pub(crate) fn diagnose_catalogs(&self, repair: bool) -> Result<Vec<Drift>> {
+        let mut lock_file = self.open_catalogs_lock()?;
+        lock_file.lock()?;
+        let catalogs = self.do_read_catalogs_register()?;
+        let mut drifts: Vec<Drift> = vec![];
+        for desc in catalogs.iter() {
+            let path = PathBuilder::default()
+                .push(self.catalogs_directory.as_path())
+                .push(desc.namespace.as_str())
+                .push(desc.id.as_str())
+                .push(desc.version.to_string())
+                .push(PATH_CATALOGS)
+                .build();
+            if path.as_path().exists() {
+                continue;
+            }
+            let mut drift = Drift::new(
+                DriftType::MissingCatalogs,
+                desc.to_string(),
+                format!("catalogs '{}' not found", path.display()),
+            );
+            if repair {
+                drift.repair(|| self.do_deregister_catalogs(desc));
+            }
+            drifts.push(drift);
+        }
+        for path in list_resource_paths(self.catalogs_directory.as_path(), PATH_CATALOGS)? {
+            let desc = match self.resolve_catalogs_path(path.as_path()) {
+                Some(desc) => desc,
+                None => continue,
+            };
+            if catalogs.contains(&desc) {
+                continue;
+            }
+            let mut drift = Drift::new(
+                DriftType::UnregisteredCatalogs,
+                desc.to_string(),
+                format!("catalogs '{}' not registered", path.display()),
+            );
+            if repair {
+                drift.repair(|| self.do_register_catalogs(&desc));
+            }
+            drifts.push(drift);
+        }
+        Ok(drifts)
+    }
+
     async fn do_pull_app(&self, desc: &AppDescriptor) -> Result<Vec<u8>> {
         let request = GetAppRequest {
             namespace: desc.namespace.clone(),
@@ -674,3 +799,46 @@ impl RepositoryManager {
         }
     }
 }
+
+// resolve (namespace, id, version) from '<directory>/<namespace>/<id>/<version>/<file>'
+fn resolve_resource_path(
+    directory: &Path,
+    path: &Path,
+    file: &str,
+) -> Option<(String, String, u64)> {
+    let path = path.strip_prefix(directory).ok()?;
+    let components: Vec<&str> = path
+        .components()
+        .map(|component| component.as_os_str().to_str())
+        .collect::<Option<Vec<&str>>>()?;
+    match components.as_slice() {
+        [namespace, id, version, name] if *name == file => {
+            let version: u64 = version.parse().ok()?;
+            Some((namespace.to_string(), id.to_string(), version))
+        }
+        _ => None,
+    }
+}
+
+// existing '<directory>/<namespace>/<id>/<version>/<file>'
+fn list_resource_paths(directory: &Path, file: &str) -> Result<Vec<PathBuf>> {
+    let mut paths: Vec<PathBuf> = vec![directory.to_path_buf()];
+    // namespace, id, version
+    for _ in 0..3 {
+        let mut children: Vec<PathBuf> = vec![];
+        for path in paths {
+            for entry in read_dir(path.as_path())? {
+                let path = entry?.path();
+                if path.is_dir() {
+                    children.push(path);
+                }
+            }
+        }
+        paths = children;
+    }
+    Ok(paths
+        .into_iter()
+        .map(|path| path.join(file))
+        .filter(|path| path.exists())
+        .collect())
+}
@@ -0,0 +1,112 @@
+use crate::{grpc, signal_name};
+use serde::{Deserialize, Serialize};
+
+// exit reason of CLD_* code, i.e 1 exited, 2 killed, 3 dumped
+// https://man7.org/linux/man-pages/man2/sigaction.2.html
+pub(crate) fn exit_reason(code: i32) -> Option<String> {
+    match code {
+        1 => Some(String::from("exited")),
+        2 => Some(String::from("killed")),
+        3 => Some(String::from("dumped")),
+        _ => None,
+    }
+}
+
+// completed run of oneshot pipe
+#[derive(Clone, Deserialize, Serialize)]
+pub struct PipeRun {
+    // microseconds since epoch
+    pub started_at: u64,
+    pub ended_at: u64,
+    // exit code or signal number of main process
+    pub exit_code: i32,
+    // exited, killed or dumped
+    pub exit_reason: Option<String>,
+    // final state, i.e inactive / dead, failed / failed, or active / exited if remain after exit
+    pub active_state: String,
+    pub sub_state: String,
+}
+
+impl PipeRun {
+    // main process exited with zero code
+    pub fn is_succeeded(&self) -> bool {
+        self.exit_reason.as_deref() == Some("exited") && self.exit_code == 0
+    }
+}
+
+impl From<PipeRun> for grpc::daemon::PipeRun {
+    fn from(origin: PipeRun) -> Self {
+        let succeeded = origin.is_succeeded();
+        grpc::daemon::PipeRun {
+            started_at: origin.started_at,
+            ended_at: origin.ended_at,
+            exit_code: origin.exit_code,
+            exit_reason: origin.exit_reason,
+            active_state: origin.active_state,
+            sub_state: origin.sub_state,
+            succeeded,
+        }
+    }
+}
+
+// exit of main process after pipe stopped
+pub struct PipeExit {
+    // exit code or signal number of main process
+    pub exit_code: i32,
+    // exited, killed or dumped
+    pub exit_reason: Option<String>,
+    // service result, i.e success, exit-code, signal, timeout
+    pub result: String,
+}
+
+impl PipeExit {
+    // main process exited or terminated by kill signal before stop timeout
+    pub fn is_clean(&self) -> bool {
+        self.result == "success"
+    }
+
+    pub fn signal(&self) -> Option<String> {
+        match self.exit_reason.as_deref() {
+            Some("killed") | Some("dumped") => signal_name(self.exit_code),
+            _ => None,
+        }
+    }
+
+    // main process killed with SIGKILL, i.e final kill signal after stop timeout
+    pub fn is_sigkilled(&self) -> bool {
+        self.signal().as_deref() == Some("SIGKILL")
+    }
+}
+
+impl From<PipeExit> for grpc::daemon::PipeExit {
+    fn from(origin: PipeExit) -> Self {
+        let clean = origin.is_clean();
+        let sigkilled = origin.is_sigkilled();
+        let signal = origin.signal();
+        grpc::daemon::PipeExit {
+            exit_code: origin.exit_code,
+            exit_reason: origin.exit_reason,
+            signal,
+            result: origin.result,
+            clean,
+            sigkilled,
+        }
+    }
+}
+
+#[derive(Default, Deserialize, Serialize)]
+pub(crate) struct PipeRunHistory {
+    // oldest first
+    pub runs: Vec<PipeRun>,
+}
+
+impl PipeRunHistory {
+    // append run and drop oldest runs beyond capacity
+    pub(crate) fn push(&mut self, run: PipeRun, capacity: usize) {
+        self.runs.push(run);
+        if self.runs.len() > capacity {
+            let overflow = self.runs.len() - capacity;
+            self.runs.drain(..overflow);
+        }
+    }
+}
@@ -0,0 +1,139 @@
+use crate::{pipe_error, Error, PipeOperation, Result};
+use std::{fmt::Display, str::FromStr};
+
+// standard linux signals, index is the signal number
+// https://man7.org/linux/man-pages/man7/signal.7.html
+const SIGNAL_NAMES: [&str; 32] = [
+    "",
+    "SIGHUP",
+    "SIGINT",
+    "SIGQUIT",
+    "SIGILL",
+    "SIGTRAP",
+    "SIGABRT",
+    "SIGBUS",
+    "SIGFPE",
+    "SIGKILL",
+    "SIGUSR1",
+    "SIGSEGV",
+    "SIGUSR2",
+    "SIGPIPE",
+    "SIGALRM",
+    "SIGTERM",
+    "SIGSTKFLT",
+    "SIGCHLD",
+    "SIGCONT",
+    "SIGSTOP",
+    "SIGTSTP",
+    "SIGTTIN",
+    "SIGTTOU",
+    "SIGURG",
+    "SIGXCPU",
+    "SIGXFSZ",
+    "SIGVTALRM",
+    "SIGPROF",
+    "SIGWINCH",
+    "SIGIO",
+    "SIGPWR",
+    "SIGSYS",
+];
+const SIGRTMIN: i32 = 34;
+const SIGRTMAX: i32 = 64;
+
+// signal number of name or number, i.e 'SIGUSR1', 'usr1', '10', 'SIGRTMIN+1'
+pub fn parse_signal(signal: &str) -> Result<i32> {
+    let signal = signal.trim();
+    let number = match signal.parse::<i32>() {
+        Ok(number) => Some(number),
+        Err(_) => {
+            let name = signal.to_ascii_uppercase();
+            let name = match name.starts_with("SIG") {
+                true => name,
+                false => format!("SIG{}", name),
+            };
+            parse_signal_name(name.as_str())
+        }
+    };
+    match number {
+        Some(number) if is_valid_signal_number(number) => Ok(number),
+        _ => Err(pipe_error(
+            PipeOperation::Validate,
+            format!("invalid signal '{}'", signal),
+        )),
+    }
+}
+
+// signal name of number, i.e 'SIGKILL' for 9, 'SIGRTMIN+1' for 35
+pub fn signal_name(number: i32) -> Option<String> {
+    if (SIGRTMIN..=SIGRTMAX).contains(&number) {
+        return match number - SIGRTMIN {
+            0 => Some(String::from("SIGRTMIN")),
+            offset => Some(format!("SIGRTMIN+{}", offset)),
+        };
+    }
+    match usize::try_from(number) {
+        Ok(number) if (1..SIGNAL_NAMES.len()).contains(&number) => {
+            Some(SIGNAL_NAMES[number].to_owned())
+        }
+        _ => None,
+    }
+}
+
+fn parse_signal_name(name: &str) -> Option<i32> {
+    if let Some(offset) = name.strip_prefix("SIGRTMIN") {
+        return match offset {
+            "" => Some(SIGRTMIN),
+            offset => Some(SIGRTMIN + offset.strip_prefix('+')?.parse::<i32>().ok()?),
+        };
+    }
+    if let Some(offset) = name.strip_prefix("SIGRTMAX") {
+        return match offset {
+            "" => Some(SIGRTMAX),
+            offset => Some(SIGRTMAX - offset.strip_prefix('-')?.parse::<i32>().ok()?),
+        };
+    }
+    SIGNAL_NAMES
+        .iter()
+        .position(|signal_name| !signal_name.is_empty() && *signal_name == name)
+        .map(|number| number as i32)
+}
+
+fn is_valid_signal_number(number: i32) -> bool {
+    (1..SIGNAL_NAMES.len() as i32).contains(&number) || (SIGRTMIN..=SIGRTMAX).contains(&number)
+}
+
+// processes of unit to signal
+#[derive(Clone, Default)]
+pub enum KillWhom {
+    Main,
+    Control,
+    #[default]
+    All,
+}
+
+impl Display for KillWhom {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let whom = match self {
+            KillWhom::Main => "main",
+            KillWhom::Control => "control",
+            KillWhom::All => "all",
+        };
+        write!(f, "{}", whom)
+    }
+}
+
+impl FromStr for KillWhom {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "main" => Ok(KillWhom::Main),
+            "control" => Ok(KillWhom::Control),
+            "all" => Ok(KillWhom::All),
+            _ => Err(pipe_error(
+                PipeOperation::Validate,
+                format!("invalid kill whom '{}', expect main, control or all", s),
+            )),
+        }
+    }
+}
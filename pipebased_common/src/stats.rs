@@ -0,0 +1,53 @@
+use crate::{grpc, unix_timestamp_micros, Result, ServiceProxyBlocking};
+
+// systemd reports unset accounting value as u64::MAX
+fn accounting_value(value: u64) -> Option<u64> {
+    (value != u64::MAX).then_some(value)
+}
+
+// resource usage sampled from cgroup accounting, undefined if accounting unavailable
+pub struct PipeStats {
+    pub id: String,
+    // microseconds since epoch of sample
+    pub timestamp: u64,
+    pub memory_current: Option<u64>,
+    // cumulative counters
+    pub cpu_usage_nsec: Option<u64>,
+    pub tasks_current: Option<u64>,
+    pub io_read_bytes: Option<u64>,
+    pub io_write_bytes: Option<u64>,
+    pub ip_ingress_bytes: Option<u64>,
+    pub ip_egress_bytes: Option<u64>,
+}
+
+impl PipeStats {
+    pub(crate) fn sample(id: &str, service: &ServiceProxyBlocking<'_>) -> Result<Self> {
+        Ok(PipeStats {
+            id: id.to_owned(),
+            timestamp: unix_timestamp_micros(),
+            memory_current: accounting_value(service.memory_current()?),
+            cpu_usage_nsec: accounting_value(service.cpu_usage_nsec()?),
+            tasks_current: accounting_value(service.tasks_current()?),
+            io_read_bytes: accounting_value(service.io_read_bytes()?),
+            io_write_bytes: accounting_value(service.io_write_bytes()?),
+            ip_ingress_bytes: accounting_value(service.ip_ingress_bytes()?),
+            ip_egress_bytes: accounting_value(service.ip_egress_bytes()?),
+        })
+    }
+}
+
+impl From<PipeStats> for grpc::daemon::PipeStats {
+    fn from(origin: PipeStats) -> Self {
+        grpc::daemon::PipeStats {
+            id: origin.id,
+            timestamp: origin.timestamp,
+            memory_current: origin.memory_current,
+            cpu_usage_nsec: origin.cpu_usage_nsec,
+            tasks_current: origin.tasks_current,
+            io_read_bytes: origin.io_read_bytes,
+            io_write_bytes: origin.io_write_bytes,
+            ip_ingress_bytes: origin.ip_ingress_bytes,
+            ip_egress_bytes: origin.ip_egress_bytes,
+        }
+    }
+}
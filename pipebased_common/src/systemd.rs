@@ -0,0 +1,155 @@
+use crate::Result;
+
+// (type, file_name, destination) of unit file symlink change
+pub type UnitFileChange = (String, String, String);
+
+// systemd manager methods not exposed by systemd_client
+// https://www.freedesktop.org/wiki/Software/systemd/dbus/
+#[zbus::dbus_proxy(
+    interface = "org.freedesktop.systemd1.Manager",
+    default_service = "org.freedesktop.systemd1",
+    default_path = "/org/freedesktop/systemd1"
+)]
+trait Manager {
+    fn reload(&self) -> zbus::Result<()>;
+    fn subscribe(&self) -> zbus::Result<()>;
+    fn get_unit_file_state(&self, file: &str) -> zbus::Result<String>;
+    // returns (carries_install_info, changes)
+    fn enable_unit_files(
+        &self,
+        files: &[&str],
+        runtime: bool,
+        force: bool,
+    ) -> zbus::Result<(bool, Vec<UnitFileChange>)>;
+    fn disable_unit_files(
+        &self,
+        files: &[&str],
+        runtime: bool,
+    ) -> zbus::Result<Vec<UnitFileChange>>;
+    fn reload_or_restart_unit(
+        &self,
+        name: &str,
+        mode: &str,
+    ) -> zbus::Result<zvariant::OwnedObjectPath>;
+    fn kill_unit(&self, name: &str, whom: &str, signal: i32) -> zbus::Result<()>;
+    fn reset_failed_unit(&self, name: &str) -> zbus::Result<()>;
+}
+
+pub fn build_blocking_manager_proxy() -> Result<ManagerProxyBlocking<'static>> {
+    let connection = zbus::blocking::Connection::system()?;
+    let proxy = ManagerProxyBlocking::new(&connection)?;
+    Ok(proxy)
+}
+
+// unit properties not exposed by systemd_client
+#[zbus::dbus_proxy(
+    interface = "org.freedesktop.systemd1.Unit",
+    default_service = "org.freedesktop.systemd1"
+)]
+trait Unit {
+    #[dbus_proxy(property)]
+    fn fragment_path(&self) -> zbus::Result<String>;
+    #[dbus_proxy(property)]
+    fn active_enter_timestamp(&self) -> zbus::Result<u64>;
+    #[dbus_proxy(property)]
+    fn inactive_enter_timestamp(&self) -> zbus::Result<u64>;
+    #[dbus_proxy(property)]
+    fn unit_file_state(&self) -> zbus::Result<String>;
+}
+
+pub fn build_blocking_unit_proxy(
+    object: zvariant::OwnedObjectPath,
+) -> Result<UnitProxyBlocking<'static>> {
+    let connection = zbus::blocking::Connection::system()?;
+    let proxy = UnitProxyBlocking::builder(&connection)
+        .path(object)?
+        .build()?;
+    Ok(proxy)
+}
+
+// (path, argv, ignore_failure, start_realtime, start_monotonic, exit_realtime, exit_monotonic, pid, code, status)
+pub type ExecCommand = (String, Vec<String>, bool, u64, u64, u64, u64, u32, i32, i32);
+
+#[zbus::dbus_proxy(
+    interface = "org.freedesktop.systemd1.Service",
+    default_service = "org.freedesktop.systemd1"
+)]
+trait Service {
+    #[dbus_proxy(property)]
+    fn user(&self) -> zbus::Result<String>;
+    #[dbus_proxy(property)]
+    fn group(&self) -> zbus::Result<String>;
+    #[dbus_proxy(property)]
+    fn environment(&self) -> zbus::Result<Vec<String>>;
+    #[dbus_proxy(property)]
+    fn working_directory(&self) -> zbus::Result<String>;
+    #[dbus_proxy(property)]
+    fn exec_start(&self) -> zbus::Result<Vec<ExecCommand>>;
+    #[dbus_proxy(property, name = "MainPID")]
+    fn main_pid(&self) -> zbus::Result<u32>;
+    #[dbus_proxy(property)]
+    fn exec_main_code(&self) -> zbus::Result<i32>;
+    #[dbus_proxy(property)]
+    fn exec_main_status(&self) -> zbus::Result<i32>;
+    // success, or failure reason such as exit-code, signal, timeout
+    #[dbus_proxy(property, name = "Result")]
+    fn result(&self) -> zbus::Result<String>;
+    #[dbus_proxy(property, name = "NRestarts")]
+    fn n_restarts(&self) -> zbus::Result<u32>;
+    // microseconds since epoch
+    #[dbus_proxy(property)]
+    fn exec_main_start_timestamp(&self) -> zbus::Result<u64>;
+    #[dbus_proxy(property)]
+    fn exec_main_exit_timestamp(&self) -> zbus::Result<u64>;
+    // cgroup accounting, u64::MAX if accounting disabled or unavailable
+    #[dbus_proxy(property, name = "MemoryCurrent")]
+    fn memory_current(&self) -> zbus::Result<u64>;
+    #[dbus_proxy(property, name = "CPUUsageNSec")]
+    fn cpu_usage_nsec(&self) -> zbus::Result<u64>;
+    #[dbus_proxy(property, name = "TasksCurrent")]
+    fn tasks_current(&self) -> zbus::Result<u64>;
+    #[dbus_proxy(property, name = "IOReadBytes")]
+    fn io_read_bytes(&self) -> zbus::Result<u64>;
+    #[dbus_proxy(property, name = "IOWriteBytes")]
+    fn io_write_bytes(&self) -> zbus::Result<u64>;
+    #[dbus_proxy(property, name = "IPIngressBytes")]
+    fn ip_ingress_bytes(&self) -> zbus::Result<u64>;
+    #[dbus_proxy(property, name = "IPEgressBytes")]
+    fn ip_egress_bytes(&self) -> zbus::Result<u64>;
+}
+
+pub fn build_blocking_service_proxy(
+    object: zvariant::OwnedObjectPath,
+) -> Result<ServiceProxyBlocking<'static>> {
+    let connection = zbus::blocking::Connection::system()?;
+    let proxy = ServiceProxyBlocking::builder(&connection)
+        .path(object)?
+        .build()?;
+    Ok(proxy)
+}
+
+#[zbus::dbus_proxy(
+    interface = "org.freedesktop.systemd1.Timer",
+    default_service = "org.freedesktop.systemd1"
+)]
+trait Timer {
+    // microseconds since epoch, 0 if no calendar trigger
+    #[dbus_proxy(property, name = "NextElapseUSecRealtime")]
+    fn next_elapse_usec_realtime(&self) -> zbus::Result<u64>;
+    // microseconds since boot, 0 if no monotonic trigger
+    #[dbus_proxy(property, name = "NextElapseUSecMonotonic")]
+    fn next_elapse_usec_monotonic(&self) -> zbus::Result<u64>;
+    // microseconds since epoch, 0 if never triggered
+    #[dbus_proxy(property, name = "LastTriggerUSec")]
+    fn last_trigger_usec(&self) -> zbus::Result<u64>;
+}
+
+pub fn build_blocking_timer_proxy(
+    object: zvariant::OwnedObjectPath,
+) -> Result<TimerProxyBlocking<'static>> {
+    let connection = zbus::blocking::Connection::system()?;
+    let proxy = TimerProxyBlocking::builder(&connection)
+        .path(object)?
+        .build()?;
+    Ok(proxy)
+}
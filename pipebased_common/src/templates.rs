@@ -0,0 +1,819 @@
+use crate::{
+    signal_name, EnvironmentVariable, KillMode, RestartPolicy, ServiceType, SYSTEMD_PIPE_ID_KEY,
+};
+use std::fmt::Display;
+
+// configuration templates of systemd
+// https://www.freedesktop.org/software/systemd/man/systemd.unit.html
+pub struct UnitConfiguration<'a> {
+    pub description: &'a str,
+    // id of pipe owning the unit
+    pub pipe_id: Option<&'a str>,
+    pub after: Vec<&'a str>,
+    pub requires: Vec<&'a str>,
+    pub wants: Vec<&'a str>,
+    pub start_limit_burst: Option<u32>,
+    pub start_limit_interval_sec: Option<&'a str>,
+}
+
+impl<'a> Display for UnitConfiguration<'a> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "[Unit]")?;
+        writeln!(f, "Description={}", self.description)?;
+        if let Some(pipe_id) = self.pipe_id {
+            writeln!(f, "{}={}", SYSTEMD_PIPE_ID_KEY, pipe_id)?;
+        }
+        if let Some(start_limit_burst) = self.start_limit_burst {
+            writeln!(f, "StartLimitBurst={}", start_limit_burst)?;
+        }
+        if let Some(start_limit_interval_sec) = self.start_limit_interval_sec {
+            writeln!(f, "StartLimitIntervalSec={}", start_limit_interval_sec)?;
+        }
+        if !self.requires.is_empty() {
+            writeln!(f, "Requires={}", self.requires.join(" "))?;
+        }
+        if !self.wants.is_empty() {
+            writeln!(f, "Wants={}", self.wants.join(" "))?;
+        }
+        if !self.after.is_empty() {
+            writeln!(f, "After={}", self.after.join(" "))?;
+        }
+        Ok(())
+    }
+}
+
+impl<'a> UnitConfiguration<'a> {
+    pub fn builder() -> UnitConfigurationBuilder<'a> {
+        UnitConfigurationBuilder::default()
+    }
+}
+
+#[derive(Default)]
+pub struct UnitConfigurationBuilder<'a> {
+    pub description: &'a str,
+    pub pipe_id: Option<&'a str>,
+    pub after: Vec<&'a str>,
+    pub requires: Vec<&'a str>,
+    pub wants: Vec<&'a str>,
+    pub start_limit_burst: Option<u32>,
+    pub start_limit_interval_sec: Option<&'a str>,
+}
+
+impl<'a> UnitConfigurationBuilder<'a> {
+    pub fn description(mut self, description: &'a str) -> Self {
+        self.description = description;
+        self
+    }
+
+    pub fn pipe_id(mut self, pipe_id: &'a str) -> Self {
+        self.pipe_id = Some(pipe_id);
+        self
+    }
+
+    pub fn after(mut self, after: &'a str) -> Self {
+        self.after.push(after);
+        self
+    }
+
+    pub fn requires(mut self, requires: &'a str) -> Self {
+        self.requires.push(requires);
+        self
+    }
+
+    pub fn wants(mut self, wants: &'a str) -> Self {
+        self.wants.push(wants);
+        self
+    }
+
+    pub fn start_limit_burst(mut self, start_limit_burst: u32) -> Self {
+        self.start_limit_burst = Some(start_limit_burst);
+        self
+    }
+
+    pub fn start_limit_interval_sec(mut self, start_limit_interval_sec: &'a str) -> Self {
+        self.start_limit_interval_sec = Some(start_limit_interval_sec);
+        self
+    }
+
+    pub fn build(self) -> UnitConfiguration<'a> {
+        let description = self.description;
+        let pipe_id = self.pipe_id;
+        let after = self.after;
+        let requires = self.requires;
+        let wants = self.wants;
+        let start_limit_burst = self.start_limit_burst;
+        let start_limit_interval_sec = self.start_limit_interval_sec;
+        UnitConfiguration {
+            description,
+            pipe_id,
+            after,
+            requires,
+            wants,
+            start_limit_burst,
+            start_limit_interval_sec,
+        }
+    }
+}
+
+// https://www.freedesktop.org/software/systemd/man/systemd.service.html
+pub struct ServiceConfiguration<'a> {
+    pub exec_start: Vec<&'a str>,
+    pub restart_policy: RestartPolicy,
+    // a unit-less value in seconds, or a time span value such as "5min 20s"
+    pub restart_sec: &'a str,
+    pub working_directory: Option<&'a str>,
+    pub user: Option<&'a str>,
+    pub group: Option<&'a str>,
+    pub envs: Vec<&'a EnvironmentVariable>,
+    pub env_files: Vec<&'a str>,
+    pub slice: Option<&'a str>,
+    // https://www.freedesktop.org/software/systemd/man/systemd.resource-control.html
+    pub memory_max: Option<&'a str>,
+    pub memory_high: Option<&'a str>,
+    pub cpu_quota: Option<&'a str>,
+    pub cpu_weight: Option<u64>,
+    pub tasks_max: Option<&'a str>,
+    pub io_weight: Option<u64>,
+    // https://www.freedesktop.org/software/systemd/man/systemd.exec.html#Sandboxing
+    pub protect_system: Option<&'a str>,
+    pub protect_home: Option<&'a str>,
+    pub private_tmp: Option<bool>,
+    pub no_new_privileges: Option<bool>,
+    pub capability_bounding_set: Option<Vec<&'a str>>,
+    pub read_write_paths: Vec<&'a str>,
+    pub restrict_address_families: Option<Vec<&'a str>>,
+    pub service_type: ServiceType,
+    pub remain_after_exit: Option<bool>,
+    // cpu, memory, io and ip accounting, read as pipe stats
+    pub accounting: bool,
+    // stop timeout and kill signals, escalated to final kill signal on timeout
+    pub timeout_stop_sec: Option<&'a str>,
+    pub kill_signal: Option<i32>,
+    pub kill_mode: Option<KillMode>,
+    pub final_kill_signal: Option<i32>,
+    pub send_sigkill: Option<bool>,
+}
+
+impl<'a> Display for ServiceConfiguration<'a> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "[Service]")?;
+        if let Some(working_directory) = self.working_directory {
+            writeln!(f, "WorkingDirectory={}", working_directory)?;
+        }
+        if let Some(user) = self.user {
+            writeln!(f, "User={}", user)?;
+        }
+        if let Some(group) = self.group {
+            writeln!(f, "Group={}", group)?;
+        }
+        for env in self.envs.iter() {
+            writeln!(f, "Environment={}", quote(env.to_string().as_str()))?;
+        }
+        for env_file in self.env_files.iter() {
+            writeln!(f, "EnvironmentFile={}", escape_specifier(env_file))?;
+        }
+        if let Some(slice) = self.slice {
+            writeln!(f, "Slice={}", slice)?;
+        }
+        if let Some(memory_max) = self.memory_max {
+            writeln!(f, "MemoryMax={}", memory_max)?;
+        }
+        if let Some(memory_high) = self.memory_high {
+            writeln!(f, "MemoryHigh={}", memory_high)?;
+        }
+        if let Some(cpu_quota) = self.cpu_quota {
+            writeln!(f, "CPUQuota={}", cpu_quota)?;
+        }
+        if let Some(cpu_weight) = self.cpu_weight {
+            writeln!(f, "CPUWeight={}", cpu_weight)?;
+        }
+        if let Some(tasks_max) = self.tasks_max {
+            writeln!(f, "TasksMax={}", tasks_max)?;
+        }
+        if let Some(io_weight) = self.io_weight {
+            writeln!(f, "IOWeight={}", io_weight)?;
+        }
+        if self.accounting {
+            writeln!(f, "CPUAccounting=yes")?;
+            writeln!(f, "MemoryAccounting=yes")?;
+            writeln!(f, "IOAccounting=yes")?;
+            writeln!(f, "IPAccounting=yes")?;
+        }
+        if let Some(protect_system) = self.protect_system {
+            writeln!(f, "ProtectSystem={}", protect_system)?;
+        }
+        if let Some(protect_home) = self.protect_home {
+            writeln!(f, "ProtectHome={}", protect_home)?;
+        }
+        if let Some(private_tmp) = self.private_tmp {
+            writeln!(f, "PrivateTmp={}", yes_or_no(private_tmp))?;
+        }
+        if let Some(no_new_privileges) = self.no_new_privileges {
+            writeln!(f, "NoNewPrivileges={}", yes_or_no(no_new_privileges))?;
+        }
+        if let Some(capabilities) = self.capability_bounding_set.as_ref() {
+            // empty value drops all capabilities
+            writeln!(f, "CapabilityBoundingSet={}", capabilities.join(" "))?;
+        }
+        for read_write_path in self.read_write_paths.iter() {
+            writeln!(f, "ReadWritePaths={}", escape_specifier(read_write_path))?;
+        }
+        if let Some(families) = self.restrict_address_families.as_ref() {
+            writeln!(f, "RestrictAddressFamilies={}", families.join(" "))?;
+        }
+        writeln!(f, "Type={}", self.service_type)?;
+        if let Some(remain_after_exit) = self.remain_after_exit {
+            writeln!(f, "RemainAfterExit={}", yes_or_no(remain_after_exit))?;
+        }
+        if let Some(timeout_stop_sec) = self.timeout_stop_sec {
+            writeln!(f, "TimeoutStopSec={}", timeout_stop_sec)?;
+        }
+        if let Some(kill_signal) = self.kill_signal {
+            writeln!(f, "KillSignal={}", signal_directive(kill_signal))?;
+        }
+        if let Some(kill_mode) = self.kill_mode.as_ref() {
+            writeln!(f, "KillMode={}", kill_mode)?;
+        }
+        if let Some(final_kill_signal) = self.final_kill_signal {
+            writeln!(f, "FinalKillSignal={}", signal_directive(final_kill_signal))?;
+        }
+        if let Some(send_sigkill) = self.send_sigkill {
+            writeln!(f, "SendSIGKILL={}", yes_or_no(send_sigkill))?;
+        }
+        writeln!(f, "ExecStart={}", exec_start_directive(&self.exec_start))?;
+        writeln!(f, "Restart={}", self.restart_policy)?;
+        writeln!(f, "RestartSec={}", self.restart_sec)
+    }
+}
+
+impl<'a> ServiceConfiguration<'a> {
+    pub fn builder() -> ServiceConfigurationBuilder<'a> {
+        ServiceConfigurationBuilder::default()
+    }
+}
+
+pub struct ServiceConfigurationBuilder<'a> {
+    pub exec_start: Vec<&'a str>,
+    pub restart_policy: RestartPolicy,
+    pub restart_sec: &'a str,
+    pub working_directory: Option<&'a str>,
+    pub user: Option<&'a str>,
+    pub group: Option<&'a str>,
+    pub envs: Vec<&'a EnvironmentVariable>,
+    pub env_files: Vec<&'a str>,
+    pub slice: Option<&'a str>,
+    pub memory_max: Option<&'a str>,
+    pub memory_high: Option<&'a str>,
+    pub cpu_quota: Option<&'a str>,
+    pub cpu_weight: Option<u64>,
+    pub tasks_max: Option<&'a str>,
+    pub io_weight: Option<u64>,
+    pub protect_system: Option<&'a str>,
+    pub protect_home: Option<&'a str>,
+    pub private_tmp: Option<bool>,
+    pub no_new_privileges: Option<bool>,
+    pub capability_bounding_set: Option<Vec<&'a str>>,
+    pub read_write_paths: Vec<&'a str>,
+    pub restrict_address_families: Option<Vec<&'a str>>,
+    pub service_type: ServiceType,
+    pub remain_after_exit: Option<bool>,
+    pub accounting: bool,
+    pub timeout_stop_sec: Option<&'a str>,
+    pub kill_signal: Option<i32>,
+    pub kill_mode: Option<KillMode>,
+    pub final_kill_signal: Option<i32>,
+    pub send_sigkill: Option<bool>,
+}
+
+impl<'a> Default for ServiceConfigurationBuilder<'a> {
+    fn default() -> Self {
+        ServiceConfigurationBuilder {
+            exec_start: vec![],
+            restart_policy: RestartPolicy::No,
+            restart_sec: "100ms",
+            working_directory: None,
+            user: None,
+            group: None,
+            envs: vec![],
+            env_files: vec![],
+            slice: None,
+            memory_max: None,
+            memory_high: None,
+            cpu_quota: None,
+            cpu_weight: None,
+            tasks_max: None,
+            io_weight: None,
+            protect_system: None,
+            protect_home: None,
+            private_tmp: None,
+            no_new_privileges: None,
+            capability_bounding_set: None,
+            read_write_paths: vec![],
+            restrict_address_families: None,
+            service_type: ServiceType::Simple,
+            remain_after_exit: None,
+            accounting: false,
+            timeout_stop_sec: None,
+            kill_signal: None,
+            kill_mode: None,
+            final_kill_signal: None,
+            send_sigkill: None,
+        }
+    }
+}
+
+impl<'a> ServiceConfigurationBuilder<'a> {
+    pub fn exec_start(mut self, exec_start: Vec<&'a str>) -> Self {
+        self.exec_start = exec_start;
+        self
+    }
+
+    pub fn restart_policy(mut self, restart_policy: RestartPolicy) -> Self {
+        self.restart_policy = restart_policy;
+        self
+    }
+
+    pub fn restart_sec(mut self, restart_sec: &'a str) -> Self {
+        self.restart_sec = restart_sec;
+        self
+    }
+
+    pub fn working_directory(mut self, working_directory: &'a str) -> Self {
+        self.working_directory = Some(working_directory);
+        self
+    }
+
+    pub fn user(mut self, user: &'a str) -> Self {
+        self.user = Some(user);
+        self
+    }
+
+    pub fn group(mut self, group: &'a str) -> Self {
+        self.group = Some(group);
+        self
+    }
+
+    pub fn slice(mut self, slice: &'a str) -> Self {
+        self.slice = Some(slice);
+        self
+    }
+
+    pub fn env(mut self, env: &'a EnvironmentVariable) -> Self {
+        self.envs.push(env);
+        self
+    }
+
+    pub fn env_file(mut self, env_file: &'a str) -> Self {
+        self.env_files.push(env_file);
+        self
+    }
+
+    pub fn memory_max(mut self, memory_max: &'a str) -> Self {
+        self.memory_max = Some(memory_max);
+        self
+    }
+
+    pub fn memory_high(mut self, memory_high: &'a str) -> Self {
+        self.memory_high = Some(memory_high);
+        self
+    }
+
+    pub fn cpu_quota(mut self, cpu_quota: &'a str) -> Self {
+        self.cpu_quota = Some(cpu_quota);
+        self
+    }
+
+    pub fn cpu_weight(mut self, cpu_weight: u64) -> Self {
+        self.cpu_weight = Some(cpu_weight);
+        self
+    }
+
+    pub fn tasks_max(mut self, tasks_max: &'a str) -> Self {
+        self.tasks_max = Some(tasks_max);
+        self
+    }
+
+    pub fn io_weight(mut self, io_weight: u64) -> Self {
+        self.io_weight = Some(io_weight);
+        self
+    }
+
+    pub fn protect_system(mut self, protect_system: &'a str) -> Self {
+        self.protect_system = Some(protect_system);
+        self
+    }
+
+    pub fn protect_home(mut self, protect_home: &'a str) -> Self {
+        self.protect_home = Some(protect_home);
+        self
+    }
+
+    pub fn private_tmp(mut self, private_tmp: bool) -> Self {
+        self.private_tmp = Some(private_tmp);
+        self
+    }
+
+    pub fn no_new_privileges(mut self, no_new_privileges: bool) -> Self {
+        self.no_new_privileges = Some(no_new_privileges);
+        self
+    }
+
+    pub fn capability_bounding_set(mut self, capabilities: Vec<&'a str>) -> Self {
+        self.capability_bounding_set = Some(capabilities);
+        self
+    }
+
+    pub fn read_write_path(mut self, read_write_path: &'a str) -> Self {
+        self.read_write_paths.push(read_write_path);
+        self
+    }
+
+    pub fn restrict_address_families(mut self, families: Vec<&'a str>) -> Self {
+        self.restrict_address_families = Some(families);
+        self
+    }
+
+    pub fn service_type(mut self, service_type: ServiceType) -> Self {
+        self.service_type = service_type;
+        self
+    }
+
+    pub fn remain_after_exit(mut self, remain_after_exit: bool) -> Self {
+        self.remain_after_exit = Some(remain_after_exit);
+        self
+    }
+
+    pub fn accounting(mut self, accounting: bool) -> Self {
+        self.accounting = accounting;
+        self
+    }
+
+    pub fn timeout_stop_sec(mut self, timeout_stop_sec: &'a str) -> Self {
+        self.timeout_stop_sec = Some(timeout_stop_sec);
+        self
+    }
+
+    pub fn kill_signal(mut self, kill_signal: i32) -> Self {
+        self.kill_signal = Some(kill_signal);
+        self
+    }
+
+    pub fn kill_mode(mut self, kill_mode: KillMode) -> Self {
+        self.kill_mode = Some(kill_mode);
+        self
+    }
+
+    pub fn final_kill_signal(mut self, final_kill_signal: i32) -> Self {
+        self.final_kill_signal = Some(final_kill_signal);
+        self
+    }
+
+    pub fn send_sigkill(mut self, send_sigkill: bool) -> Self {
+        self.send_sigkill = Some(send_sigkill);
+        self
+    }
+
+    pub fn build(self) -> ServiceConfiguration<'a> {
+        let exec_start = self.exec_start;
+        let restart_policy = self.restart_policy;
+        let restart_sec = self.restart_sec;
+        let working_directory = self.working_directory;
+        let user = self.user;
+        let group = self.group;
+        let envs = self.envs;
+        let env_files = self.env_files;
+        let slice = self.slice;
+        let memory_max = self.memory_max;
+        let memory_high = self.memory_high;
+        let cpu_quota = self.cpu_quota;
+        let cpu_weight = self.cpu_weight;
+        let tasks_max = self.tasks_max;
+        let io_weight = self.io_weight;
+        let protect_system = self.protect_system;
+        let protect_home = self.protect_home;
+        let private_tmp = self.private_tmp;
+        let no_new_privileges = self.no_new_privileges;
+        let capability_bounding_set = self.capability_bounding_set;
+        let read_write_paths = self.read_write_paths;
+        let restrict_address_families = self.restrict_address_families;
+        let service_type = self.service_type;
+        let remain_after_exit = self.remain_after_exit;
+        let accounting = self.accounting;
+        let timeout_stop_sec = self.timeout_stop_sec;
+        let kill_signal = self.kill_signal;
+        let kill_mode = self.kill_mode;
+        let final_kill_signal = self.final_kill_signal;
+        let send_sigkill = self.send_sigkill;
+        ServiceConfiguration {
+            exec_start,
+            restart_policy,
+            restart_sec,
+            working_directory,
+            user,
+            group,
+            envs,
+            env_files,
+            slice,
+            memory_max,
+            memory_high,
+            cpu_quota,
+            cpu_weight,
+            tasks_max,
+            io_weight,
+            protect_system,
+            protect_home,
+            private_tmp,
+            no_new_privileges,
+            capability_bounding_set,
+            read_write_paths,
+            restrict_address_families,
+            service_type,
+            remain_after_exit,
+            accounting,
+            timeout_stop_sec,
+            kill_signal,
+            kill_mode,
+            final_kill_signal,
+            send_sigkill,
+        }
+    }
+}
+
+pub struct InstallConfiguration<'a> {
+    pub wanted_by: Vec<&'a str>,
+}
+
+impl<'a> Display for InstallConfiguration<'a> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "[Install]")?;
+        writeln!(f, "WantedBy={}", self.wanted_by.join(" "))
+    }
+}
+
+impl<'a> InstallConfiguration<'a> {
+    pub fn builder() -> InstallConfigurationBuilder<'a> {
+        InstallConfigurationBuilder::default()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.wanted_by.is_empty()
+    }
+}
+
+pub struct InstallConfigurationBuilder<'a> {
+    pub wanted_by: Vec<&'a str>,
+}
+
+impl<'a> Default for InstallConfigurationBuilder<'a> {
+    fn default() -> Self {
+        InstallConfigurationBuilder {
+            // https://unix.stackexchange.com/questions/404667/systemd-service-what-is-multi-user-target
+            wanted_by: vec!["multi-user.target"],
+        }
+    }
+}
+
+impl<'a> InstallConfigurationBuilder<'a> {
+    pub fn wanted_by(mut self, wanted_by: &'a str) -> Self {
+        self.wanted_by.push(wanted_by);
+        self
+    }
+
+    pub fn build(self) -> InstallConfiguration<'a> {
+        let wanted_by = self.wanted_by;
+        InstallConfiguration { wanted_by }
+    }
+}
+
+pub struct ServiceUnitConfiguration<'a> {
+    pub unit: UnitConfiguration<'a>,
+    pub service: ServiceConfiguration<'a>,
+    pub install: InstallConfiguration<'a>,
+}
+
+impl<'a> Display for ServiceUnitConfiguration<'a> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}\n{}", self.unit, self.service)?;
+        // section omitted if nothing to install
+        if !self.install.is_empty() {
+            write!(f, "\n{}", self.install)?;
+        }
+        Ok(())
+    }
+}
+
+impl<'a> ServiceUnitConfiguration<'a> {
+    pub fn builder() -> ServiceUnitConfigurationBuilder<'a> {
+        ServiceUnitConfigurationBuilder::default()
+    }
+}
+
+#[derive(Default)]
+pub struct ServiceUnitConfigurationBuilder<'a> {
+    pub unit: UnitConfigurationBuilder<'a>,
+    pub service: ServiceConfigurationBuilder<'a>,
+    pub install: InstallConfigurationBuilder<'a>,
+}
+
+impl<'a> ServiceUnitConfigurationBuilder<'a> {
+    pub fn unit(mut self, unit: UnitConfigurationBuilder<'a>) -> Self {
+        self.unit = unit;
+        self
+    }
+
+    pub fn service(mut self, service: ServiceConfigurationBuilder<'a>) -> Self {
+        self.service = service;
+        self
+    }
+
+    pub fn install(mut self, install: InstallConfigurationBuilder<'a>) -> Self {
+        self.install = install;
+        self
+    }
+
+    pub fn build(self) -> ServiceUnitConfiguration<'a> {
+        let unit = self.unit.build();
+        let service = self.service.build();
+        let install = self.install.build();
+        ServiceUnitConfiguration {
+            unit,
+            service,
+            install,
+        }
+    }
+}
+
+// https://www.freedesktop.org/software/systemd/man/systemd.timer.html
+pub struct TimerConfiguration<'a> {
+    pub on_calendar: Option<&'a str>,
+    pub on_boot_sec: Option<&'a str>,
+    pub on_unit_active_sec: Option<&'a str>,
+}
+
+impl<'a> Display for TimerConfiguration<'a> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "[Timer]")?;
+        if let Some(on_calendar) = self.on_calendar {
+            writeln!(f, "OnCalendar={}", on_calendar)?;
+            // trigger missed runs when system was down
+            writeln!(f, "Persistent=yes")?;
+        }
+        if let Some(on_boot_sec) = self.on_boot_sec {
+            writeln!(f, "OnBootSec={}", on_boot_sec)?;
+        }
+        if let Some(on_unit_active_sec) = self.on_unit_active_sec {
+            writeln!(f, "OnUnitActiveSec={}", on_unit_active_sec)?;
+        }
+        Ok(())
+    }
+}
+
+impl<'a> TimerConfiguration<'a> {
+    pub fn builder() -> TimerConfigurationBuilder<'a> {
+        TimerConfigurationBuilder::default()
+    }
+}
+
+#[derive(Default)]
+pub struct TimerConfigurationBuilder<'a> {
+    pub on_calendar: Option<&'a str>,
+    pub on_boot_sec: Option<&'a str>,
+    pub on_unit_active_sec: Option<&'a str>,
+}
+
+impl<'a> TimerConfigurationBuilder<'a> {
+    pub fn on_calendar(mut self, on_calendar: &'a str) -> Self {
+        self.on_calendar = Some(on_calendar);
+        self
+    }
+
+    pub fn on_boot_sec(mut self, on_boot_sec: &'a str) -> Self {
+        self.on_boot_sec = Some(on_boot_sec);
+        self
+    }
+
+    pub fn on_unit_active_sec(mut self, on_unit_active_sec: &'a str) -> Self {
+        self.on_unit_active_sec = Some(on_unit_active_sec);
+        self
+    }
+
+    pub fn build(self) -> TimerConfiguration<'a> {
+        let on_calendar = self.on_calendar;
+        let on_boot_sec = self.on_boot_sec;
+        let on_unit_active_sec = self.on_unit_active_sec;
+        TimerConfiguration {
+            on_calendar,
+            on_boot_sec,
+            on_unit_active_sec,
+        }
+    }
+}
+
+pub struct TimerUnitConfiguration<'a> {
+    pub unit: UnitConfiguration<'a>,
+    pub timer: TimerConfiguration<'a>,
+    pub install: InstallConfiguration<'a>,
+}
+
+impl<'a> Display for TimerUnitConfiguration<'a> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}\n{}", self.unit, self.timer)?;
+        if !self.install.is_empty() {
+            write!(f, "\n{}", self.install)?;
+        }
+        Ok(())
+    }
+}
+
+impl<'a> TimerUnitConfiguration<'a> {
+    pub fn builder() -> TimerUnitConfigurationBuilder<'a> {
+        TimerUnitConfigurationBuilder::default()
+    }
+}
+
+pub struct TimerUnitConfigurationBuilder<'a> {
+    pub unit: UnitConfigurationBuilder<'a>,
+    pub timer: TimerConfigurationBuilder<'a>,
+    pub install: InstallConfigurationBuilder<'a>,
+}
+
+impl<'a> Default for TimerUnitConfigurationBuilder<'a> {
+    fn default() -> Self {
+        TimerUnitConfigurationBuilder {
+            unit: UnitConfigurationBuilder::default(),
+            timer: TimerConfigurationBuilder::default(),
+            // timers are enabled into timers.target instead of multi-user.target
+            install: InstallConfigurationBuilder {
+                wanted_by: vec!["timers.target"],
+            },
+        }
+    }
+}
+
+impl<'a> TimerUnitConfigurationBuilder<'a> {
+    pub fn unit(mut self, unit: UnitConfigurationBuilder<'a>) -> Self {
+        self.unit = unit;
+        self
+    }
+
+    pub fn timer(mut self, timer: TimerConfigurationBuilder<'a>) -> Self {
+        self.timer = timer;
+        self
+    }
+
+    pub fn build(self) -> TimerUnitConfiguration<'a> {
+        let unit = self.unit.build();
+        let timer = self.timer.build();
+        let install = self.install.build();
+        TimerUnitConfiguration {
+            unit,
+            timer,
+            install,
+        }
+    }
+}
+
+fn yes_or_no(value: bool) -> &'static str {
+    match value {
+        true => "yes",
+        false => "no",
+    }
+}
+
+// signal name if known, number otherwise
+fn signal_directive(signal: i32) -> String {
+    signal_name(signal).unwrap_or_else(|| signal.to_string())
+}
+
+// '%' starts a specifier in most unit settings, escape it to keep literal
+fn escape_specifier(value: &str) -> String {
+    value.replace('%', "%%")
+}
+
+// quoted arguments with specifiers and variables escaped
+// https://www.freedesktop.org/software/systemd/man/systemd.service.html#Command%20lines
+fn exec_start_directive(exec_start: &[&str]) -> String {
+    exec_start
+        .iter()
+        .map(|arg| quote(arg).replace('$', "$$"))
+        .collect::<Vec<String>>()
+        .join(" ")
+}
+
+// https://www.freedesktop.org/software/systemd/man/systemd.syntax.html#Quoting
+fn quote(value: &str) -> String {
+    let mut buffer = String::with_capacity(value.len() + 2);
+    buffer.push('"');
+    for c in value.chars() {
+        match c {
+            '\\' => buffer.push_str(r"\\"),
+            '"' => buffer.push_str(r#"\""#),
+            '\n' => buffer.push_str(r"\n"),
+            '\t' => buffer.push_str(r"\t"),
+            '%' => buffer.push_str("%%"),
+            c => buffer.push(c),
+        }
+    }
+    buffer.push('"');
+    buffer
+}
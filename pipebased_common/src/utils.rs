@@ -11,6 +11,7 @@ use std::{
     io::{BufWriter, Write},
     path::{Path, PathBuf},
     process::Command,
+    time::{SystemTime, UNIX_EPOCH},
 };
 
 pub fn generate_random_id() -> String {
@@ -25,6 +26,34 @@ pub fn generate_random_id() -> String {
 }
 
 // fs ops
+// seconds since epoch
+pub fn unix_timestamp_secs() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_secs())
+        .unwrap_or_default()
+}
+
+// microseconds since epoch
+pub fn unix_timestamp_micros() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_micros() as u64)
+        .unwrap_or_default()
+}
+
+// microseconds since boot to microseconds since epoch, boot time derived from /proc/uptime
+pub fn monotonic_to_unix_timestamp_micros(monotonic: u64) -> Option<u64> {
+    let uptime = fs::read_to_string("/proc/uptime").ok()?;
+    let uptime: f64 = uptime.split_whitespace().next()?.parse().ok()?;
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .ok()?
+        .as_micros() as u64;
+    let boot = now.checked_sub((uptime * 1_000_000.0) as u64)?;
+    Some(boot + monotonic)
+}
+
 pub fn create_directory<P>(path: P) -> Result<()>
 where
     P: AsRef<Path>,
@@ -41,6 +70,22 @@ where
     Ok(())
 }
 
+pub fn remove_file<P>(path: P) -> Result<()>
+where
+    P: AsRef<Path>,
+{
+    fs::remove_file(path)?;
+    Ok(())
+}
+
+pub fn read_link<P>(path: P) -> Result<PathBuf>
+where
+    P: AsRef<Path>,
+{
+    let path = fs::read_link(path)?;
+    Ok(path)
+}
+
 pub fn create_recursive_directory_with_permission<P>(paths: &[P], permission: &str) -> Result<()>
 where
     P: AsRef<Path>,
@@ -198,6 +243,42 @@ where
     }
 }
 
+// systemd time span
+// https://www.freedesktop.org/software/systemd/man/systemd.time.html
+const TIME_SPAN_UNITS: &[&str] = &[
+    "usec", "us", "µs", "msec", "ms", "seconds", "second", "sec", "s", "minutes", "minute", "min",
+    "m", "hours", "hour", "hr", "h", "days", "day", "d", "weeks", "week", "w", "months", "month",
+    "M", "years", "year", "y",
+];
+
+// a unit-less value in seconds, or a time span value such as "5min 20s"
+pub fn is_valid_time_span(span: &str) -> bool {
+    let span = span.trim();
+    if span == "infinity" {
+        return true;
+    }
+    let mut rest = span;
+    let mut segments: usize = 0;
+    while !rest.is_empty() {
+        let digits = rest
+            .find(|c: char| !(c.is_ascii_digit() || c == '.'))
+            .unwrap_or(rest.len());
+        if digits == 0 || rest[..digits].parse::<f64>().is_err() {
+            return false;
+        }
+        rest = rest[digits..].trim_start();
+        let unit = rest
+            .find(|c: char| c.is_ascii_digit() || c.is_whitespace())
+            .unwrap_or(rest.len());
+        if unit > 0 && !TIME_SPAN_UNITS.contains(&&rest[..unit]) {
+            return false;
+        }
+        rest = rest[unit..].trim_start();
+        segments += 1;
+    }
+    segments > 0
+}
+
 // path builder
 #[derive(Default)]
 pub struct PathBuilder {
@@ -0,0 +1,338 @@
+use crate::{
+    build_blocking_manager_proxy, build_blocking_service_proxy, exit_reason, grpc,
+    unix_timestamp_micros, PipeManager, Result, SIGNAL_JOB_REMOVED, SYSTEMD_SERVICE,
+    SYSTEMD_SERVICE_UNIT_SUFFIX,
+};
+use std::{collections::HashMap, fmt::Display, time::SystemTime};
+use tokio::sync::broadcast;
+use tracing::{error, info, warn};
+use zvariant::OwnedValue;
+
+const SYSTEMD_UNIT_INTERFACE: &str = "org.freedesktop.systemd1.Unit";
+const SYSTEMD_UNIT_PATH_PREFIX: &str = "/org/freedesktop/systemd1/unit/";
+const SIGNAL_PROPERTIES_CHANGED: &str = "PropertiesChanged";
+const PROPERTY_ACTIVE_STATE: &str = "ActiveState";
+const PROPERTY_SUB_STATE: &str = "SubState";
+// sub state of service waiting for automatic restart
+const SUB_STATE_AUTO_RESTART: &str = "auto-restart";
+// job results considered as failure
+// https://www.freedesktop.org/wiki/Software/systemd/dbus/
+const JOB_FAILED_RESULTS: &[&str] = &["failed", "timeout", "dependency"];
+
+#[derive(Clone, Debug)]
+pub enum PipeEventType {
+    Started,
+    Stopped,
+    Failed,
+    Restarted,
+}
+
+impl Display for PipeEventType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let event_type = match self {
+            PipeEventType::Started => "started",
+            PipeEventType::Stopped => "stopped",
+            PipeEventType::Failed => "failed",
+            PipeEventType::Restarted => "restarted",
+        };
+        write!(f, "{}", event_type)
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct PipeEvent {
+    pub id: String,
+    pub event_type: PipeEventType,
+    // microseconds since epoch
+    pub timestamp: u64,
+    pub active_state: String,
+    pub sub_state: String,
+    // main process exit code or signal number, undefined for started
+    pub exit_code: Option<i32>,
+    // main process exit reason: exited, killed, dumped
+    pub exit_reason: Option<String>,
+    // result of failed job
+    pub job_result: Option<String>,
+}
+
+impl From<PipeEvent> for grpc::daemon::PipeEvent {
+    fn from(origin: PipeEvent) -> Self {
+        grpc::daemon::PipeEvent {
+            id: origin.id,
+            event_type: origin.event_type.to_string(),
+            timestamp: origin.timestamp,
+            active_state: origin.active_state,
+            sub_state: origin.sub_state,
+            exit_code: origin.exit_code,
+            exit_reason: origin.exit_reason,
+            job_result: origin.job_result,
+        }
+    }
+}
+
+// https://www.freedesktop.org/software/systemd/man/sd_bus_path_encode.html
+fn unescape_bus_path(escaped: &str) -> Option<String> {
+    let bytes = escaped.as_bytes();
+    let mut buffer: Vec<u8> = Vec::with_capacity(bytes.len());
+    let mut i: usize = 0;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'_' => {
+                let hex = escaped.get(i + 1..i + 3)?;
+                buffer.push(u8::from_str_radix(hex, 16).ok()?);
+                i += 3;
+            }
+            byte => {
+                buffer.push(byte);
+                i += 1;
+            }
+        }
+    }
+    String::from_utf8(buffer).ok()
+}
+
+// unit name of unit path, i.e '/org/freedesktop/systemd1/unit/foo_2eservice' -> 'foo.service'
+fn unit_path_to_unit_name(path: &str) -> Option<String> {
+    unescape_bus_path(path.strip_prefix(SYSTEMD_UNIT_PATH_PREFIX)?)
+}
+
+fn owned_value_to_string(value: &OwnedValue) -> Option<String> {
+    let value: &str = value.downcast_ref()?;
+    Some(value.to_owned())
+}
+
+#[derive(Default)]
+struct PipeUnitState {
+    active_state: String,
+    sub_state: String,
+    // restart observed, emit restarted once active again
+    restarting: bool,
+}
+
+// track pipe unit states and derive events from systemd signals
+pub(crate) struct PipeWatcher {
+    pipe_manager: PipeManager,
+    // unit name -> pipe id, reloaded once pipe register modified
+    units: HashMap<String, String>,
+    register_modified: Option<SystemTime>,
+    states: HashMap<String, PipeUnitState>,
+    tx: broadcast::Sender<PipeEvent>,
+}
+
+impl PipeWatcher {
+    pub(crate) fn new(pipe_manager: PipeManager, tx: broadcast::Sender<PipeEvent>) -> Self {
+        PipeWatcher {
+            pipe_manager,
+            units: HashMap::new(),
+            register_modified: None,
+            states: HashMap::new(),
+            tx,
+        }
+    }
+
+    // block and watch until connection closed
+    pub(crate) fn run(mut self) -> Result<()> {
+        let connection = zbus::blocking::Connection::system()?;
+        // systemd emits unit signals only if any client subscribed
+        build_blocking_manager_proxy()?.subscribe()?;
+        let dbus = zbus::blocking::fdo::DBusProxy::new(&connection)?;
+        for member in [SIGNAL_PROPERTIES_CHANGED, SIGNAL_JOB_REMOVED] {
+            let rule = format!(
+                "type='signal',sender='{}',member='{}'",
+                SYSTEMD_SERVICE, member
+            );
+            dbus.add_match(rule.as_str()).map_err(zbus::Error::from)?;
+        }
+        info!("watch pipe units ...");
+        for message in zbus::blocking::MessageIterator::from(&connection) {
+            let message = message?;
+            let member = match message.member() {
+                Some(member) => member.to_string(),
+                None => continue,
+            };
+            let result = match member.as_str() {
+                SIGNAL_PROPERTIES_CHANGED => self.on_properties_changed(&message),
+                SIGNAL_JOB_REMOVED => self.on_job_removed(&message),
+                _ => continue,
+            };
+            if let Err(err) = result {
+                warn!("handle systemd signal failed, error: {:#?}", err);
+            }
+        }
+        Ok(())
+    }
+
+    // pipe id of registered pipe unit
+    fn resolve_pipe_id(&mut self, unit_name: &str) -> Option<String> {
+        // signals of all units on host, pipes run as services
+        if !unit_name.ends_with(SYSTEMD_SERVICE_UNIT_SUFFIX) {
+            return None;
+        }
+        if let Err(err) = self.reload_units() {
+            error!("read pipe register failed, error: {:#?}", err);
+        }
+        self.units.get(unit_name).cloned()
+    }
+
+    // reload pipe units if register modified, evict states of removed pipes
+    fn reload_units(&mut self) -> Result<()> {
+        let register_modified = self.pipe_manager.pipe_register_modified()?;
+        // no pipe registered if register not created yet
+        if register_modified == self.register_modified {
+            return Ok(());
+        }
+        self.units = self
+            .pipe_manager
+            .list_pipe_units()?
+            .into_iter()
+            .map(|(id, unit_name)| (unit_name, id))
+            .collect();
+        self.register_modified = register_modified;
+        let units = &self.units;
+        self.states
+            .retain(|id, _| units.values().any(|unit_id| unit_id == id));
+        Ok(())
+    }
+
+    fn on_properties_changed(&mut self, message: &zbus::Message) -> Result<()> {
+        let unit_name = match message
+            .path()
+            .and_then(|path| unit_path_to_unit_name(path.as_str()))
+        {
+            Some(unit_name) => unit_name,
+            None => return Ok(()),
+        };
+        let (interface, changed, _): (String, HashMap<String, OwnedValue>, Vec<String>) =
+            message.body()?;
+        if interface != SYSTEMD_UNIT_INTERFACE {
+            return Ok(());
+        }
+        let active_state = changed
+            .get(PROPERTY_ACTIVE_STATE)
+            .and_then(owned_value_to_string);
+        let sub_state = changed
+            .get(PROPERTY_SUB_STATE)
+            .and_then(owned_value_to_string);
+        if active_state.is_none() && sub_state.is_none() {
+            return Ok(());
+        }
+        let id = match self.resolve_pipe_id(unit_name.as_str()) {
+            Some(id) => id,
+            None => return Ok(()),
+        };
+        let state = self.states.entry(id.to_owned()).or_default();
+        let previous_active_state = std::mem::take(&mut state.active_state);
+        state.active_state = active_state.unwrap_or_else(|| previous_active_state.to_owned());
+        if let Some(sub_state) = sub_state {
+            state.sub_state = sub_state;
+        }
+        // auto restart, or manual restart which skips inactive state
+        if state.sub_state == SUB_STATE_AUTO_RESTART
+            || (previous_active_state == "deactivating" && state.active_state == "activating")
+        {
+            state.restarting = true;
+        }
+        if previous_active_state == state.active_state {
+            return Ok(());
+        }
+        // oneshot run completed, recorded in run history
+        if previous_active_state == "activating"
+            && matches!(
+                state.active_state.as_str(),
+                "active" | "inactive" | "failed"
+            )
+        {
+            if let Err(err) = self.pipe_manager.record_run(id.as_str()) {
+                warn!(
+                    pipe_id = id.as_str(),
+                    "record pipe run failed, error: {:#?}", err
+                );
+            }
+        }
+        let event_type = match state.active_state.as_str() {
+            "active" if state.restarting => {
+                state.restarting = false;
+                PipeEventType::Restarted
+            }
+            "active" => PipeEventType::Started,
+            "inactive" if !state.restarting => PipeEventType::Stopped,
+            "failed" => PipeEventType::Failed,
+            _ => return Ok(()),
+        };
+        let active_state = state.active_state.to_owned();
+        let sub_state = state.sub_state.to_owned();
+        let mut event = PipeEvent {
+            id,
+            event_type,
+            timestamp: unix_timestamp_micros(),
+            active_state,
+            sub_state,
+            exit_code: None,
+            exit_reason: None,
+            job_result: None,
+        };
+        if !matches!(event.event_type, PipeEventType::Started) {
+            Self::fill_exit_status(&mut event, message);
+        }
+        self.publish(event);
+        Ok(())
+    }
+
+    fn on_job_removed(&mut self, message: &zbus::Message) -> Result<()> {
+        let (_, _, unit_name, result): (u32, zvariant::OwnedObjectPath, String, String) =
+            message.body()?;
+        if !JOB_FAILED_RESULTS.contains(&result.as_str()) {
+            return Ok(());
+        }
+        let id = match self.resolve_pipe_id(unit_name.as_str()) {
+            Some(id) => id,
+            None => return Ok(()),
+        };
+        let state = self.states.entry(id.to_owned()).or_default();
+        // failed state already published with properties change
+        if state.active_state == "failed" {
+            return Ok(());
+        }
+        let event = PipeEvent {
+            id,
+            event_type: PipeEventType::Failed,
+            timestamp: unix_timestamp_micros(),
+            active_state: state.active_state.to_owned(),
+            sub_state: state.sub_state.to_owned(),
+            exit_code: None,
+            exit_reason: None,
+            job_result: Some(result),
+        };
+        self.publish(event);
+        Ok(())
+    }
+
+    fn fill_exit_status(event: &mut PipeEvent, message: &zbus::Message) {
+        let path = match message.path() {
+            Some(path) => zvariant::OwnedObjectPath::from(path.to_owned()),
+            None => return,
+        };
+        let service = match build_blocking_service_proxy(path) {
+            Ok(service) => service,
+            Err(err) => {
+                warn!(
+                    pipe_id = event.id.as_str(),
+                    "read exit status failed, error: {:#?}", err
+                );
+                return;
+            }
+        };
+        event.exit_code = service.exec_main_status().ok();
+        event.exit_reason = service.exec_main_code().ok().and_then(exit_reason);
+    }
+
+    fn publish(&self, event: PipeEvent) {
+        info!(
+            pipe_id = event.id.as_str(),
+            event = %event.event_type,
+            "pipe event"
+        );
+        // no active subscriber
+        let _ = self.tx.send(event);
+    }
+}